@@ -0,0 +1,78 @@
+use crate::db::{Db, Result};
+use crate::models::{AdStatus, JobAd};
+use crate::opener::Opener;
+use crate::reports;
+use crate::undo::PreviousStatus;
+
+/// Which of an ad's links an open action goes to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AdLink {
+    /// The direct application link when there is one, otherwise the ad page ("open").
+    Apply,
+    /// Always the ad page ("open_ad").
+    Page,
+    /// Only the direct application link ("apply_direct").
+    Direct,
+}
+
+impl AdLink {
+    pub fn from_action(action: &str) -> Option<Self> {
+        match action {
+            "open" => Some(AdLink::Apply),
+            "open_ad" => Some(AdLink::Page),
+            "apply_direct" => Some(AdLink::Direct),
+            _ => None,
+        }
+    }
+
+    pub fn of(self, ad: &JobAd) -> Option<&str> {
+        match self {
+            AdLink::Apply => ad.apply_link(),
+            AdLink::Page => ad.webpage_url.as_deref(),
+            AdLink::Direct => ad.application_details.as_ref().and_then(|d| d.url.as_deref()),
+        }.filter(|u| !u.is_empty())
+    }
+}
+
+/// Opens `link` of `ad` without its tracking parameters. Ok(false) when the ad has no such link.
+pub fn open_ad_link(opener: &dyn Opener, ad: &JobAd, link: AdLink) -> anyhow::Result<bool> {
+    let Some(url) = link.of(ad) else { return Ok(false) };
+    opener.open(&reports::clean_url(url))?;
+    Ok(true)
+}
+
+/// Opens a new mail with the ad's headline as subject and `reports::ad_share_text` as body.
+pub fn email_ad(opener: &dyn Opener, ad: &JobAd) -> anyhow::Result<()> {
+    opener.open(&reports::mailto_link(&ad.headline, &reports::ad_share_text(ad)))
+}
+
+/// What "Öppna & markera sökt" did.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OpenApplied {
+    /// The link was opened and the ad is Applied; `previous` is for undo.
+    Marked { previous: PreviousStatus },
+    /// The ad was already Applied; the link was opened and its application date kept.
+    AlreadyApplied,
+    /// The ad has neither an application link nor an ad page.
+    NoLink,
+    /// The browser didn't start, so the ad isn't marked either.
+    OpenFailed,
+    NotFound,
+}
+
+/// Opens the ad's application link (the ad page when it has none) and marks the ad Applied
+/// with `applied_at` now. Nothing is marked unless the link could be opened.
+pub async fn open_and_mark_applied(db: &Db, id: &str, opener: &dyn Opener) -> Result<OpenApplied> {
+    let Some(ad) = db.get_job_ad(id).await? else { return Ok(OpenApplied::NotFound) };
+    match open_ad_link(opener, &ad, AdLink::Apply) {
+        Ok(true) => {}
+        Ok(false) => return Ok(OpenApplied::NoLink),
+        Err(e) => {
+            tracing::warn!("Kunde inte öppna ansökan för {}: {:#}", id, e);
+            return Ok(OpenApplied::OpenFailed);
+        }
+    }
+    if ad.is_applied() { return Ok(OpenApplied::AlreadyApplied); }
+    db.update_ad_status(id, Some(AdStatus::Applied)).await?;
+    Ok(OpenApplied::Marked { previous: PreviousStatus::of(&ad) })
+}
@@ -4,12 +4,89 @@ use async_openai::{
         ChatCompletionRequestSystemMessageArgs, ChatCompletionRequestUserMessageArgs,
         CreateChatCompletionRequestArgs,
     },
+    error::OpenAIError,
     Client,
 };
 use anyhow::Result;
+use std::future::Future;
+use std::time::Duration;
+
+/// Default system prompt; `{max}` is replaced with the top of the rating scale.
+pub const DEFAULT_SYSTEM_PROMPT: &str = "You are a career advisor assistant. You rate job matches from 1 to {max}. Output only the number.";
+pub const DEFAULT_SCALE_MAX: u8 = 10;
+pub const DEFAULT_MODEL: &str = "llama3";
+/// Extra attempts for a chat request that failed transiently (see `is_transient`).
+pub const DEFAULT_RETRIES: u32 = 2;
+/// Ratings `rate_batch` runs at once unless the settings say otherwise.
+pub const DEFAULT_CONCURRENCY: u32 = 3;
+const RETRY_FIRST_DELAY: Duration = Duration::from_secs(2);
+
+/// Why a rating failed. A missing model gets its own variant so the user sees "install or
+/// pick another model" instead of a rating of 0 that looks like a bad match.
+#[derive(Debug, thiserror::Error)]
+pub enum AiError {
+    #[error("Modellen '{model}' saknas, tillgängliga: {}", if available.is_empty() { "inga".to_string() } else { available.join(", ") })]
+    ModelNotFound { model: String, available: Vec<String> },
+    #[error("AI-anrop misslyckades: {0}")]
+    Api(#[from] OpenAIError),
+}
+
+/// Ollama answers an unknown model with 404 and `model "x" not found`; OpenAI-style servers
+/// say "model ... does not exist". Matching on the text works for both.
+pub fn is_model_not_found(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("model") && (message.contains("not found") || message.contains("does not exist"))
+}
+
+/// Whether a failed request is worth repeating: connection errors, timeouts and 5xx. Ollama
+/// refuses connections or answers 5xx while it loads a model on the first request of a
+/// session. async-openai turns a 5xx body it can't parse into an `ApiError` without type
+/// or code, while real 4xx answers carry a type, so those are not retried.
+pub fn is_transient(err: &OpenAIError) -> bool {
+    match err {
+        OpenAIError::Reqwest(e) => e.is_connect() || e.is_timeout() || e.status().is_some_and(|s| s.is_server_error()),
+        OpenAIError::ApiError(e) => e.r#type.is_none() && e.code.is_none() && !is_model_not_found(&e.message),
+        _ => false,
+    }
+}
+
+/// Runs `op`, repeating it up to `retries` more times while `transient` says the error is
+/// temporary. The wait starts at `first_delay` and doubles per attempt.
+pub async fn retry_transient<T, E, F, Fut>(retries: u32, first_delay: Duration, transient: impl Fn(&E) -> bool, mut op: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut delay = first_delay;
+    let mut attempt = 0;
+    loop {
+        match op().await {
+            Err(e) if attempt < retries && transient(&e) => {
+                attempt += 1;
+                tracing::warn!("AI-anrop misslyckades tillfälligt, försök {} av {} om {:?}", attempt, retries, delay);
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+            result => return result,
+        }
+    }
+}
 
 pub struct AiRanker {
     client: Client<async_openai::config::OpenAIConfig>,
+    system_prompt: String,
+    scale_max: u8,
+    retries: u32,
+}
+
+/// Extracts the first whole number from the model output and clamps it to `[0, scale_max]`.
+/// Reading the full number (not just the first digit) keeps "10" from becoming 1.
+pub fn parse_rating(content: &str, scale_max: u8) -> u8 {
+    let digits: String = content.chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse::<u32>().map(|n| n.min(scale_max as u32) as u8).unwrap_or(0)
 }
 
 impl AiRanker {
@@ -20,22 +97,56 @@ impl AiRanker {
         
         Ok(Self {
             client: Client::with_config(config),
+            system_prompt: DEFAULT_SYSTEM_PROMPT.to_string(),
+            scale_max: DEFAULT_SCALE_MAX,
+            retries: DEFAULT_RETRIES,
         })
     }
 
-    pub async fn rate_job(&self, ad: &JobAd, my_profile: &str) -> Result<u8> {
+    /// Routes the AI requests through the same proxy as the job search.
+    pub fn with_proxy(mut self, proxy: Option<&str>) -> Result<Self> {
+        if proxy.is_some_and(|p| !p.trim().is_empty()) {
+            self.client = self.client.with_http_client(crate::api::http_client(proxy)?);
+        }
+        Ok(self)
+    }
+
+    /// Overrides the system prompt and the rating scale (e.g. 1–5 for stricter local models).
+    /// An empty prompt keeps the default.
+    pub fn with_scoring(mut self, system_prompt: &str, scale_max: u8) -> Self {
+        if !system_prompt.trim().is_empty() {
+            self.system_prompt = system_prompt.to_string();
+        }
+        self.scale_max = scale_max.max(1);
+        self
+    }
+
+    /// How many times a transiently failed rating is retried (0 = fail at once).
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Lists the model ids on the configured endpoint; used by the connectivity self-test
+    /// and to suggest alternatives when the configured model is missing.
+    pub async fn available_models(&self) -> Result<Vec<String>, OpenAIError> {
+        let models = self.client.models().list().await?;
+        Ok(models.data.into_iter().map(|m| m.id).collect())
+    }
+
+    pub async fn rate_job(&self, ad: &JobAd, my_profile: &str) -> Result<u8, AiError> {
         let description = ad.description.as_ref().and_then(|d| d.text.as_ref()).map(|s| s.as_str()).unwrap_or("");
         
         let prompt = format!(
-            "Rate how well this job matches my profile. Output ONLY a single number from 1 to 10.\n\nMy Profile:\n{}\n\nJob Headline: {}\nJob Description: {}",
-            my_profile, ad.headline, description
+            "Rate how well this job matches my profile. Output ONLY a single number from 1 to {}.\n\nMy Profile:\n{}\n\nJob Headline: {}\nJob Description: {}",
+            self.scale_max, my_profile, ad.headline, description
         );
 
         let request = CreateChatCompletionRequestArgs::default()
-            .model("llama3")
+            .model(DEFAULT_MODEL)
             .messages([
                 ChatCompletionRequestSystemMessageArgs::default()
-                    .content("You are a career advisor assistant. You rate job matches from 1 to 10. Output only the digit.")
+                    .content(self.system_prompt.replace("{max}", &self.scale_max.to_string()))
                     .build()? 
                     .into(),
                 ChatCompletionRequestUserMessageArgs::default()
@@ -46,14 +157,163 @@ impl AiRanker {
             .max_tokens(10u32)
             .build()?;
 
-        let response = self.client.chat().create(request).await?;
+        let response = match retry_transient(self.retries, RETRY_FIRST_DELAY, is_transient, || {
+            let request = request.clone();
+            async move { self.client.chat().create(request).await }
+        }).await {
+            Ok(r) => r,
+            Err(e) if is_model_not_found(&e.to_string()) => {
+                // Best effort: a server that can't list models still gets the clear error.
+                let available = self.available_models().await.unwrap_or_default();
+                let err = AiError::ModelNotFound { model: DEFAULT_MODEL.to_string(), available };
+                tracing::error!("{}", err);
+                return Err(err);
+            }
+            Err(e) => return Err(e.into()),
+        };
         let content = response.choices[0].message.content.clone().unwrap_or_default();
         
-        let rating = content.trim().chars()
-            .find(|c| c.is_ascii_digit())
-            .and_then(|c| c.to_digit(10))
-            .unwrap_or(0) as u8;
+        Ok(parse_rating(&content, self.scale_max))
+    }
+}
+
+/// Outcome of `rate_batch`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BatchTally {
+    pub rated: usize,
+    pub failed: usize,
+    /// Stopped by `cancel` before every ad was rated.
+    pub cancelled: bool,
+}
+
+/// Rates `ads` with `rate`, at most `concurrency` at a time so a small local GPU isn't
+/// swamped. Each result goes to `on_rated` as soon as it arrives, so the caller can persist
+/// it and nothing already rated is lost on cancel. Setting `cancel` to true starts no new
+/// requests and aborts the ones in flight.
+pub async fn rate_batch<R, RFut, F, FFut>(ads: Vec<JobAd>, concurrency: usize, mut cancel: tokio::sync::watch::Receiver<bool>, rate: R, mut on_rated: F) -> BatchTally
+where
+    R: Fn(JobAd) -> RFut,
+    RFut: Future<Output = Result<u8, AiError>> + Send + 'static,
+    F: FnMut(String, Result<u8, AiError>) -> FFut,
+    FFut: Future<Output = ()>,
+{
+    let mut pending = ads.into_iter();
+    let mut in_flight = tokio::task::JoinSet::new();
+    let mut tally = BatchTally::default();
+    loop {
+        while in_flight.len() < concurrency.max(1) && !*cancel.borrow() {
+            let Some(ad) = pending.next() else { break };
+            let (id, rating) = (ad.id.clone(), rate(ad));
+            in_flight.spawn(async move { (id, rating.await) });
+        }
+        if in_flight.is_empty() { break; }
+        tokio::select! {
+            Some(joined) = in_flight.join_next() => match joined {
+                Ok((id, result)) => {
+                    if result.is_ok() { tally.rated += 1; } else { tally.failed += 1; }
+                    on_rated(id, result).await;
+                }
+                Err(e) => { tracing::warn!("AI-betygsättning avbröts oväntat: {}", e); tally.failed += 1; }
+            },
+            // A dropped sender disables this branch rather than firing forever
+            Ok(()) = cancel.changed() => {}
+        }
+        if *cancel.borrow() {
+            in_flight.abort_all();
+            tally.cancelled = true;
+            break;
+        }
+    }
+    tally
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rating_reads_whole_number_and_clamps() {
+        assert_eq!(parse_rating("10", 10), 10);
+        assert_eq!(parse_rating("Betyg: 7/10", 10), 7);
+        assert_eq!(parse_rating("8", 5), 5);
+        assert_eq!(parse_rating("no idea", 10), 0);
+    }
+
+    #[test]
+    fn model_not_found_is_recognized_and_lists_alternatives() {
+        assert!(is_model_not_found("model \"llama3\" not found, try pulling it first"));
+        assert!(is_model_not_found("The model `gpt-5` does not exist"));
+        assert!(!is_model_not_found("connection refused"));
+        let err = AiError::ModelNotFound { model: "llama3".to_string(), available: vec!["mistral".to_string(), "qwen2".to_string()] };
+        assert_eq!(err.to_string(), "Modellen 'llama3' saknas, tillgängliga: mistral, qwen2");
+    }
+
+    fn api_error(r#type: Option<&str>, message: &str) -> OpenAIError {
+        OpenAIError::ApiError(async_openai::error::ApiError { message: message.to_string(), r#type: r#type.map(str::to_string), param: None, code: None })
+    }
+
+    #[test]
+    fn server_errors_are_transient_client_errors_are_not() {
+        assert!(is_transient(&api_error(None, "502 Bad Gateway")));
+        assert!(!is_transient(&api_error(Some("invalid_request_error"), "max_tokens too large")));
+        assert!(!is_transient(&api_error(None, "model \"llama3\" not found")));
+    }
+
+    #[tokio::test]
+    async fn retry_recovers_after_one_transient_failure() {
+        let calls = std::cell::Cell::new(0);
+        let mock = || {
+            calls.set(calls.get() + 1);
+            let n = calls.get();
+            async move { if n == 1 { Err(api_error(None, "model is loading")) } else { Ok(7u8) } }
+        };
+        let rating = retry_transient(2, Duration::ZERO, is_transient, mock).await;
+        assert_eq!(rating.ok(), Some(7));
+        assert_eq!(calls.get(), 2);
+
+        calls.set(0);
+        let client_error = || { calls.set(calls.get() + 1); async { Err::<u8, _>(api_error(Some("invalid_request_error"), "bad")) } };
+        assert!(retry_transient(2, Duration::ZERO, is_transient, client_error).await.is_err());
+        assert_eq!(calls.get(), 1);
+    }
+
+    fn ads(n: usize) -> Vec<JobAd> {
+        (0..n).map(|i| serde_json::from_value(serde_json::json!({ "id": i.to_string(), "headline": "x", "publication_date": "2026-01-01" })).unwrap()).collect()
+    }
+
+    #[tokio::test]
+    async fn batch_never_exceeds_concurrency() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        let (running, peak) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+        let (_tx, rx) = tokio::sync::watch::channel(false);
+        let rate = |_ad: JobAd| {
+            let (running, peak) = (running.clone(), peak.clone());
+            async move {
+                peak.fetch_max(running.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                Ok(5)
+            }
+        };
+        let tally = rate_batch(ads(7), 2, rx, rate, |_, _| async {}).await;
+        assert_eq!(tally, BatchTally { rated: 7, failed: 0, cancelled: false });
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
 
-        Ok(rating)
+    #[tokio::test]
+    async fn cancelled_batch_keeps_finished_ratings() {
+        let (tx, rx) = tokio::sync::watch::channel(false);
+        let mut saved = Vec::new();
+        let rate = |_ad: JobAd| async { tokio::time::sleep(Duration::from_millis(5)).await; Ok(8) };
+        let tally = rate_batch(ads(10), 2, rx, rate, |id, result| {
+            saved.push((id, result.ok()));
+            if saved.len() == 3 { let _ = tx.send(true); }
+            async {}
+        }).await;
+        assert!(tally.cancelled);
+        assert_eq!(tally.rated, 3);
+        assert_eq!(saved.len(), 3);
+        assert!(saved.iter().all(|(_, r)| *r == Some(8)));
     }
 }
\ No newline at end of file
@@ -2,10 +2,64 @@ use reqwest::Client;
 use crate::models::JobAd;
 use serde_json::Value;
 use anyhow::{Result, Context};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Longest `Retry-After` we wait out ourselves before retrying once; anything longer is
+/// returned as `ApiError::RateLimited` so the user isn't stuck watching a spinner.
+pub const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(10);
+/// Used when a 429 comes without a (parseable) `Retry-After`.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(5);
+/// Sent on every request so JobTech can tell our traffic apart; override with `with_user_agent`.
+pub const DEFAULT_USER_AGENT: &str = concat!("Jobseeker/", env!("CARGO_PKG_VERSION"));
+
+/// API failures the caller handles differently from a generic error. Returned inside the
+/// `anyhow::Error`; find it with `downcast_ref::<ApiError>()`.
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {
+    #[error("API överbelastad, försök igen om {} s", retry_after.as_secs())]
+    RateLimited { retry_after: Duration },
+}
+
+/// `Retry-After` in seconds. The HTTP-date form isn't used by JobTech and falls back to the default.
+fn retry_after(response: &reqwest::Response) -> Duration {
+    response.headers().get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_RETRY_AFTER)
+}
 
 pub struct JobSearchClient {
     client: Client,
     base_url: String,
+    /// Taxonomy concept id sent as `occupation-field`; None searches every field.
+    occupation_field: Option<String>,
+    user_agent: String,
+    /// Least time between two requests; the first one goes out at once.
+    request_delay: Duration,
+    last_request: tokio::sync::Mutex<Option<tokio::time::Instant>>,
+}
+
+/// Common occupation fields (yrkesområden) as (label, JobTech taxonomy concept id). The
+/// search only gets the id; the label is for the settings dropdown.
+pub const OCCUPATION_FIELDS: &[(&str, &str)] = &[
+    ("Data/IT", "apaJ_2ja_LuF"),
+    ("Administration, ekonomi, juridik", "X82t_awd_Qyc"),
+    ("Bygg och anläggning", "j7Cq_ZJe_GkT"),
+    ("Försäljning, inköp, marknadsföring", "RPTn_bxG_ExZ"),
+    ("Hotell, restaurang, storhushåll", "ScKy_FHB_7wT"),
+    ("Hälso- och sjukvård", "NYW6_mP6_vwf"),
+    ("Pedagogik", "MVqp_eS8_kDZ"),
+    ("Transport, distribution, lager", "ASGV_zcE_bWf"),
+];
+
+pub fn occupation_field_label(id: &str) -> Option<&'static str> {
+    OCCUPATION_FIELDS.iter().find(|(_, i)| *i == id).map(|(label, _)| *label)
+}
+
+pub fn occupation_field_id(label: &str) -> Option<&'static str> {
+    OCCUPATION_FIELDS.iter().find(|(l, _)| *l == label).map(|(_, id)| *id)
 }
 
 const MUNICIPALITIES: &[(&str, &str)] = &[
@@ -41,14 +95,166 @@ const MUNICIPALITIES: &[(&str, &str)] = &[
     ("öckerö", "1407"), ("stenungsund", "1415"), ("tjörn", "1419"),
 ];
 
+/// Regions (län) as (name, län code). A kommun code starts with its län code, so the
+/// region of any entry in `MUNICIPALITIES` follows from its first two digits.
+pub const REGIONS: &[(&str, &str)] = &[
+    ("Skåne", "12"),
+    ("Stockholm", "01"),
+    ("Uppsala", "03"),
+    ("Västra Götaland", "14"),
+    ("Halland", "13"),
+    ("Östergötland", "05"),
+];
+
+/// Every kommun code the municipality table has for `region` (case-insensitive), in table
+/// order. Skåne is complete; the other regions only have their larger kommuner.
+pub fn region_municipality_codes(region: &str) -> Vec<String> {
+    let Some((_, prefix)) = REGIONS.iter().find(|(name, _)| name.to_lowercase() == region.trim().to_lowercase()) else { return Vec::new() };
+    MUNICIPALITIES.iter()
+        .filter(|(_, code)| code.starts_with(prefix))
+        .map(|(_, code)| code.to_string())
+        .collect()
+}
+
+/// jobtech accepts `published-after` as a number of minutes back from now, which avoids
+/// guessing which timezone a bare datetime would be interpreted in. One extra minute of
+/// overlap makes sure nothing published right at the watermark slips through.
+pub fn published_after_minutes(since: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+    ((now - since).num_minutes() + 1).max(1)
+}
+
+/// HTTP client for all outgoing requests. With no explicit proxy reqwest still honours the
+/// standard `HTTP_PROXY`/`HTTPS_PROXY` environment variables.
+pub fn http_client(proxy: Option<&str>) -> Result<Client> {
+    match proxy.map(str::trim).filter(|p| !p.is_empty()) {
+        Some(url) => {
+            let proxy = reqwest::Proxy::all(url).with_context(|| format!("Ogiltig proxyadress: {}", url))?;
+            Client::builder().proxy(proxy).build().context("Kunde inte skapa HTTP-klient med proxy")
+        }
+        None => Ok(Client::new()),
+    }
+}
+
+/// Turns one search hit (or single-ad response) into a `JobAd`. Unusual but valid hits are
+/// kept: a missing or null headline gets a placeholder and a missing publication date
+/// becomes the time the ad was fetched.
+pub fn ad_from_hit(hit: &Value) -> Option<JobAd> {
+    let mut value = hit.clone();
+    if let Some(obj) = value.as_object_mut() {
+        for key in ["headline", "publication_date"] {
+            if obj.get(key).is_some_and(Value::is_null) { obj.remove(key); }
+        }
+    }
+    match serde_json::from_value::<JobAd>(value) {
+        Ok(mut ad) => {
+            ad.webpage_url = hit["webpage_url"].as_str().map(|s| s.to_string());
+            ad.update_experience_years();
+            ad.update_salary_range();
+            ad.update_language();
+            if ad.publication_date.is_empty() {
+                ad.publication_date = ad.internal_created_at.format("%Y-%m-%dT%H:%M:%S").to_string();
+            }
+            // Extrahera working_hours_type om det saknas i automatisk deserialisering
+            if ad.working_hours_type.is_none() {
+                if let Some(label) = hit["working_hours_type"]["label"].as_str() {
+                    ad.working_hours_type = Some(crate::models::WorkingHours { label: Some(label.to_string()) });
+                }
+            }
+            Some(ad)
+        }
+        Err(e) => {
+            tracing::debug!("Skipping unparseable job ad {}: {}", hit["id"], e);
+            None
+        }
+    }
+}
+
 impl JobSearchClient {
     pub fn new() -> Self {
         Self {
             client: Client::new(),
             base_url: "https://jobsearch.api.jobtechdev.se".to_string(),
+            occupation_field: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            request_delay: Duration::ZERO,
+            last_request: tokio::sync::Mutex::new(None),
         }
     }
 
+    /// Scopes every search to one occupation field (see `OCCUPATION_FIELDS`). Free-text
+    /// `q` still applies within it.
+    pub fn with_occupation_field(mut self, id: Option<&str>) -> Self {
+        self.occupation_field = id.map(str::trim).filter(|i| !i.is_empty()).map(|i| i.to_string());
+        self
+    }
+
+    /// Points the client at another server with the same API, e.g. a local mock in tests.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Replaces `DEFAULT_USER_AGENT`, e.g. to add a contact address.
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// Spaces requests at least `delay` apart so a search over many municipalities and
+    /// keywords doesn't arrive as one burst. Requests are sent one at a time, so this is the
+    /// whole pacing; a search with a single call never waits.
+    pub fn with_request_delay(mut self, delay: Duration) -> Self {
+        self.request_delay = delay;
+        self
+    }
+
+    /// Waits until `request_delay` has passed since the previous request.
+    async fn pace(&self) {
+        let mut last = self.last_request.lock().await;
+        if let Some(at) = *last {
+            tokio::time::sleep_until(at + self.request_delay).await;
+        }
+        *last = Some(tokio::time::Instant::now());
+    }
+
+    /// GET with the headers every JobTech request carries.
+    fn get(&self, url: &str) -> reqwest::RequestBuilder {
+        self.client.get(url)
+            .header("accept", "application/json")
+            .header(reqwest::header::USER_AGENT, &self.user_agent)
+    }
+
+    /// Sends the request; on 429 waits out a short `Retry-After` and tries once more, a
+    /// longer one (or a second 429) becomes `ApiError::RateLimited`. Other statuses are
+    /// left to the caller. This is separate from any retrying the caller does on 5xx. The retry
+    /// is paced like any other request, so a `Retry-After` shorter than `request_delay` (or 0)
+    /// doesn't send it sooner than the client's spacing allows.
+    async fn send_respecting_rate_limit(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let retry = request.try_clone();
+        self.pace().await;
+        let response = request.send().await?;
+        if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Ok(response);
+        }
+        let wait = retry_after(&response);
+        tracing::warn!("JobTech API svarade 429, Retry-After {:?}", wait);
+        let Some(retry) = retry.filter(|_| wait <= MAX_RATE_LIMIT_WAIT) else {
+            return Err(ApiError::RateLimited { retry_after: wait }.into());
+        };
+        tokio::time::sleep(wait).await;
+        self.pace().await;
+        let response = retry.send().await?;
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ApiError::RateLimited { retry_after: retry_after(&response) }.into());
+        }
+        Ok(response)
+    }
+
+    /// Like `new`, but sends every request through `proxy` when one is given.
+    pub fn with_proxy(proxy: Option<&str>) -> Result<Self> {
+        Ok(Self { client: http_client(proxy)?, ..Self::new() })
+    }
+
     pub fn get_municipality_code(name: &str) -> Option<&'static str> {
         let name_lower = name.to_lowercase();
         MUNICIPALITIES.iter()
@@ -68,29 +274,101 @@ impl JobSearchClient {
             })
     }
 
+    /// Canonical code for a municipality given either as a code ("1283") or a name
+    /// ("Helsingborg", "Helsingborgs kommun" works too). JobTech returns both forms in
+    /// `workplace_address.municipality`, so comparisons should go through this.
+    pub fn municipality_code(value: &str) -> Option<String> {
+        let value = value.trim();
+        if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()) {
+            return Some(value.to_string());
+        }
+        let lower = value.to_lowercase();
+        Self::get_municipality_code(&lower)
+            .or_else(|| lower.strip_suffix(" kommun").and_then(|n| Self::get_municipality_code(n).or_else(|| Self::get_municipality_code(n.strip_suffix('s')?))))
+            .map(|c| c.to_string())
+    }
+
+    /// Municipality codes for a comma-separated list of codes, kommun names ("Malmö",
+    /// "Helsingborgs kommun") and regions ("Skåne", "Skåne län"), which expand to all of their
+    /// kommuner. Input order is kept and each code appears once, so overlapping entries don't
+    /// cost extra API calls. Unknown names are dropped with a warning.
     pub fn parse_locations(input: &str) -> Vec<String> {
-        input.split(',')
-            .map(|s| s.trim())
-            .filter(|s| !s.is_empty())
-            .map(|s| {
-                // If it looks like a code (digits), keep it. Otherwise try to resolve name.
-                if s.chars().all(char::is_numeric) {
-                    s.to_string()
-                } else {
-                    Self::get_municipality_code(s).map(|c| c.to_string()).unwrap_or_default()
+        let mut seen = std::collections::HashSet::new();
+        let mut codes = Vec::new();
+        for part in input.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let resolved = match Self::municipality_code(part) {
+                Some(code) => vec![code],
+                None => {
+                    let lower = part.to_lowercase();
+                    let region = lower.strip_suffix(" län").unwrap_or(&lower);
+                    let mut region_codes = region_municipality_codes(region);
+                    if region_codes.is_empty() { if let Some(name) = region.strip_suffix('s') { region_codes = region_municipality_codes(name); } }
+                    if region_codes.is_empty() { tracing::warn!("Okänd ort '{}' ignoreras i sökningen", part); }
+                    region_codes
                 }
-            })
-            .filter(|s| !s.is_empty())
-            .collect()
+            };
+            codes.extend(resolved.into_iter().filter(|c| seen.insert(c.clone())));
+        }
+        codes
+    }
+
+    /// Number of ads matching `query` in `municipalities` (empty = the whole country), from
+    /// `total.value` with `limit=0`, so no ads are transferred. One call per municipality;
+    /// an ad has one municipality, so the counts add up.
+    pub async fn count_hits(&self, query: &str, municipalities: &[String]) -> Result<u64> {
+        let url = format!("{}/search", self.base_url);
+        let mut total = 0;
+        let scopes: Vec<Option<&String>> = if municipalities.is_empty() { vec![None] } else { municipalities.iter().map(Some).collect() };
+        for m in scopes {
+            let mut params = vec![("q", query.to_string()), ("limit", "0".to_string())];
+            if let Some(field) = &self.occupation_field { params.push(("occupation-field", field.clone())); }
+            if let Some(m) = m { params.push(("municipality", m.clone())); }
+            let response = self.send_respecting_rate_limit(self.get(&url).query(&params))
+                .await
+                .with_context(|| format!("Failed to count hits for '{}'", query))?;
+            if !response.status().is_success() {
+                return Err(anyhow::anyhow!("API Error: {}", response.status()));
+            }
+            let json: Value = response.json().await.context("Failed to parse JSON response")?;
+            total += json["total"]["value"].as_u64().context("No 'total' in response")?;
+        }
+        Ok(total)
+    }
+
+    /// Smallest possible request (`limit=1`) for the connectivity self-test. Returns the HTTP
+    /// status without interpreting the body.
+    pub async fn ping(&self) -> Result<u16> {
+        let url = format!("{}/search", self.base_url);
+        let response = self.get(&url)
+            .query(&[("q", "it"), ("limit", "1")])
+            .send()
+            .await
+            .context("Failed to send request to JobSearch API")?;
+        Ok(response.status().as_u16())
+    }
+
+    /// Fetches one ad from the single-ad endpoint, which always has the full description
+    /// (search hits are sometimes cut short).
+    pub async fn get_ad(&self, id: &str) -> Result<JobAd> {
+        let url = format!("{}/ad/{}", self.base_url, urlencoding::encode(id));
+        let response = self.send_respecting_rate_limit(self.get(&url))
+            .await
+            .with_context(|| format!("Failed to fetch ad {}", id))?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("API Error: {} for ad {}", response.status(), id));
+        }
+        let json: Value = response.json().await.context("Failed to parse JSON response")?;
+        ad_from_hit(&json).with_context(|| format!("Failed to parse job ad {}", id))
     }
 
     /// ⚠️ GUARDED: JobTech API requires numeric municipality codes for filtering.
     /// Do not change this to send names directly. Use JobSearchClient::get_municipality_code
     /// to resolve names before calling search.
-    pub async fn search(&self, query: &str, municipalities: &[String], limit: u32) -> Result<Vec<JobAd>> {
+    /// `published_after` limits the result to ads published after that moment (incremental search).
+    pub async fn search(&self, query: &str, municipalities: &[String], limit: u32, published_after: Option<DateTime<Utc>>) -> Result<Vec<JobAd>> {
         if municipalities.len() > 1 {
             // Multiple municipalities: do separate API calls per municipality and merge results
-            return self.search_multi_municipalities(query, municipalities, limit).await;
+            return self.search_multi_municipalities(query, municipalities, limit, published_after).await;
         }
         
         // Single municipality (or empty): use original logic
@@ -102,6 +380,12 @@ impl JobSearchClient {
             ("q", query.to_string()),
             ("limit", limit.to_string()),
         ];
+        if let Some(since) = published_after {
+            params.push(("published-after", published_after_minutes(since, Utc::now()).to_string()));
+        }
+        if let Some(field) = &self.occupation_field {
+            params.push(("occupation-field", field.clone()));
+        }
 
         for m in municipalities {
             if !m.is_empty() {
@@ -110,9 +394,7 @@ impl JobSearchClient {
         }
 
         let url = format!("{}/search", self.base_url);
-        let request = self.client.get(&url)
-            .header("accept", "application/json")
-            .query(&params);
+        let request = self.get(&url).query(&params);
         
         // Log the full URL for debugging (with parameters)
         if let Some(req_builder) = request.try_clone() {
@@ -121,8 +403,7 @@ impl JobSearchClient {
             }
         }
 
-        let response = request
-            .send()
+        let response = self.send_respecting_rate_limit(request)
             .await
             .context("Failed to send request to JobSearch API")?;
 
@@ -142,38 +423,10 @@ impl JobSearchClient {
 
         tracing::info!("API found {} raw hits", hits.len());
 
-        let mut ads = Vec::new();
-        for hit in hits {
-            let ad_val = hit.clone();
-
-            // Extract webpage_url from root if not present in nested structs
-            let webpage_url = hit["webpage_url"].as_str().map(|s| s.to_string());
-
-            match serde_json::from_value::<JobAd>(ad_val.clone()) {
-                Ok(mut ad) => {
-                    ad.webpage_url = webpage_url;
-
-                    // Extrahera working_hours_type om det saknas i automatisk deserialisering
-                    if ad.working_hours_type.is_none() {
-                        if let Some(label) = hit["working_hours_type"]["label"].as_str() {
-                            ad.working_hours_type = Some(crate::models::WorkingHours {
-                                label: Some(label.to_string()),
-                            });
-                        }
-                    }
-
-                    ads.push(ad);
-                },
-                Err(e) => {
-                    eprintln!("Error parsing job ad: {}. Value: {:?}", e, hit);
-                }
-            }
-        }
-
-        Ok(ads)
+        Ok(hits.iter().filter_map(ad_from_hit).collect())
     }
 
-    async fn search_multi_municipalities(&self, query: &str, municipalities: &[String], limit_per_municipality: u32) -> Result<Vec<JobAd>> {
+    async fn search_multi_municipalities(&self, query: &str, municipalities: &[String], limit_per_municipality: u32, published_after: Option<DateTime<Utc>>) -> Result<Vec<JobAd>> {
         use std::collections::HashSet;
         
         tracing::info!("Searching across {} municipalities (separate API calls)", municipalities.len());
@@ -185,19 +438,23 @@ impl JobSearchClient {
             
             // ⚠️ API CONSTRAINT: 'limit' must be <= 100 per call.
             // ⚠️ API CONSTRAINT: Do NOT add 'sort' parameter. It triggers 400 Bad Request.
-            let params = vec![
+            let mut params = vec![
                 ("q", query.to_string()),
                 ("limit", limit_per_municipality.to_string()),
                 ("municipality", m.to_string()),
             ];
+            if let Some(since) = published_after {
+                params.push(("published-after", published_after_minutes(since, Utc::now()).to_string()));
+            }
+            if let Some(field) = &self.occupation_field {
+                params.push(("occupation-field", field.clone()));
+            }
             
             let url = format!("{}/search", self.base_url);
             tracing::info!("Fetching for municipality {}: {}", m, url);
             
-            let response = self.client.get(&url)
-                .header("accept", "application/json")
-                .query(&params)
-                .send()
+            // A rate limit ends the whole search instead of skipping to the next municipality
+            let response = self.send_respecting_rate_limit(self.get(&url).query(&params))
                 .await
                 .with_context(|| format!("Failed to fetch for municipality {}", m))?;
             
@@ -210,25 +467,10 @@ impl JobSearchClient {
                 if let Some(hits) = json["hits"].as_array() {
                     tracing::info!("Municipality {}: {} hits", m, hits.len());
                     
-                    for hit in hits {
-                        let ad_val = hit.clone();
-                        let webpage_url = hit["webpage_url"].as_str().map(|s| s.to_string());
-                        
-                        if let Ok(mut ad) = serde_json::from_value::<JobAd>(ad_val.clone()) {
-                            ad.webpage_url = webpage_url;
-                            
-                            if ad.working_hours_type.is_none() {
-                                if let Some(label) = hit["working_hours_type"]["label"].as_str() {
-                                    ad.working_hours_type = Some(crate::models::WorkingHours {
-                                        label: Some(label.to_string()),
-                                    });
-                                }
-                            }
-                            
-                            // Deduplicate by ad ID
-                            if seen_ids.insert(ad.id.clone()) {
-                                all_ads.push(ad);
-                            }
+                    for ad in hits.iter().filter_map(ad_from_hit) {
+                        // Deduplicate by ad ID
+                        if seen_ids.insert(ad.id.clone()) {
+                            all_ads.push(ad);
                         }
                     }
                 }
@@ -263,4 +505,78 @@ mod tests {
         let parsed = JobSearchClient::parse_locations(" , 1283,  malmö  , ");
         assert_eq!(parsed, vec!["1283".to_string(), "1280".to_string()]);
     }
+
+    #[test]
+    fn parse_locations_handles_mixed_codes_names_and_regions() {
+        // Codes pass through, names resolve whatever the casing, a region expands and the
+        // unknown place is dropped
+        let parsed = JobSearchClient::parse_locations("1283, MALMÖ,  Helsingborgs kommun , Hallands län, unknownplace");
+        assert_eq!(parsed, vec!["1283", "1280", "1384", "1383", "1380"]);
+        assert_eq!(JobSearchClient::parse_locations("Halland"), JobSearchClient::parse_locations("hallands län"));
+    }
+
+    #[test]
+    fn parse_locations_dedupes_overlapping_entries() {
+        // Helsingborg three ways, and Malmö again inside Skåne
+        assert_eq!(JobSearchClient::parse_locations("1283, helsingborg, Helsingborg kommun"), vec!["1283"]);
+        let parsed = JobSearchClient::parse_locations("malmö, Skåne län");
+        assert_eq!(parsed.len(), 33);
+        assert_eq!(parsed[0], "1280");
+        assert_eq!(parsed.iter().filter(|c| *c == "1280").count(), 1);
+    }
+
+    #[test]
+    fn parse_locations_searches_each_kommun_once() {
+        // The same kommun by name, code and casing is one API call, not three
+        assert_eq!(JobSearchClient::parse_locations("malmö, 1280, Malmö"), vec!["1280"]);
+        assert_eq!(JobSearchClient::parse_locations("Lund, lund, LUND"), vec!["1281"]);
+        assert_eq!(JobSearchClient::parse_locations("1283, 1280, 1283, 1280"), vec!["1283", "1280"]);
+        // First-seen order is kept, so the same input always gives the same calls
+        assert_eq!(JobSearchClient::parse_locations("Lund, 1280, lund, Malmö kommun"), vec!["1281", "1280"]);
+    }
+
+    #[test]
+    fn parse_locations_drops_unknown_and_non_ascii_digits() {
+        assert!(JobSearchClient::parse_locations("unknownplace, ,").is_empty());
+        // Only ASCII digits are codes; anything else has to resolve as a name
+        assert!(JobSearchClient::parse_locations("١٢٨٣").is_empty());
+    }
+
+    #[test]
+    fn skane_expands_to_all_33_kommuner() {
+        let codes = region_municipality_codes("skåne");
+        assert_eq!(codes.len(), 33);
+        assert!(codes.iter().all(|c| c.starts_with("12")));
+        for expected in ["1283", "1280", "1281", "1290", "1271", "1214"] {
+            assert!(codes.contains(&expected.to_string()), "missing {}", expected);
+        }
+        assert_eq!(region_municipality_codes("Halland"), vec!["1384", "1383", "1380"]);
+        assert!(region_municipality_codes("Atlantis").is_empty());
+    }
+
+    #[test]
+    fn municipality_code_accepts_code_or_name() {
+        assert_eq!(JobSearchClient::municipality_code("1283").as_deref(), Some("1283"));
+        assert_eq!(JobSearchClient::municipality_code(" Helsingborg ").as_deref(), Some("1283"));
+        assert_eq!(JobSearchClient::municipality_code("Malmö kommun").as_deref(), Some("1280"));
+        assert_eq!(JobSearchClient::municipality_code("Helsingborgs kommun").as_deref(), Some("1283"));
+        assert_eq!(JobSearchClient::municipality_code("Atlantis"), None);
+    }
+
+    #[test]
+    fn published_after_minutes_covers_the_gap() {
+        let now = Utc::now();
+        assert_eq!(published_after_minutes(now - chrono::Duration::minutes(90), now), 91);
+        // A watermark in the future (clock skew) still yields a valid, positive value
+        assert_eq!(published_after_minutes(now + chrono::Duration::minutes(5), now), 1);
+    }
+
+    #[test]
+    fn hit_without_publication_date_or_headline_is_kept() {
+        let hit = serde_json::json!({ "id": "42", "headline": null, "webpage_url": "https://arbetsformedlingen.se/annons/42" });
+        let ad = ad_from_hit(&hit).expect("hit should be kept");
+        assert_eq!(ad.headline, "(ingen rubrik)");
+        assert!(ad.publication_date.starts_with(&ad.internal_created_at.format("%Y-%m-%d").to_string()));
+        assert_eq!(ad.webpage_url.as_deref(), Some("https://arbetsformedlingen.se/annons/42"));
+    }
 }
@@ -0,0 +1,52 @@
+use chrono::Datelike;
+
+/// Exports every ad of a past month to a JSON file, to keep the live database small by hand
+/// (the automatic archiving only looks at age). With `--remove` the month's new and rejected
+/// ads are then deleted; saved, applied, starred and pinned ads always stay. The database is
+/// copied to `jobseeker.redb.bak.<timestamp>` before anything is deleted. Close Jobseeker first.
+///
+/// Usage: archive --archive-month YYYY-MM [--remove] [--out FILE] [PATH]
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut month = None;
+    let mut remove = false;
+    let mut out = None;
+    let mut path = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--archive-month" => month = args.next(),
+            "--remove" => remove = true,
+            "--out" => out = args.next().map(std::path::PathBuf::from),
+            _ => path = Some(std::path::PathBuf::from(arg)),
+        }
+    }
+    let month = month.ok_or("Ange månad med --archive-month ÅÅÅÅ-MM")?;
+    let (year, mon) = month.split_once('-')
+        .and_then(|(y, m)| Some((y.parse::<i32>().ok()?, m.parse::<u32>().ok().filter(|m| (1..=12).contains(m))?)))
+        .ok_or_else(|| format!("Ogiltig månad '{}', använd ÅÅÅÅ-MM", month))?;
+    let today = chrono::Local::now().date_naive();
+    if (year, mon) >= (today.year(), today.month()) {
+        return Err(format!("{} är inte avslutad än; bara tidigare månader kan arkiveras", month).into());
+    }
+
+    let db_path = path.unwrap_or_else(Jobseeker::paths::db_path);
+    println!("Databas: {}", db_path.display());
+    let file_name = format!("jobseeker_arkiv_{:04}-{:02}.json", year, mon);
+    let out = out.unwrap_or_else(|| directories::UserDirs::new()
+        .and_then(|u| u.download_dir().map(|d| d.join(&file_name)))
+        .unwrap_or_else(|| std::path::PathBuf::from(&file_name)));
+
+    let db = Jobseeker::open_existing_db(&db_path).await?;
+    let written = db.export_month(year, mon, &out).await?;
+    println!("Skrev {} annonser till {}", written, out.display());
+    if !remove || written == 0 { return Ok(()); }
+
+    drop(db);
+    let backup = Jobseeker::paths::backup_db(&db_path)?;
+    println!("Säkerhetskopia: {}", backup.display());
+    let db = Jobseeker::open_existing_db(&db_path).await?;
+    let removed = db.remove_archived_month(year, mon).await?;
+    println!("Tog bort {} nya/avvisade annonser ur databasen; sparade och sökta finns kvar.", removed);
+    Ok(())
+}
@@ -0,0 +1,43 @@
+/// Checks that the database opens and can be read. `--stats` adds a snapshot for bug
+/// reports: ad count per status, oldest/newest fetch time and file size. Everything is read
+/// in one read transaction and nothing is written.
+///
+/// Usage: db_check [--stats] [PATH]. Exits with 1 when the database can't be opened or read.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut stats = false;
+    let mut path = None;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--stats" => stats = true,
+            _ => path = Some(std::path::PathBuf::from(arg)),
+        }
+    }
+    let db_path = path.unwrap_or_else(Jobseeker::paths::db_path);
+    println!("Databas: {}", db_path.display());
+
+    let db = Jobseeker::db::open_db_snapshot(&db_path)?;
+    drop(db.begin_read()?);
+    println!("OK: databasen går att öppna och läsa.");
+    if db.is_copy() { println!("(Jobseeker är öppet; kontrollen gjordes på en kopia av filen.)"); }
+    if !stats { return Ok(()); }
+
+    let corpus = Jobseeker::db::corpus_stats(&db)?;
+    println!("\nAnnonser totalt: {}", corpus.total);
+    for (status, n) in corpus.by_status {
+        let label = match status {
+            Jobseeker::models::AdStatus::New => "Nya",
+            Jobseeker::models::AdStatus::Rejected => "Avvisade",
+            Jobseeker::models::AdStatus::Bookmarked => "Sparade",
+            Jobseeker::models::AdStatus::ThumbsUp => "Tumme upp",
+            Jobseeker::models::AdStatus::Applied => "Sökta",
+        };
+        println!("  {:<10} {:>6}", label, n);
+    }
+    if corpus.unreadable > 0 { println!("  {:<10} {:>6}", "Oläsbara", corpus.unreadable); }
+    let fmt = |t: Option<chrono::DateTime<chrono::Utc>>| t.map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string()).unwrap_or_else(|| "–".to_string());
+    println!("Äldst hämtad: {}", fmt(corpus.oldest));
+    println!("Senast hämtad: {}", fmt(corpus.newest));
+    let size = std::fs::metadata(&db_path)?.len();
+    println!("Filstorlek: {} kB", size / 1024);
+    Ok(())
+}
@@ -0,0 +1,48 @@
+/// Finds ads stored more than once across all months (same headline and employer, open at
+/// overlapping times) and lists them with the record that would be kept: the most advanced
+/// status, then the newest. `--dry-run` (the default) only lists; `--fix` merges star, pin,
+/// tags and rating into the kept record and deletes the others after copying the database to `jobseeker.redb.bak.<timestamp>`, which
+/// `restore_backup` can put back. Reposts of a job later on are left alone. Close Jobseeker first.
+///
+/// Usage: dedupe [--dry-run | --fix] [PATH]
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut fix = false;
+    let mut path = None;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--fix" => fix = true,
+            "--dry-run" => fix = false,
+            _ => path = Some(std::path::PathBuf::from(arg)),
+        }
+    }
+    let db_path = path.unwrap_or_else(Jobseeker::paths::db_path);
+    println!("Databas: {}", db_path.display());
+
+    let db = Jobseeker::open_existing_db(&db_path).await?;
+    let plan = db.dedupe_ads(true).await?;
+    if plan.groups.is_empty() {
+        println!("Inga dubbletter hittades.");
+        return Ok(());
+    }
+    for (kept, removed) in &plan.groups {
+        let ad = db.get_job_ad(kept).await?;
+        let title = ad.as_ref().map(|a| format!("{} – {}", a.headline, a.employer.as_ref().and_then(|e| e.name.as_deref()).unwrap_or("okänd arbetsgivare"))).unwrap_or_default();
+        println!("{}", title);
+        println!("  behåll {}", kept);
+        for id in removed { println!("  ta bort {}", id); }
+    }
+    println!("{} dubbletter i {} grupper.", plan.removed_count(), plan.groups.len());
+    if !fix {
+        println!("Provkörning, inget ändrat. Kör med --fix för att ta bort.");
+        return Ok(());
+    }
+
+    drop(db);
+    let backup = Jobseeker::paths::backup_db(&db_path)?;
+    println!("Säkerhetskopia: {}", backup.display());
+    let db = Jobseeker::open_existing_db(&db_path).await?;
+    let done = db.dedupe_ads(false).await?;
+    println!("Tog bort {} dubbletter.", done.removed_count());
+    Ok(())
+}
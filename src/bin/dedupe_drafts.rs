@@ -0,0 +1,41 @@
+/// Removes duplicate application drafts left behind by reposted ads: within each group of
+/// reposts (same headline and employer) only the most recently saved draft is kept. Without
+/// `--apply` it only lists what would be removed. Before deleting, the database is copied to
+/// `jobseeker.redb.bak.<timestamp>`, which `restore_backup` can put back. Close Jobseeker first.
+///
+/// Usage: dedupe_drafts [--apply] [PATH]
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut apply = false;
+    let mut path = None;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--apply" => apply = true,
+            _ => path = Some(std::path::PathBuf::from(arg)),
+        }
+    }
+    let db_path = path.unwrap_or_else(Jobseeker::paths::db_path);
+    println!("Databas: {}", db_path.display());
+
+    let db = Jobseeker::open_existing_db(&db_path).await?;
+    let groups = db.duplicate_ad_groups().await?;
+    let plan = db.dedupe_drafts(&groups, true).await?;
+    if plan.removed.is_empty() {
+        println!("Inga dubblettutkast hittades.");
+        return Ok(());
+    }
+    for id in &plan.removed { println!("  ta bort utkast för {}", id); }
+    println!("{} utkast att ta bort, {} behålls.", plan.removed.len(), plan.kept.len());
+    if !apply {
+        println!("Provkörning, inget ändrat. Kör med --apply för att ta bort.");
+        return Ok(());
+    }
+
+    drop(db);
+    let backup = Jobseeker::paths::backup_db(&db_path)?;
+    println!("Säkerhetskopia: {}", backup.display());
+    let db = Jobseeker::open_existing_db(&db_path).await?;
+    let done = db.dedupe_drafts(&groups, false).await?;
+    println!("Tog bort {} utkast.", done.removed.len());
+    Ok(())
+}
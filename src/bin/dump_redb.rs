@@ -0,0 +1,48 @@
+use redb::{ReadableTable, TableDefinition};
+
+const TABLES: &[&str] = &["job_ads", "job_applications", "settings", "month_views", "snippets", "recent_views", "draft_updated_at", "search_watermarks", "search_checkpoints"];
+
+/// Prints every record in the database. The default is readable (`ID=... JSON=...` with the
+/// value pretty-printed); `--ndjson` writes one compact JSON object per line and nothing
+/// else, for `jq` or attaching to a bug report.
+///
+/// Usage: dump_redb [--ndjson] [--table NAME] [PATH]
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut ndjson = false;
+    let mut only_table: Option<String> = None;
+    let mut path = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--ndjson" => ndjson = true,
+            "--table" => only_table = args.next(),
+            _ => path = Some(std::path::PathBuf::from(arg)),
+        }
+    }
+    let db_path = path.unwrap_or_else(Jobseeker::paths::db_path);
+    if let Some(name) = only_table.as_deref() {
+        if !TABLES.contains(&name) {
+            return Err(format!("Okänd tabell '{}'. Giltiga tabeller: {}", name, TABLES.join(", ")).into());
+        }
+    }
+
+    let db = Jobseeker::db::open_db_snapshot(&db_path)?;
+    if db.is_copy() { eprintln!("Jobseeker är öppet; läser en kopia av databasen."); }
+    let read_txn = db.begin_read()?;
+    for name in TABLES.iter().filter(|t| only_table.as_deref().is_none_or(|o| o == **t)) {
+        // A table that was never written to doesn't exist yet
+        let Ok(table) = read_txn.open_table(TableDefinition::<&str, &str>::new(name)) else { continue };
+        if !ndjson { println!("== {} ({} poster) ==", name, table.len()?); }
+        for item in table.iter()? {
+            let (key, value) = item?;
+            // Values are JSON except for plain strings (e.g. timestamps), which stay strings
+            let parsed = serde_json::from_str::<serde_json::Value>(value.value()).unwrap_or_else(|_| serde_json::Value::String(value.value().to_string()));
+            if ndjson {
+                println!("{}", serde_json::json!({ "table": name, "id": key.value(), "value": parsed }));
+            } else {
+                println!("ID={} JSON={}", key.value(), serde_json::to_string_pretty(&parsed)?);
+            }
+        }
+    }
+    Ok(())
+}
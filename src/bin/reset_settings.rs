@@ -1,5 +1,4 @@
-use std::path::PathBuf;
-use redb::{Database, TableDefinition};
+use redb::TableDefinition;
 
 const SETTINGS_TABLE: TableDefinition<&str, &str> = TableDefinition::new("settings");
 
@@ -9,25 +8,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("==============================");
     println!();
 
-    // Find DB path
-    let db_path = if cfg!(target_os = "android") {
-        PathBuf::from("/data/data/com.gnawsoftware.jobseeker/files/jobseeker.redb")
-    } else {
-        if let Some(proj_dirs) = directories::ProjectDirs::from("com", "GnawSoftware", "Jobseeker") {
-            proj_dirs.data_dir().join("jobseeker.redb")
-        } else {
-            PathBuf::from("jobseeker.redb")
-        }
-    };
+    // Same path as the app uses
+    let db_path = Jobseeker::paths::db_path();
 
     println!("DB path: {}", db_path.display());
 
-    if !db_path.exists() {
-        println!("Error: DB file not found!");
-        return Ok(());
-    }
-
-    let db = Database::open(&db_path)?;
+    let db = match Jobseeker::open_db_exclusive(&db_path) {
+        Ok(db) => db,
+        Err(e) => {
+            println!("Error: {}", e);
+            return Ok(());
+        }
+    };
 
     // Read current settings
     let read_txn = db.begin_read()?;
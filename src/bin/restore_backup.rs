@@ -0,0 +1,100 @@
+use std::path::{Path, PathBuf};
+
+/// A backup file found on disk, newest first when listed.
+struct Backup {
+    path: PathBuf,
+    modified: chrono::DateTime<chrono::Local>,
+    size: u64,
+}
+
+/// Backups are either `jobseeker.redb.bak.<timestamp>` next to the database (the tools write
+/// those before changing it) or `jobseeker_backup_<timestamp>.redb` from the Backup button,
+/// which ends up in Downloads.
+fn is_backup_name(name: &str) -> bool {
+    name.starts_with(&format!("{}.bak.", Jobseeker::paths::DB_FILE_NAME))
+        || (name.starts_with("jobseeker_backup_") && name.ends_with(".redb"))
+}
+
+fn find_backups(db_path: &Path) -> Vec<Backup> {
+    let mut dirs: Vec<PathBuf> = db_path.parent().map(Path::to_path_buf).into_iter().collect();
+    if let Some(downloads) = directories::UserDirs::new().and_then(|u| u.download_dir().map(Path::to_path_buf)) {
+        if !dirs.contains(&downloads) { dirs.push(downloads); }
+    }
+    let mut backups: Vec<Backup> = dirs.iter()
+        .filter_map(|d| std::fs::read_dir(d).ok())
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_str().is_some_and(is_backup_name))
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            Some(Backup { path: e.path(), modified: meta.modified().ok()?.into(), size: meta.len() })
+        })
+        .collect();
+    backups.sort_by(|a, b| b.modified.cmp(&a.modified));
+    backups
+}
+
+fn print_list(backups: &[Backup]) {
+    if backups.is_empty() {
+        println!("Inga säkerhetskopior hittades.");
+        return;
+    }
+    for (i, b) in backups.iter().enumerate() {
+        println!("{:>3}. {}  {:>8} kB  {}", i + 1, b.modified.format("%Y-%m-%d %H:%M"), b.size / 1024, b.path.display());
+    }
+}
+
+/// Copies the current database aside, then puts `backup` in its place. The copy goes to a
+/// temporary file in the same directory first, so the database is never half-written, and
+/// that copy is what gets checked, so the backup itself is never opened for writing.
+fn restore(backup: &Path, db_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    // Replacing the file under a running app would lose its writes and confuse its next ones
+    if let Err(Jobseeker::DbError::BusyLock(_)) = Jobseeker::open_db_exclusive(db_path) {
+        return Err(format!("Databasen {} används av Jobseeker. Stäng Jobseeker först och försök igen.", db_path.display()).into());
+    }
+    let tmp = db_path.with_extension("redb.restoring");
+    std::fs::copy(backup, &tmp)?;
+    // Refuse files that redb can't open rather than replacing a working database with them
+    if let Err(e) = Jobseeker::open_db_exclusive(&tmp) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(format!("{} är ingen läsbar databas: {}", backup.display(), e).into());
+    }
+    if db_path.exists() {
+        let aside = Jobseeker::paths::backup_db(db_path)?;
+        println!("Nuvarande databas sparad som {}", aside.display());
+    }
+    std::fs::rename(&tmp, db_path)?;
+    println!("Återställde {} till {}", backup.display(), db_path.display());
+    Ok(())
+}
+
+/// Usage: restore_backup [--list | --restore FILE]. Without arguments the backups are
+/// listed and one is picked by number. Close Jobseeker first.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = Jobseeker::paths::db_path();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let backups = find_backups(&db_path);
+
+    match args.first().map(String::as_str) {
+        Some("--list") => print_list(&backups),
+        Some("--restore") => {
+            let file = args.get(1).ok_or("--restore kräver en fil")?;
+            restore(Path::new(file), &db_path)?;
+        }
+        Some(other) => return Err(format!("Okänt argument '{}'. Använd --list eller --restore FIL", other).into()),
+        None => {
+            println!("Databas: {}\n", db_path.display());
+            print_list(&backups);
+            if backups.is_empty() { return Ok(()); }
+            print!("\nVälj nummer att återställa (tomt = avbryt): ");
+            std::io::Write::flush(&mut std::io::stdout())?;
+            let mut input = String::new();
+            std::io::stdin().read_line(&mut input)?;
+            match input.trim().parse::<usize>().ok().and_then(|n| backups.get(n.wrapping_sub(1))) {
+                Some(b) => restore(&b.path, &db_path)?,
+                None => println!("Avbrutet, inget ändrat."),
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,35 @@
+//! Copies the search configuration between machines.
+//!
+//!     settings_transfer --export-settings jobseeker-settings.json
+//!     settings_transfer --import-settings jobseeker-settings.json
+//!
+//! Close Jobseeker first; the database can only be opened by one process at a time.
+
+use Jobseeker::models::AppSettings;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (flag, file) = match args.as_slice() {
+        [flag, file] if flag == "--export-settings" || flag == "--import-settings" => (flag.as_str(), std::path::PathBuf::from(file)),
+        _ => {
+            eprintln!("Användning: settings_transfer --export-settings <fil> | --import-settings <fil>");
+            std::process::exit(2);
+        }
+    };
+
+    let db_path = Jobseeker::paths::db_path();
+    let db = Jobseeker::db::Db::new(&db_path.to_string_lossy()).await?;
+    let current = db.load_settings().await?.unwrap_or_default();
+
+    if flag == "--export-settings" {
+        current.export_to(&file)?;
+        println!("Inställningar exporterade till {}", file.display());
+    } else {
+        let imported = AppSettings::import_from(&file)?;
+        // The sync folder belongs to this machine and is not part of the file
+        db.save_settings(&AppSettings { sync_path: current.sync_path, ..imported }).await?;
+        println!("Inställningar importerade från {}", file.display());
+    }
+    Ok(())
+}
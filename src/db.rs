@@ -1,12 +1,429 @@
 use redb::{Database, TableDefinition, ReadableTable};
-use crate::models::{JobAd, AdStatus, AppSettings};
-use anyhow::{Result, Context};
-use chrono::Utc;
+use crate::models::{JobAd, AdStatus, AppSettings, MonthBasis};
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Datelike, Utc};
 use std::sync::Arc;
 
 const JOB_ADS_TABLE: TableDefinition<&str, &str> = TableDefinition::new("job_ads");
 const APPLICATIONS_TABLE: TableDefinition<&str, &str> = TableDefinition::new("job_applications");
 const SETTINGS_TABLE: TableDefinition<&str, &str> = TableDefinition::new("settings");
+/// "YYYY-MM" -> rfc3339 time the month was last shown in the inbox.
+const MONTH_VIEWS_TABLE: TableDefinition<&str, &str> = TableDefinition::new("month_views");
+/// Snippet name -> Markdown text, reusable paragraphs for cover letters.
+const SNIPPETS_TABLE: TableDefinition<&str, &str> = TableDefinition::new("snippets");
+/// `RECENT_VIEWS_KEY` -> the "Senast visade" history as a JSON list of `RecentView`,
+/// newest first.
+const RECENT_VIEWS_TABLE: TableDefinition<&str, &str> = TableDefinition::new("recent_views");
+const RECENT_VIEWS_KEY: &str = "history";
+/// job_id -> rfc3339 time the draft was last saved. Kept beside `APPLICATIONS_TABLE` so the
+/// drafts stay plain text.
+const DRAFT_UPDATES_TABLE: TableDefinition<&str, &str> = TableDefinition::new("draft_updated_at");
+/// Search profile ("p0".."p3") -> rfc3339 start of its last fully successful search.
+const SEARCH_WATERMARKS_TABLE: TableDefinition<&str, &str> = TableDefinition::new("search_watermarks");
+/// Search profile -> JSON `SearchCheckpoint` of an interrupted search.
+const SEARCH_CHECKPOINTS_TABLE: TableDefinition<&str, &str> = TableDefinition::new("search_checkpoints");
+const SEARCH_PROFILES: [&str; 4] = ["p0", "p1", "p2", "p3"];
+
+/// Name of the snippet `Db::seed_profile_snippet` creates from `AppSettings::my_profile`.
+pub const PROFILE_SNIPPET_NAME: &str = "Min profil";
+
+/// Words too common in Swedish job titles to say anything about similarity.
+const STOPWORDS: &[&str] = &[
+    "och", "eller", "för", "med", "till", "som", "att", "inom", "det", "den", "hos",
+    "söker", "sökes", "vill", "nya", "din", "vår", "våra", "the", "and", "for", "with",
+];
+
+/// Lowercased headline + occupation words, minus stopwords and very short tokens.
+fn significant_words(ad: &JobAd) -> std::collections::HashSet<String> {
+    let occupation = ad.occupation.as_ref().and_then(|o| o.label.as_deref()).unwrap_or("");
+    format!("{} {}", ad.headline, occupation)
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 3 && !STOPWORDS.contains(w))
+        .map(|w| w.to_string())
+        .collect()
+}
+
+/// Decides whether an ad belongs to the given month. In `Activity` mode that is the date
+/// the user acted on it (applied_at/bookmarked_at), falling back to when it was fetched.
+/// In `Publication` mode it is always the ad's publication date, regardless of status.
+fn ad_in_month(ad: &JobAd, y: i32, m: u32, basis: MonthBasis) -> bool {
+    match basis {
+        MonthBasis::Activity => {
+            let acted_at = match ad.status {
+                _ if ad.is_applied() => ad.applied_at,
+                Some(AdStatus::Bookmarked) | Some(AdStatus::ThumbsUp) => ad.bookmarked_at,
+                _ => None,
+            };
+            let date = acted_at.unwrap_or(ad.internal_created_at).with_timezone(&chrono::Local);
+            date.year() == y && date.month() == m
+        }
+        MonthBasis::Publication => {
+            if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(&ad.publication_date) {
+                dt.year() == y && dt.month() == m
+            } else if let Ok(dt) = chrono::NaiveDate::parse_from_str(&ad.publication_date, "%Y-%m-%d") {
+                dt.year() == y && dt.month() == m
+            } else {
+                ad.publication_date.starts_with(&format!("{}-{:02}", y, m))
+            }
+        }
+    }
+}
+
+/// Sets the status and the matching timestamp; `applied_at` overrides "now" for Applied.
+/// Leaving Applied clears `applied_at`, otherwise `JobAd::is_applied` would still count it.
+fn set_status(ad: &mut JobAd, status: Option<AdStatus>, applied_at: Option<DateTime<Utc>>) {
+    ad.status = status;
+    let now = Utc::now();
+    match status {
+        Some(AdStatus::Applied) => ad.applied_at = Some(applied_at.unwrap_or(now)),
+        Some(AdStatus::Bookmarked) | Some(AdStatus::ThumbsUp) => { ad.bookmarked_at = Some(now); ad.applied_at = None; }
+        _ => ad.applied_at = None,
+    }
+}
+
+/// Counts `times` per weekday (Monday = 0) and hour. `applied_at` is stored in UTC; the
+/// buckets are taken in `tz`, normally `chrono::Local`, so an application sent at 23:30 on a
+/// Sunday evening counts as Sunday 23 even though UTC has it on Sunday 21 or 22.
+pub fn weekday_hour_buckets<Tz: chrono::TimeZone>(times: impl IntoIterator<Item = DateTime<Utc>>, tz: &Tz) -> [[u32; 24]; 7] {
+    use chrono::Timelike;
+    let mut buckets = [[0u32; 24]; 7];
+    for at in times {
+        let local = at.with_timezone(tz);
+        buckets[local.weekday().num_days_from_monday() as usize][local.hour() as usize] += 1;
+    }
+    buckets
+}
+
+/// Which ads `Db::get_filtered_jobs` returns. The default is the inbox: every ad except
+/// rejected ones, from all months.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JobFilter<'a> {
+    /// Only these statuses (Applied by `is_applied`); empty means all but Rejected.
+    pub statuses: &'a [AdStatus],
+    /// Only the given month; both `year` and `month` must be set for either to count.
+    pub year: Option<i32>,
+    pub month: Option<u32>,
+    /// Cached ads from these employers are hidden as well.
+    pub employer_blacklist: Option<&'a [String]>,
+    /// Only ads carrying this tag.
+    pub tag: Option<&'a str>,
+    /// Which date places an ad in a month (see `ad_in_month`).
+    pub month_basis: MonthBasis,
+    /// Only ads published on or after this day.
+    pub published_after: Option<chrono::NaiveDate>,
+}
+
+/// Progress of a multi-keyword search that has not finished yet, so an interrupted run
+/// (window closed, network gone) can pick up where it stopped instead of starting over.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SearchCheckpoint {
+    /// When the interrupted run started; becomes the watermark once the resumed run
+    /// completes, so nothing published in between is skipped for the finished keywords.
+    pub started_at: DateTime<Utc>,
+    pub completed_keywords: Vec<String>,
+}
+
+/// Typed errors from the database layer, so callers can tell a lock held by another
+/// instance apart from I/O problems or a damaged file and show matching guidance.
+#[derive(Debug, thiserror::Error)]
+pub enum DbError {
+    #[error("Databasen {} används redan av ett annat program. Stäng andra instanser av Jobseeker och försök igen.", .0.display())]
+    BusyLock(PathBuf),
+    #[error("Kunde inte läsa eller skriva databasfilen: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Databasen verkar vara skadad: {0}")]
+    Corrupt(String),
+    #[error("Databasfilen har ett format som kräver uppgradering: {0}")]
+    Migration(String),
+    #[error("Hittade inte {0}")]
+    NotFound(String),
+    #[error("Kunde inte omvandla data till JSON: {0}")]
+    Serialize(String),
+    /// A legacy settings file outside the database that can't be read; the database is fine.
+    #[error("De gamla inställningarna i {} kunde inte läsas och importeras inte: {1}", .0.display())]
+    LegacySettings(PathBuf, String),
+    #[error("Databasfel: {0}")]
+    Other(String),
+}
+
+impl From<redb::Error> for DbError {
+    fn from(e: redb::Error) -> Self {
+        match e {
+            redb::Error::Io(io) => DbError::Io(io),
+            redb::Error::Corrupted(msg) => DbError::Corrupt(msg),
+            redb::Error::UpgradeRequired(version) => DbError::Migration(format!("filformat {}", version)),
+            other => DbError::Other(other.to_string()),
+        }
+    }
+}
+
+// redb has one error type per operation; all of them convert into `redb::Error`.
+macro_rules! db_error_from_redb {
+    ($($ty:ty),*) => {
+        $(impl From<$ty> for DbError {
+            fn from(e: $ty) -> Self { DbError::from(redb::Error::from(e)) }
+        })*
+    };
+}
+db_error_from_redb!(redb::DatabaseError, redb::TransactionError, redb::TableError, redb::StorageError, redb::CommitError);
+
+impl DbError {
+    /// For a stored value that no longer parses: the data, not the code, is broken.
+    pub fn decode(e: serde_json::Error) -> Self {
+        DbError::Corrupt(e.to_string())
+    }
+
+    /// For a value that can't be written as JSON, which is a bug rather than damaged data.
+    pub fn encode(e: serde_json::Error) -> Self {
+        DbError::Serialize(e.to_string())
+    }
+}
+
+pub type Result<T> = std::result::Result<T, DbError>;
+
+/// Maps the result of opening `path`, reporting a lock held by another process as `BusyLock`
+/// with the path filled in. Every open goes through here: the plain `From<redb::Error>`
+/// doesn't know which file was meant, so it has no `BusyLock` arm.
+fn opened(path: &Path, result: std::result::Result<Database, redb::DatabaseError>) -> Result<Database> {
+    match result {
+        Ok(db) => Ok(db),
+        Err(redb::DatabaseError::DatabaseAlreadyOpen) => Err(DbError::BusyLock(path.to_path_buf())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Opens an existing database for the CLI tools without ever creating or re-initialising it.
+/// The open is read-write and exclusive: redb only allows one process at a time, so a lock
+/// held by the GUI is reported as `DbError::BusyLock` instead of redb's generic error. Tools
+/// that only read should use `open_db_snapshot`, which also works while the app runs.
+pub fn open_db_exclusive(path: impl AsRef<Path>) -> Result<Database> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return Err(DbError::NotFound(format!("någon databas på {}", path.display())));
+    }
+    opened(path, Database::open(path))
+}
+
+/// The database at `path` for a CLI tool that changes it. Unlike `Db::new` a missing file is
+/// an error, so a mistyped path doesn't quietly create an empty database, and a lock held by
+/// the app comes back as `BusyLock` before anything is set up.
+pub async fn open_existing_db(path: &Path) -> Result<Db> {
+    drop(open_db_exclusive(path)?);
+    let path_str = path.to_str().ok_or_else(|| DbError::Other(format!("sökvägen {} är inte giltig UTF-8", path.display())))?;
+    Db::new(path_str).await
+}
+
+/// A database opened for reading by a tool. When the app holds the lock, this is a private
+/// copy of the file taken when the tool started; the copy is deleted on drop.
+pub struct DbSnapshot {
+    database: Database,
+    copy: Option<PathBuf>,
+}
+
+impl DbSnapshot {
+    /// True when the app had the database open and a copy is being read instead.
+    pub fn is_copy(&self) -> bool { self.copy.is_some() }
+}
+
+impl std::ops::Deref for DbSnapshot {
+    type Target = Database;
+    fn deref(&self) -> &Database { &self.database }
+}
+
+impl Drop for DbSnapshot {
+    fn drop(&mut self) {
+        if let Some(copy) = &self.copy { let _ = std::fs::remove_file(copy); }
+    }
+}
+
+/// Like `open_db_exclusive`, but read-only tools keep working while the app is running: on
+/// `BusyLock` the file is copied and the copy is opened. The app already copies its open
+/// database for sync and backup, and redb only ever commits whole transactions, so the copy
+/// shows the state after the last commit (redb repairs it on open if it was taken mid-write).
+/// Anything written through the snapshot is lost, so it is for reading only.
+pub fn open_db_snapshot(path: impl AsRef<Path>) -> Result<DbSnapshot> {
+    let path = path.as_ref();
+    match open_db_exclusive(path) {
+        Ok(database) => Ok(DbSnapshot { database, copy: None }),
+        Err(DbError::BusyLock(_)) => {
+            let copy = std::env::temp_dir().join(format!("jobseeker_snapshot_{}_{}.redb", std::process::id(), Utc::now().timestamp_millis()));
+            std::fs::copy(path, &copy)?;
+            match Database::open(&copy) {
+                Ok(database) => Ok(DbSnapshot { database, copy: Some(copy) }),
+                Err(e) => { let _ = std::fs::remove_file(&copy); Err(e.into()) }
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Older versions kept app state next to the user's settings; it moves to its own table the
+/// first time such a database is opened, so resetting or exporting settings leaves it alone.
+fn move_state_out_of_settings(write_txn: &redb::WriteTransaction) -> Result<()> {
+    let mut settings = write_txn.open_table(SETTINGS_TABLE)?;
+    let recent = settings.remove("recent_views")?.map(|h| h.value().to_string());
+    if let Some(recent) = recent {
+        write_txn.open_table(RECENT_VIEWS_TABLE)?.insert(RECENT_VIEWS_KEY, recent.as_str())?;
+    }
+    let drafts = settings.remove("draft_updated_at")?.map(|h| h.value().to_string());
+    // Only save times are lost if the old map can't be read; the drafts count as oldest then
+    if let Some(Ok(updated)) = drafts.map(|d| serde_json::from_str::<std::collections::HashMap<String, DateTime<Utc>>>(&d)) {
+        let mut table = write_txn.open_table(DRAFT_UPDATES_TABLE)?;
+        for (id, at) in updated { table.insert(id.as_str(), at.to_rfc3339().as_str())?; }
+    }
+    for (prefix, definition) in [("watermark_", SEARCH_WATERMARKS_TABLE), ("checkpoint_", SEARCH_CHECKPOINTS_TABLE)] {
+        let mut table = write_txn.open_table(definition)?;
+        for profile in SEARCH_PROFILES {
+            let value = settings.remove(format!("{}{}", prefix, profile).as_str())?.map(|h| h.value().to_string());
+            if let Some(value) = value { table.insert(profile, value.as_str())?; }
+        }
+    }
+    Ok(())
+}
+
+/// One opened ad in the "Senast visade" history.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RecentView {
+    pub id: String,
+    pub viewed_at: DateTime<Utc>,
+}
+
+/// What `Db::dedupe_drafts` kept and removed (or would remove, on a dry run), as ad ids.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DraftDedupe {
+    pub kept: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// Groups reposts of the same job: ads with the same headline and employer, compared
+/// without case or surrounding whitespace. Only groups of two or more are returned, each
+/// in the order the ads were given.
+pub fn duplicate_ad_groups(ads: &[JobAd]) -> Vec<Vec<String>> {
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+    for ad in ads {
+        let employer = ad.employer.as_ref().and_then(|e| e.name.as_deref()).unwrap_or("");
+        let key = format!("{}\n{}", ad.headline.trim().to_lowercase(), employer.trim().to_lowercase());
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, ids)) => ids.push(ad.id.clone()),
+            None => groups.push((key, vec![ad.id.clone()])),
+        }
+    }
+    groups.into_iter().map(|(_, ids)| ids).filter(|ids| ids.len() > 1).collect()
+}
+
+/// How long an ad without a last application date counts as open, for `true_duplicate_groups`.
+pub const DUPLICATE_OPEN_DAYS: i64 = 30;
+
+/// Days an ad was open: publication to last application date (or `DUPLICATE_OPEN_DAYS`).
+/// None without a readable publication date.
+fn open_window(ad: &JobAd) -> Option<(chrono::NaiveDate, chrono::NaiveDate)> {
+    let day = |d: &str| chrono::NaiveDate::parse_from_str(d.get(..10)?, "%Y-%m-%d").ok();
+    let start = day(&ad.publication_date)?;
+    let end = ad.last_application_date.as_deref().and_then(day).filter(|e| *e >= start).unwrap_or(start + chrono::Duration::days(DUPLICATE_OPEN_DAYS));
+    Some((start, end))
+}
+
+/// Stricter than `duplicate_ad_groups`, for deleting records: the same job stored more than
+/// once, i.e. identical headline and employer *and* overlapping open periods. A repost months
+/// later is a new ad and stays; ads without a readable publication date are never grouped.
+pub fn true_duplicate_groups(ads: &[JobAd]) -> Vec<Vec<String>> {
+    let by_id: std::collections::HashMap<&str, &JobAd> = ads.iter().map(|a| (a.id.as_str(), a)).collect();
+    let mut result = Vec::new();
+    for group in duplicate_ad_groups(ads) {
+        let mut dated: Vec<(chrono::NaiveDate, chrono::NaiveDate, String)> = group.into_iter()
+            .filter_map(|id| { let (start, end) = open_window(by_id.get(id.as_str())?)?; Some((start, end, id)) })
+            .collect();
+        dated.sort();
+        let mut cluster: Vec<String> = Vec::new();
+        let mut cluster_end = chrono::NaiveDate::MIN;
+        for (start, end, id) in dated {
+            if start > cluster_end && !cluster.is_empty() {
+                if cluster.len() > 1 { result.push(std::mem::take(&mut cluster)); } else { cluster.clear(); }
+            }
+            cluster_end = if cluster.is_empty() { end } else { cluster_end.max(end) };
+            cluster.push(id);
+        }
+        if cluster.len() > 1 { result.push(cluster); }
+    }
+    result
+}
+
+/// The record `Db::dedupe_ads` keeps of a duplicate group: the most advanced status
+/// (applied > tummen upp > sparad > avvisad > ny), then the newest publication. On a tie the
+/// first one listed stays.
+pub fn preferred_duplicate<'a>(ads: &[&'a JobAd]) -> Option<&'a JobAd> {
+    let rank = |ad: &JobAd| if ad.is_applied() { AdStatus::Applied as u8 } else { ad.status.unwrap_or(AdStatus::New) as u8 };
+    ads.iter().enumerate()
+        .max_by(|(i, a), (j, b)| rank(a).cmp(&rank(b)).then(a.publication_date.cmp(&b.publication_date)).then(j.cmp(i)))
+        .map(|(_, ad)| *ad)
+}
+
+/// Folds what the user added to a duplicate about to be deleted into the `kept` record: the
+/// star and pin stay if either copy had them, the tags are joined and the best AI rating wins.
+/// Returns whether `kept` changed.
+pub fn merge_duplicate(kept: &mut JobAd, removed: &JobAd) -> bool {
+    let before = (kept.starred, kept.pinned, kept.tags.len(), kept.rating);
+    kept.starred |= removed.starred;
+    kept.pinned |= removed.pinned;
+    for tag in &removed.tags {
+        if !kept.tags.contains(tag) { kept.tags.push(tag.clone()); }
+    }
+    kept.rating = kept.rating.max(removed.rating);
+    before != (kept.starred, kept.pinned, kept.tags.len(), kept.rating)
+}
+
+/// What `Db::dedupe_ads` kept and removed (or would remove, on a dry run): one entry per
+/// group, the kept id with the ids deleted in its favour.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AdDedupe {
+    pub groups: Vec<(String, Vec<String>)>,
+}
+
+impl AdDedupe {
+    pub fn removed_count(&self) -> usize {
+        self.groups.iter().map(|(_, removed)| removed.len()).sum()
+    }
+}
+
+/// Most views kept by `Db::record_view`; older ones fall off the end.
+pub const RECENT_VIEWS_CAP: usize = 50;
+
+/// Snapshot of what the ad table holds, for `db_check --stats` and bug reports.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorpusStats {
+    pub total: usize,
+    /// Ad count per status, in `AdStatus` order; ads without a status count as New.
+    pub by_status: [(AdStatus, usize); 5],
+    /// Records that no longer parse as a `JobAd`; counted in `total` too.
+    pub unreadable: usize,
+    pub oldest: Option<DateTime<Utc>>,
+    pub newest: Option<DateTime<Utc>>,
+}
+
+/// Counts the ads in one read transaction of `database`, which may be opened read-only.
+pub fn corpus_stats(database: &Database) -> Result<CorpusStats> {
+    let mut stats = CorpusStats {
+        total: 0,
+        by_status: [AdStatus::New, AdStatus::Rejected, AdStatus::Bookmarked, AdStatus::ThumbsUp, AdStatus::Applied].map(|s| (s, 0)),
+        unreadable: 0,
+        oldest: None,
+        newest: None,
+    };
+    let read_txn = database.begin_read()?;
+    // A database the app never saved an ad to has no table yet
+    let Ok(table) = read_txn.open_table(JOB_ADS_TABLE) else { return Ok(stats) };
+    for item in table.iter()? {
+        let (_, json_handle) = item?;
+        stats.total += 1;
+        let Ok(ad) = serde_json::from_str::<JobAd>(json_handle.value()) else { stats.unreadable += 1; continue };
+        let status = ad.status.unwrap_or(AdStatus::New);
+        if let Some(entry) = stats.by_status.iter_mut().find(|(s, _)| *s == status) { entry.1 += 1; }
+        stats.oldest = Some(stats.oldest.map_or(ad.internal_created_at, |o| o.min(ad.internal_created_at)));
+        stats.newest = Some(stats.newest.map_or(ad.internal_created_at, |n| n.max(ad.internal_created_at)));
+    }
+    Ok(stats)
+}
 
 /// RedB database wrapper. Uses JSON serialization for values to support
 /// complex job advertisement and settings objects while keeping the key-value structure.
@@ -19,8 +436,7 @@ impl Db {
     /// Opens or creates the RedB database at the given path.
     /// Tables are automatically initialized if they don't exist.
     pub async fn new(db_path: &str) -> Result<Self> {
-        let db = Database::create(db_path)
-            .context("Failed to create/open RedB database")?;
+        let db = opened(Path::new(db_path), Database::create(db_path))?;
 
         // Initiera tabeller
         let write_txn = db.begin_write()?;
@@ -28,19 +444,43 @@ impl Db {
             let _ = write_txn.open_table(JOB_ADS_TABLE)?;
             let _ = write_txn.open_table(APPLICATIONS_TABLE)?;
             let _ = write_txn.open_table(SETTINGS_TABLE)?;
+            let _ = write_txn.open_table(MONTH_VIEWS_TABLE)?;
+            let _ = write_txn.open_table(SNIPPETS_TABLE)?;
+            let _ = write_txn.open_table(RECENT_VIEWS_TABLE)?;
+            let _ = write_txn.open_table(DRAFT_UPDATES_TABLE)?;
+            let _ = write_txn.open_table(SEARCH_WATERMARKS_TABLE)?;
+            let _ = write_txn.open_table(SEARCH_CHECKPOINTS_TABLE)?;
+            move_state_out_of_settings(&write_txn)?;
         }
         write_txn.commit()?;
 
         Ok(Self { database: Arc::new(db) })
     }
 
+    /// Like `new`, but waits out a lock held by another process (a CLI tool finishing, the
+    /// previous instance still closing): up to `attempts` tries, `delay` apart. Any other
+    /// error, and a lock that outlasts the tries, is returned as is.
+    pub async fn open_with_retry(db_path: &str, attempts: u32, delay: std::time::Duration) -> Result<Self> {
+        let mut attempt = 1;
+        loop {
+            match Self::new(db_path).await {
+                Err(DbError::BusyLock(path)) if attempt < attempts => {
+                    tracing::info!("Databasen {} är låst, försöker igen ({} av {})", path.display(), attempt, attempts);
+                    attempt += 1;
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
     // --- Inställningar ---
     /// Saves the application settings as a JSON blob.
     pub async fn save_settings(&self, settings: &AppSettings) -> Result<()> {
         let write_txn = self.database.begin_write()?;
         {
             let mut table = write_txn.open_table(SETTINGS_TABLE)?;
-            let json = serde_json::to_string(settings)?;
+            let json = serde_json::to_string(&AppSettings { version: crate::models::SETTINGS_VERSION, ..settings.clone() }).map_err(DbError::encode)?;
             table.insert("current", json.as_str())?;
         }
         write_txn.commit()?;
@@ -51,20 +491,186 @@ impl Db {
         let read_txn = self.database.begin_read()?;
         let table = read_txn.open_table(SETTINGS_TABLE)?;
         if let Some(json_handle) = table.get("current")? {
-            let settings: AppSettings = serde_json::from_str(json_handle.value())?;
+            // Older blobs are upgraded in memory; the next save writes the current version
+            let settings = AppSettings::migrate(serde_json::from_str(json_handle.value()).map_err(DbError::decode)?).map_err(DbError::decode)?;
             Ok(Some(settings))
         } else {
             Ok(None)
         }
     }
 
+    /// True on a fresh install: no settings row and no ads saved yet.
+    pub async fn is_first_run(&self) -> Result<bool> {
+        let read_txn = self.database.begin_read()?;
+        let settings = read_txn.open_table(SETTINGS_TABLE)?;
+        let ads = read_txn.open_table(JOB_ADS_TABLE)?;
+        Ok(settings.get("current")?.is_none() && ads.iter()?.next().is_none())
+    }
+
+    /// Saves `AppSettings::default()` on a first run, so every later load reads a stored row
+    /// instead of silently falling back to the defaults. Returns whether anything was saved.
+    pub async fn seed_first_run(&self) -> Result<bool> {
+        if !self.is_first_run().await? { return Ok(false); }
+        self.save_settings(&AppSettings::default()).await?;
+        Ok(true)
+    }
+
+    /// Moves a legacy `settings.json` (from the iced frontend) into the database, unless a
+    /// settings row already exists. The file is then renamed to
+    /// `settings.json.imported.<timestamp>`, so it is imported once and never again; should the
+    /// rename fail, the stored row still keeps later starts from reading it. Returns the new
+    /// name of the file when something was imported. A file that can't be read or parsed is
+    /// `DbError::LegacySettings` and left untouched; nothing is written then.
+    pub async fn import_legacy_settings(&self, path: &Path) -> Result<Option<PathBuf>> {
+        if !path.is_file() { return Ok(None); }
+        let write_txn = self.database.begin_write()?;
+        {
+            let mut table = write_txn.open_table(SETTINGS_TABLE)?;
+            if table.get("current")?.is_some() { return Ok(None); }
+            let legacy_error = |e: &dyn std::fmt::Display| DbError::LegacySettings(path.to_path_buf(), e.to_string());
+            let text = std::fs::read_to_string(path).map_err(|e| legacy_error(&e))?;
+            // Fields are matched by name; what the old file lacks gets the defaults
+            let settings = serde_json::from_str(&text).and_then(AppSettings::migrate).map_err(|e| legacy_error(&e))?;
+            let json = serde_json::to_string(&AppSettings { version: crate::models::SETTINGS_VERSION, ..settings }).map_err(DbError::encode)?;
+            table.insert("current", json.as_str())?;
+        }
+        write_txn.commit()?;
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let imported = path.with_file_name(format!("{}.imported.{}", name, Utc::now().format("%Y%m%d%H%M%S")));
+        std::fs::rename(path, &imported)?;
+        Ok(Some(imported))
+    }
+
+    // --- Sökvattenmärken ---
+    /// Start time of the last fully successful search for a profile ("p1", "p2", "p3", or
+    /// "p0" for all zones at once).
+    /// Used as `published-after` so repeated searches only fetch genuinely new ads.
+    pub async fn get_search_watermark(&self, profile: &str) -> Result<Option<DateTime<Utc>>> {
+        let read_txn = self.database.begin_read()?;
+        let table = read_txn.open_table(SEARCH_WATERMARKS_TABLE)?;
+        if let Some(handle) = table.get(profile)? {
+            Ok(DateTime::parse_from_rfc3339(handle.value()).ok().map(|d| d.with_timezone(&Utc)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn set_search_watermark(&self, profile: &str, at: DateTime<Utc>) -> Result<()> {
+        let write_txn = self.database.begin_write()?;
+        {
+            let mut table = write_txn.open_table(SEARCH_WATERMARKS_TABLE)?;
+            table.insert(profile, at.to_rfc3339().as_str())?;
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    /// Forgets all watermarks and checkpoints so the next search per profile is a full one
+    /// (e.g. after the keywords or zones changed and older ads may now match).
+    pub async fn clear_search_watermarks(&self) -> Result<()> {
+        let write_txn = self.database.begin_write()?;
+        {
+            let mut watermarks = write_txn.open_table(SEARCH_WATERMARKS_TABLE)?;
+            let mut checkpoints = write_txn.open_table(SEARCH_CHECKPOINTS_TABLE)?;
+            for profile in SEARCH_PROFILES {
+                watermarks.remove(profile)?;
+                checkpoints.remove(profile)?;
+            }
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    pub async fn get_search_checkpoint(&self, profile: &str) -> Result<Option<SearchCheckpoint>> {
+        let read_txn = self.database.begin_read()?;
+        let table = read_txn.open_table(SEARCH_CHECKPOINTS_TABLE)?;
+        match table.get(profile)? {
+            Some(handle) => Ok(Some(serde_json::from_str(handle.value()).map_err(DbError::decode)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn set_search_checkpoint(&self, profile: &str, checkpoint: &SearchCheckpoint) -> Result<()> {
+        let json = serde_json::to_string(checkpoint).map_err(DbError::encode)?;
+        let write_txn = self.database.begin_write()?;
+        {
+            let mut table = write_txn.open_table(SEARCH_CHECKPOINTS_TABLE)?;
+            table.insert(profile, json.as_str())?;
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    pub async fn clear_search_checkpoint(&self, profile: &str) -> Result<()> {
+        let write_txn = self.database.begin_write()?;
+        {
+            let mut table = write_txn.open_table(SEARCH_CHECKPOINTS_TABLE)?;
+            table.remove(profile)?;
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    // --- Månadsbesök ---
+    /// Records that `month` ("YYYY-MM") was viewed now and returns when it was viewed before.
+    pub async fn touch_month_view(&self, month: &str) -> Result<Option<DateTime<Utc>>> {
+        let write_txn = self.database.begin_write()?;
+        let previous;
+        {
+            let mut table = write_txn.open_table(MONTH_VIEWS_TABLE)?;
+            previous = table.get(month)?.and_then(|h| DateTime::parse_from_rfc3339(h.value()).ok()).map(|d| d.with_timezone(&Utc));
+            table.insert(month, Utc::now().to_rfc3339().as_str())?;
+        }
+        write_txn.commit()?;
+        Ok(previous)
+    }
+
+    // --- Senast visade ---
+    /// Adds `id` to the front of the view history, newest first. Opening the ad that is
+    /// already on top only refreshes its time, and the history is capped at `RECENT_VIEWS_CAP`.
+    pub async fn record_view(&self, id: &str) -> Result<()> {
+        let write_txn = self.database.begin_write()?;
+        {
+            let mut table = write_txn.open_table(RECENT_VIEWS_TABLE)?;
+            let mut views: Vec<RecentView> = match table.get(RECENT_VIEWS_KEY)? {
+                Some(handle) => serde_json::from_str(handle.value()).map_err(DbError::decode)?,
+                None => Vec::new(),
+            };
+            let view = RecentView { id: id.to_string(), viewed_at: Utc::now() };
+            match views.first_mut() {
+                Some(top) if top.id == id => *top = view,
+                _ => views.insert(0, view),
+            }
+            views.truncate(RECENT_VIEWS_CAP);
+            table.insert(RECENT_VIEWS_KEY, serde_json::to_string(&views).map_err(DbError::encode)?.as_str())?;
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    /// The `n` most recent views, newest first. An ad opened again later can appear more
+    /// than once.
+    pub async fn recent_views(&self, n: usize) -> Result<Vec<RecentView>> {
+        let read_txn = self.database.begin_read()?;
+        let table = read_txn.open_table(RECENT_VIEWS_TABLE)?;
+        let mut views: Vec<RecentView> = match table.get(RECENT_VIEWS_KEY)? {
+            Some(handle) => serde_json::from_str(handle.value()).map_err(DbError::decode)?,
+            None => Vec::new(),
+        };
+        views.truncate(n);
+        Ok(views)
+    }
+
     // --- Jobbapplikationer ---
-    /// Drafts are stored indexed by job_id.
+    /// Drafts are stored indexed by job_id. The save time goes into `DRAFT_UPDATES_TABLE`,
+    /// so the draft table itself stays plain text.
     pub async fn save_application_draft(&self, job_id: &str, content: &str) -> Result<()> {
         let write_txn = self.database.begin_write()?;
         {
             let mut table = write_txn.open_table(APPLICATIONS_TABLE)?;
             table.insert(job_id, content)?;
+            let mut updated = write_txn.open_table(DRAFT_UPDATES_TABLE)?;
+            updated.insert(job_id, Utc::now().to_rfc3339().as_str())?;
         }
         write_txn.commit()?;
         Ok(())
@@ -77,22 +683,195 @@ impl Db {
         Ok(value.map(|v| v.value().to_string()))
     }
 
+    // --- Textbitar ---
+    /// Every snippet as (name, text), by name without regard to case.
+    pub async fn list_snippets(&self) -> Result<Vec<(String, String)>> {
+        let read_txn = self.database.begin_read()?;
+        let table = read_txn.open_table(SNIPPETS_TABLE)?;
+        let mut list = Vec::new();
+        for item in table.iter()? {
+            let (name, body) = item?;
+            list.push((name.value().to_string(), body.value().to_string()));
+        }
+        list.sort_by(|a, b| a.0.to_lowercase().cmp(&b.0.to_lowercase()));
+        Ok(list)
+    }
+
+    /// Saves a snippet under `name` (trimmed), replacing one with the same name.
+    pub async fn save_snippet(&self, name: &str, body: &str) -> Result<()> {
+        let name = name.trim();
+        if name.is_empty() { return Err(DbError::Other("textbiten behöver ett namn".to_string())); }
+        let write_txn = self.database.begin_write()?;
+        {
+            let mut table = write_txn.open_table(SNIPPETS_TABLE)?;
+            table.insert(name, body)?;
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    /// Deletes the snippet `name`; false when there was none.
+    pub async fn delete_snippet(&self, name: &str) -> Result<bool> {
+        let write_txn = self.database.begin_write()?;
+        let removed = {
+            let mut table = write_txn.open_table(SNIPPETS_TABLE)?;
+            table.remove(name.trim())?.is_some()
+        };
+        write_txn.commit()?;
+        Ok(removed)
+    }
+
+    /// Adds `profile` as the snippet "Min profil" the first time it is called with a non-empty
+    /// profile. Only once: a user who deletes it doesn't get it back. Returns whether it was added.
+    pub async fn seed_profile_snippet(&self, profile: &str) -> Result<bool> {
+        if profile.trim().is_empty() { return Ok(false); }
+        let write_txn = self.database.begin_write()?;
+        let mut added = false;
+        {
+            let mut settings = write_txn.open_table(SETTINGS_TABLE)?;
+            let mut snippets = write_txn.open_table(SNIPPETS_TABLE)?;
+            if settings.get("profile_snippet_seeded")?.is_none() {
+                if snippets.get(PROFILE_SNIPPET_NAME)?.is_none() {
+                    snippets.insert(PROFILE_SNIPPET_NAME, profile)?;
+                    added = true;
+                }
+                settings.insert("profile_snippet_seeded", "true")?;
+            }
+        }
+        write_txn.commit()?;
+        Ok(added)
+    }
+
+    /// Every ad with a non-empty saved draft as (id, headline), by headline. A draft whose ad
+    /// has been removed is listed under its id.
+    pub async fn list_drafts(&self) -> Result<Vec<(String, String)>> {
+        let read_txn = self.database.begin_read()?;
+        let drafts = read_txn.open_table(APPLICATIONS_TABLE)?;
+        let ads = read_txn.open_table(JOB_ADS_TABLE)?;
+        let mut list = Vec::new();
+        for item in drafts.iter()? {
+            let (id, content) = item?;
+            if content.value().trim().is_empty() { continue; }
+            let id = id.value().to_string();
+            let headline = ads.get(id.as_str())?.and_then(|h| serde_json::from_str::<JobAd>(h.value()).ok()).map(|ad| ad.headline).unwrap_or_else(|| id.clone());
+            list.push((id, headline));
+        }
+        list.sort_by(|a, b| a.1.to_lowercase().cmp(&b.1.to_lowercase()));
+        Ok(list)
+    }
+
+    /// Reposts among the cached ads, see `duplicate_ad_groups`.
+    pub async fn duplicate_ad_groups(&self) -> Result<Vec<Vec<String>>> {
+        let read_txn = self.database.begin_read()?;
+        let table = read_txn.open_table(JOB_ADS_TABLE)?;
+        let mut ads = Vec::new();
+        for item in table.iter()? {
+            let (_, json_handle) = item?;
+            if let Ok(ad) = serde_json::from_str::<JobAd>(json_handle.value()) { ads.push(ad); }
+        }
+        Ok(duplicate_ad_groups(&ads))
+    }
+
+    /// Within each group of duplicate ads, keeps the most recently saved non-empty draft and
+    /// deletes the others. Drafts saved before save times were recorded count as oldest; on
+    /// a tie the one listed first in the group stays. With `dry_run` nothing is written.
+    pub async fn dedupe_drafts(&self, groups: &[Vec<String>], dry_run: bool) -> Result<DraftDedupe> {
+        let mut result = DraftDedupe::default();
+        let write_txn = self.database.begin_write()?;
+        {
+            let mut drafts = write_txn.open_table(APPLICATIONS_TABLE)?;
+            let mut updated = write_txn.open_table(DRAFT_UPDATES_TABLE)?;
+            for group in groups {
+                let mut with_draft = Vec::new();
+                for id in group {
+                    if drafts.get(id.as_str())?.is_some_and(|d| !d.value().trim().is_empty()) {
+                        let at = updated.get(id.as_str())?.and_then(|h| DateTime::parse_from_rfc3339(h.value()).ok()).map(|at| at.with_timezone(&Utc));
+                        with_draft.push((id, at));
+                    }
+                }
+                if with_draft.len() < 2 { continue; }
+                let newest = with_draft.iter().enumerate()
+                    .max_by_key(|(i, (_, at))| (*at, std::cmp::Reverse(*i)))
+                    .map(|(_, (id, _))| (*id).clone());
+                let Some(newest) = newest else { continue };
+                for (id, _) in with_draft.into_iter().filter(|(id, _)| **id != newest) {
+                    if !dry_run {
+                        drafts.remove(id.as_str())?;
+                        updated.remove(id.as_str())?;
+                    }
+                    result.removed.push(id.clone());
+                }
+                result.kept.push(newest);
+            }
+        }
+        if dry_run { write_txn.abort()?; } else { write_txn.commit()?; }
+        Ok(result)
+    }
+
+    /// Deletes true duplicates (`true_duplicate_groups`) across all months, keeping
+    /// `preferred_duplicate` of each group. Star, pin, tags and rating of the deleted records
+    /// are merged into the kept one (`merge_duplicate`), and a draft moves to the kept ad when
+    /// that has none. With `dry_run` nothing is written.
+    pub async fn dedupe_ads(&self, dry_run: bool) -> Result<AdDedupe> {
+        let write_txn = self.database.begin_write()?;
+        let mut result = AdDedupe::default();
+        {
+            let mut table = write_txn.open_table(JOB_ADS_TABLE)?;
+            let mut drafts = write_txn.open_table(APPLICATIONS_TABLE)?;
+            let mut draft_updates = write_txn.open_table(DRAFT_UPDATES_TABLE)?;
+            let mut ads = Vec::new();
+            for item in table.iter()? {
+                let (_, json_handle) = item?;
+                if let Ok(ad) = serde_json::from_str::<JobAd>(json_handle.value()) { ads.push(ad); }
+            }
+            let by_id: std::collections::HashMap<&str, &JobAd> = ads.iter().map(|a| (a.id.as_str(), a)).collect();
+            for group in true_duplicate_groups(&ads) {
+                let members: Vec<&JobAd> = group.iter().filter_map(|id| by_id.get(id.as_str()).copied()).collect();
+                let Some(kept) = preferred_duplicate(&members) else { continue };
+                let removed: Vec<String> = group.iter().filter(|id| **id != kept.id).cloned().collect();
+                if !dry_run {
+                    let mut merged = kept.clone();
+                    let changed = members.iter().filter(|ad| ad.id != kept.id).fold(false, |changed, ad| merge_duplicate(&mut merged, ad) | changed);
+                    if changed { table.insert(merged.id.as_str(), serde_json::to_string(&merged).map_err(DbError::encode)?.as_str())?; }
+                    for id in &removed {
+                        table.remove(id.as_str())?;
+                        let draft = drafts.remove(id.as_str())?.map(|d| d.value().to_string());
+                        let saved_at = draft_updates.remove(id.as_str())?.map(|h| h.value().to_string());
+                        if let Some(draft) = draft.filter(|d| !d.trim().is_empty()) {
+                            if drafts.get(kept.id.as_str())?.is_none_or(|d| d.value().trim().is_empty()) {
+                                drafts.insert(kept.id.as_str(), draft.as_str())?;
+                                match saved_at {
+                                    Some(at) => { draft_updates.insert(kept.id.as_str(), at.as_str())?; }
+                                    None => { draft_updates.remove(kept.id.as_str())?; }
+                                }
+                            }
+                        }
+                    }
+                }
+                result.groups.push((kept.id.clone(), removed));
+            }
+        }
+        if dry_run { write_txn.abort()?; } else { write_txn.commit()?; }
+        Ok(result)
+    }
+
     // --- Jobbannonser ---
     /// Primary storage for fetched job ads. Deduplication is handled by job ID.
     pub async fn save_job_ad(&self, ad: &JobAd) -> Result<()> {
         let write_txn = self.database.begin_write()?;
         {
             let mut table = write_txn.open_table(JOB_ADS_TABLE)?;
-            let json = serde_json::to_string(ad)?;
+            let json = serde_json::to_string(ad).map_err(DbError::encode)?;
             table.insert(ad.id.as_str(), json.as_str())?;
         }
         write_txn.commit()?;
         Ok(())
     }
 
-    /// Fetches jobs based on status and time (year/month).
+    /// Fetches the ads `filter` selects (see `JobFilter`).
     /// Rejected jobs are excluded by default unless explicitly requested.
-    pub async fn get_filtered_jobs(&self, status_filter: &[AdStatus], year: Option<i32>, month: Option<u32>) -> Result<Vec<JobAd>> {
+    pub async fn get_filtered_jobs(&self, filter: JobFilter<'_>) -> Result<Vec<JobAd>> {
+        let JobFilter { statuses: status_filter, year, month, employer_blacklist, tag: tag_filter, month_basis, published_after } = filter;
         let read_txn = self.database.begin_read()?;
         let table = read_txn.open_table(JOB_ADS_TABLE)?;
 
@@ -106,36 +885,29 @@ impl Db {
             };
 
             if !status_filter.is_empty() {
-                if let Some(status) = ad.status {
-                    if !status_filter.contains(&status) { continue; }
-                } else { continue; }
+                // The Applied filter goes by `is_applied`, not the raw status
+                let matches = (status_filter.contains(&AdStatus::Applied) && ad.is_applied())
+                    || ad.status.is_some_and(|s| s != AdStatus::Applied && status_filter.contains(&s));
+                if !matches { continue; }
             } else if ad.status == Some(AdStatus::Rejected) {
                 // By default, don't show rejected ads in the main inbox
                 continue;
             }
 
-            if let (Some(y), Some(m)) = (year, month) {
-                let mut matched = false;
-                let year_str = y.to_string();
-                let month_str = format!("{:02}", m);
-
-                // Try to match publication date against the requested month
-                if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(&ad.publication_date) {
-                    use chrono::Datelike;
-                    if dt.year() == y && dt.month() == m { matched = true; }
-                }
-                else if let Ok(dt) = chrono::NaiveDate::parse_from_str(&ad.publication_date, "%Y-%m-%d") {
-                    use chrono::Datelike;
-                    if dt.year() == y && dt.month() == m { matched = true; }
-                }
-                else {
-                    let prefix = format!("{}-{}", year_str, month_str);
-                    if ad.publication_date.starts_with(&prefix) {
-                        matched = true;
-                    }
-                }
+            if let Some(employers) = employer_blacklist {
+                if ad.is_employer_blacklisted(employers) { continue; }
+            }
+
+            if let Some(tag) = tag_filter {
+                if !ad.has_tag(tag) { continue; }
+            }
+
+            if let Some(since) = published_after {
+                if !ad.published_on_or_after(since) { continue; }
+            }
 
-                if matched {
+            if let (Some(y), Some(m)) = (year, month) {
+                if ad_in_month(&ad, y, m, month_basis) {
                     ads.push(ad);
                 }
             } else {
@@ -152,35 +924,137 @@ impl Db {
         Ok(ads)
     }
 
-    /// Updates status and automatically sets the corresponding timestamp (applied_at/bookmarked_at).
-    pub async fn update_ad_status(&self, id: &str, status: Option<AdStatus>) -> Result<()> {
-        let mut ad = self.get_job_ad(id).await?.context("Ad not found")?;
-        ad.status = status;
+    /// Number of applied ads with `applied_at` in `[from, to)`.
+    pub async fn applied_count_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<usize> {
+        let read_txn = self.database.begin_read()?;
+        let table = read_txn.open_table(JOB_ADS_TABLE)?;
 
-        let now = Utc::now();
-        if let Some(s) = status {
-            match s {
-                AdStatus::Applied => ad.applied_at = Some(now),
-                AdStatus::Bookmarked | AdStatus::ThumbsUp => ad.bookmarked_at = Some(now),
-                _ => {}
+        let mut count = 0;
+        for item in table.iter()? {
+            let (_, json_handle) = item?;
+            let ad: JobAd = match serde_json::from_str(json_handle.value()) {
+                Ok(ad) => ad,
+                Err(_) => continue,
+            };
+            if !ad.is_applied() { continue; }
+            if let Some(at) = ad.applied_at {
+                if at >= from && at < to { count += 1; }
             }
         }
+        Ok(count)
+    }
+
+    /// Applications per weekday (Monday first) and hour of day over all time, in local time
+    /// (see `weekday_hour_buckets`).
+    pub async fn applied_by_weekday_hour(&self) -> Result<[[u32; 24]; 7]> {
+        let read_txn = self.database.begin_read()?;
+        let table = read_txn.open_table(JOB_ADS_TABLE)?;
+        let mut times = Vec::new();
+        for item in table.iter()? {
+            let (_, json_handle) = item?;
+            let Ok(ad) = serde_json::from_str::<JobAd>(json_handle.value()) else { continue };
+            if let Some(at) = ad.applied_at.filter(|_| ad.is_applied()) { times.push(at); }
+        }
+        Ok(weekday_hour_buckets(times, &chrono::Local))
+    }
+
+    /// Finds cached ads similar to the given one, scored by Jaccard similarity over the
+    /// significant words in headline and occupation. Rejected ads are never suggested.
+    pub async fn find_similar(&self, id: &str, limit: usize) -> Result<Vec<JobAd>> {
+        let target = match self.get_job_ad(id).await? {
+            Some(ad) => ad,
+            None => return Ok(Vec::new()),
+        };
+        let target_words = significant_words(&target);
+        if target_words.is_empty() { return Ok(Vec::new()); }
+
+        let read_txn = self.database.begin_read()?;
+        let table = read_txn.open_table(JOB_ADS_TABLE)?;
+
+        let mut scored: Vec<(f64, JobAd)> = Vec::new();
+        for item in table.iter()? {
+            let (_, json_handle) = item?;
+            let ad: JobAd = match serde_json::from_str(json_handle.value()) {
+                Ok(ad) => ad,
+                Err(_) => continue,
+            };
+            if ad.id == target.id || ad.status == Some(AdStatus::Rejected) { continue; }
+
+            let words = significant_words(&ad);
+            let shared = target_words.intersection(&words).count();
+            if shared == 0 { continue; }
+            let union = target_words.union(&words).count();
+            scored.push((shared as f64 / union as f64, ad));
+        }
+
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(limit);
+        Ok(scored.into_iter().map(|(_, ad)| ad).collect())
+    }
+
+    /// Updates status and automatically sets the corresponding timestamp (applied_at/bookmarked_at).
+    pub async fn update_ad_status(&self, id: &str, status: Option<AdStatus>) -> Result<()> {
+        self.update_ad_status_with_date(id, status, None).await
+    }
+
+    /// Like `update_ad_status`, but `applied_at` is set to the given moment instead of now
+    /// (for applications made outside the app). Ignored for statuses other than Applied.
+    pub async fn update_ad_status_with_date(&self, id: &str, status: Option<AdStatus>, applied_at: Option<DateTime<Utc>>) -> Result<()> {
+        let mut ad = self.get_job_ad(id).await?.ok_or_else(|| DbError::NotFound(format!("annons {}", id)))?;
+        set_status(&mut ad, status, applied_at);
+        self.save_job_ad(&ad).await?;
+        Ok(())
+    }
 
+    /// Marks the ad as Applied at `applied_at` (now if None) through `channel`.
+    pub async fn mark_applied(&self, id: &str, applied_at: Option<DateTime<Utc>>, channel: Option<String>) -> Result<()> {
+        let mut ad = self.get_job_ad(id).await?.ok_or_else(|| DbError::NotFound(format!("annons {}", id)))?;
+        set_status(&mut ad, Some(AdStatus::Applied), applied_at);
+        ad.applied_channel = channel;
         self.save_job_ad(&ad).await?;
         Ok(())
     }
 
+    /// Applies one status to many ads in a single write transaction. Unknown ids are skipped;
+    /// returns how many ads were updated.
+    pub async fn update_ad_status_bulk(&self, ids: &[String], status: Option<AdStatus>) -> Result<usize> {
+        let write_txn = self.database.begin_write()?;
+        let mut updated = 0;
+        {
+            let mut table = write_txn.open_table(JOB_ADS_TABLE)?;
+            for id in ids {
+                let mut ad: JobAd = match table.get(id.as_str())? {
+                    Some(json_handle) => serde_json::from_str(json_handle.value()).map_err(DbError::decode)?,
+                    None => continue,
+                };
+                set_status(&mut ad, status, None);
+                let json = serde_json::to_string(&ad).map_err(DbError::encode)?;
+                table.insert(id.as_str(), json.as_str())?;
+                updated += 1;
+            }
+        }
+        write_txn.commit()?;
+        Ok(updated)
+    }
+
     pub async fn get_job_ad(&self, id: &str) -> Result<Option<JobAd>> {
         let read_txn = self.database.begin_read()?;
         let table = read_txn.open_table(JOB_ADS_TABLE)?;
         if let Some(json_handle) = table.get(id)? {
-            let ad: JobAd = serde_json::from_str(json_handle.value())?;
+            let ad: JobAd = serde_json::from_str(json_handle.value()).map_err(DbError::decode)?;
             Ok(Some(ad))
         } else {
             Ok(None)
         }
     }
 
+    /// The ad's JSON exactly as stored, also when it no longer parses as a `JobAd`.
+    pub async fn get_raw_job_ad(&self, id: &str) -> Result<Option<String>> {
+        let read_txn = self.database.begin_read()?;
+        let table = read_txn.open_table(JOB_ADS_TABLE)?;
+        Ok(table.get(id)?.map(|v| v.value().to_string()))
+    }
+
     pub async fn mark_as_read(&self, id: &str) -> Result<()> {
         if let Some(mut ad) = self.get_job_ad(id).await? {
             ad.is_read = true;
@@ -197,6 +1071,217 @@ impl Db {
         Ok(())
     }
 
+    /// Bookmarks those of `ids` rated at least `threshold` that are still New, after a batch
+    /// rating. Ads the user already handled (saved, rejected, applied) are left as they are.
+    /// Returns the ids that were bookmarked.
+    pub async fn auto_bookmark_rated(&self, ids: &[String], threshold: u8) -> Result<Vec<String>> {
+        let mut bookmarked = Vec::new();
+        if threshold == 0 { return Ok(bookmarked); }
+        for id in ids {
+            let Some(ad) = self.get_job_ad(id).await? else { continue };
+            let is_new = matches!(ad.status.unwrap_or(AdStatus::New), AdStatus::New) && !ad.is_applied();
+            if is_new && ad.rating.is_some_and(|r| r >= threshold) {
+                self.update_ad_status(id, Some(AdStatus::Bookmarked)).await?;
+                bookmarked.push(id.clone());
+            }
+        }
+        Ok(bookmarked)
+    }
+
+    /// Replaces the tags of an ad. Tags are kept on the ad itself, so bookmarked and applied
+    /// ads keep them through `clear_non_bookmarked`.
+    pub async fn set_tags(&self, id: &str, tags: Vec<String>) -> Result<()> {
+        let mut ad = self.get_job_ad(id).await?.ok_or_else(|| DbError::NotFound(format!("annons {}", id)))?;
+        ad.tags = tags;
+        self.save_job_ad(&ad).await?;
+        Ok(())
+    }
+
+    /// Replaces the cached description with the full one; status, tags etc. are kept.
+    pub async fn set_description(&self, id: &str, description: Option<crate::models::Description>) -> Result<JobAd> {
+        let mut ad = self.get_job_ad(id).await?.ok_or_else(|| DbError::NotFound(format!("annons {}", id)))?;
+        ad.description = description;
+        ad.update_experience_years();
+        ad.update_language();
+        self.save_job_ad(&ad).await?;
+        Ok(ad)
+    }
+
+    /// Stars or unstars an ad; the status is left alone.
+    pub async fn set_starred(&self, id: &str, starred: bool) -> Result<()> {
+        let mut ad = self.get_job_ad(id).await?.ok_or_else(|| DbError::NotFound(format!("annons {}", id)))?;
+        ad.starred = starred;
+        self.save_job_ad(&ad).await?;
+        Ok(())
+    }
+
+    /// Pins or unpins an ad; like starring, the status is left alone.
+    pub async fn set_pinned(&self, id: &str, pinned: bool) -> Result<()> {
+        let mut ad = self.get_job_ad(id).await?.ok_or_else(|| DbError::NotFound(format!("annons {}", id)))?;
+        ad.pinned = pinned;
+        self.save_job_ad(&ad).await?;
+        Ok(())
+    }
+
+    /// Every pinned ad regardless of status and month, newest publication first.
+    pub async fn get_pinned_jobs(&self) -> Result<Vec<JobAd>> {
+        let read_txn = self.database.begin_read()?;
+        let table = read_txn.open_table(JOB_ADS_TABLE)?;
+        let mut ads = Vec::new();
+        for item in table.iter()? {
+            let (_, json_handle) = item?;
+            if let Ok(ad) = serde_json::from_str::<JobAd>(json_handle.value()) {
+                if ad.pinned { ads.push(ad); }
+            }
+        }
+        ads.sort_by(|a, b| b.publication_date.cmp(&a.publication_date));
+        Ok(ads)
+    }
+
+    /// Every starred ad regardless of status and month, most recent activity first.
+    pub async fn get_starred_jobs(&self) -> Result<Vec<JobAd>> {
+        let read_txn = self.database.begin_read()?;
+        let table = read_txn.open_table(JOB_ADS_TABLE)?;
+        let mut ads = Vec::new();
+        for item in table.iter()? {
+            let (_, json_handle) = item?;
+            if let Ok(ad) = serde_json::from_str::<JobAd>(json_handle.value()) {
+                if ad.starred { ads.push(ad); }
+            }
+        }
+        ads.sort_by_key(|ad| std::cmp::Reverse(ad.applied_at.or(ad.bookmarked_at).unwrap_or(ad.internal_created_at)));
+        Ok(ads)
+    }
+
+    /// All tags in use with the number of ads carrying each, most used first.
+    pub async fn tag_counts(&self) -> Result<Vec<(String, usize)>> {
+        let read_txn = self.database.begin_read()?;
+        let table = read_txn.open_table(JOB_ADS_TABLE)?;
+
+        let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+        for item in table.iter()? {
+            let (_, json_handle) = item?;
+            let ad: JobAd = match serde_json::from_str(json_handle.value()) {
+                Ok(ad) => ad,
+                Err(_) => continue,
+            };
+            for tag in ad.tags {
+                *counts.entry(tag).or_insert(0) += 1;
+            }
+        }
+
+        let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(counts)
+    }
+
+    /// Deletes New/Rejected ads fetched more than `older_than_days` days ago and returns how
+    /// many were removed. Protected ads (see `JobAd::is_protected`) are kept.
+    pub async fn archive_old(&self, older_than_days: u32) -> Result<usize> {
+        let cutoff = Utc::now() - chrono::Duration::days(older_than_days as i64);
+        let write_txn = self.database.begin_write()?;
+        let removed;
+        {
+            let mut table = write_txn.open_table(JOB_ADS_TABLE)?;
+            let mut keys_to_remove = Vec::new();
+
+            for item in table.iter()? {
+                let (id_handle, json_handle) = item?;
+                let ad: JobAd = match serde_json::from_str(json_handle.value()) {
+                    Ok(ad) => ad,
+                    Err(_) => continue,
+                };
+
+                if !ad.is_protected() && ad.internal_created_at < cutoff {
+                    keys_to_remove.push(id_handle.value().to_string());
+                }
+            }
+
+            removed = keys_to_remove.len();
+            for key in keys_to_remove {
+                table.remove(key.as_str())?;
+            }
+        }
+        write_txn.commit()?;
+        Ok(removed)
+    }
+
+    /// Writes every ad of the month, rejected ones included, to `path` as a JSON array in the
+    /// stored form. The month follows the saved `month_basis`, as in the inbox. Returns how
+    /// many ads were written.
+    pub async fn export_month(&self, year: i32, month: u32, path: &Path) -> Result<usize> {
+        let basis = self.load_settings().await?.unwrap_or_default().month_basis;
+        let ads = self.month_ads(year, month, basis)?;
+        crate::paths::write_atomic(path, serde_json::to_string_pretty(&ads).map_err(DbError::encode)?)?;
+        Ok(ads.len())
+    }
+
+    /// After `export_month`: removes the month's ads that `archive_old` would also remove
+    /// (new or rejected, neither starred nor pinned). Saved and applied ads stay.
+    pub async fn remove_archived_month(&self, year: i32, month: u32) -> Result<usize> {
+        let basis = self.load_settings().await?.unwrap_or_default().month_basis;
+        let ids: Vec<String> = self.month_ads(year, month, basis)?.into_iter()
+            .filter(|ad| !ad.is_protected())
+            .map(|ad| ad.id)
+            .collect();
+        let write_txn = self.database.begin_write()?;
+        {
+            let mut table = write_txn.open_table(JOB_ADS_TABLE)?;
+            for id in &ids { table.remove(id.as_str())?; }
+        }
+        write_txn.commit()?;
+        Ok(ids.len())
+    }
+
+    /// Applied ads in the month as the inbox groups it (`basis`), counted from what is stored,
+    /// so the "Sökta" counter can't drift from the database after status changes.
+    pub async fn applied_count_in_month(&self, year: i32, month: u32, basis: MonthBasis) -> Result<usize> {
+        Ok(self.month_ads(year, month, basis)?.iter().filter(|ad| ad.is_applied()).count())
+    }
+
+    fn month_ads(&self, year: i32, month: u32, basis: MonthBasis) -> Result<Vec<JobAd>> {
+        let read_txn = self.database.begin_read()?;
+        let table = read_txn.open_table(JOB_ADS_TABLE)?;
+        let mut ads = Vec::new();
+        for item in table.iter()? {
+            let (_, json_handle) = item?;
+            let Ok(ad) = serde_json::from_str::<JobAd>(json_handle.value()) else { continue };
+            if ad_in_month(&ad, year, month, basis) { ads.push(ad); }
+        }
+        Ok(ads)
+    }
+
+    /// Deletes saved New/Rejected ads that match the keyword or employer blacklist, so edits to
+    /// the blacklist also apply to ads fetched earlier. Protected ads (acted on, starred or
+    /// pinned) are kept. Returns how many were removed.
+    pub async fn purge_blacklisted(&self, keywords: &[String], employers: &[String]) -> Result<usize> {
+        let write_txn = self.database.begin_write()?;
+        let removed;
+        {
+            let mut table = write_txn.open_table(JOB_ADS_TABLE)?;
+            let mut keys_to_remove = Vec::new();
+
+            for item in table.iter()? {
+                let (id_handle, json_handle) = item?;
+                let ad: JobAd = match serde_json::from_str(json_handle.value()) {
+                    Ok(ad) => ad,
+                    Err(_) => continue,
+                };
+
+                if !ad.is_protected() && (ad.matches_blacklist_keywords(keywords) || ad.is_employer_blacklisted(employers)) {
+                    keys_to_remove.push(id_handle.value().to_string());
+                }
+            }
+
+            removed = keys_to_remove.len();
+            for key in keys_to_remove {
+                table.remove(key.as_str())?;
+            }
+        }
+        write_txn.commit()?;
+        Ok(removed)
+    }
+
     pub async fn clear_non_bookmarked(&self) -> Result<()> {
         let write_txn = self.database.begin_write()?;
         {
@@ -205,10 +1290,9 @@ impl Db {
 
             for item in table.iter()? {
                 let (id_handle, json_handle) = item?;
-                let ad: JobAd = serde_json::from_str(json_handle.value())?;
+                let ad: JobAd = serde_json::from_str(json_handle.value()).map_err(DbError::decode)?;
 
-                let status = ad.status.unwrap_or(AdStatus::New);
-                if status == AdStatus::New || status == AdStatus::Rejected {
+                if !ad.is_protected() {
                     keys_to_remove.push(id_handle.value().to_string());
                 }
             }
@@ -0,0 +1,93 @@
+use crate::ai::AiRanker;
+use crate::api::JobSearchClient;
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+/// Outcome of checking one service.
+#[derive(Debug, Clone)]
+pub struct ServiceCheck {
+    pub ok: bool,
+    pub detail: String,
+}
+
+/// Result of the "Testa anslutning" self-test: one check per external service, so a failed
+/// search can be traced to the network/API or to the AI endpoint.
+#[derive(Debug, Clone)]
+pub struct ConnectionReport {
+    pub api: ServiceCheck,
+    pub ai: ServiceCheck,
+}
+
+impl ConnectionReport {
+    pub fn to_text(&self) -> String {
+        let line = |name: &str, c: &ServiceCheck| format!("{}: {} – {}", name, if c.ok { "OK" } else { "FEL" }, c.detail);
+        format!("{}\n{}", line("JobTech API", &self.api), line("AI (Ollama)", &self.ai))
+    }
+}
+
+/// Where the ads of a priority search went, for explaining a zone that comes up empty.
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilterStats {
+    pub api_returned: usize,
+    pub already_saved: usize,
+    pub blacklisted_employer: usize,
+    /// Removed ads per blacklisted word that matched first.
+    pub blacklisted_words: BTreeMap<String, usize>,
+    /// Hidden by the municipality post-filter of the zone.
+    pub post_filtered: usize,
+}
+
+impl SearchFilterStats {
+    pub fn explain(&self) -> String {
+        let mut parts = vec![format!("API:t returnerade {}", self.api_returned)];
+        for (word, n) in &self.blacklisted_words { parts.push(format!("{} borttagna av svartlistat ord '{}'", n, word)); }
+        if self.blacklisted_employer > 0 { parts.push(format!("{} av svartlistad arbetsgivare", self.blacklisted_employer)); }
+        if self.already_saved > 0 { parts.push(format!("{} fanns redan", self.already_saved)); }
+        if self.post_filtered > 0 { parts.push(format!("{} dolda av kommunfiltret", self.post_filtered)); }
+        parts.join(", ")
+    }
+}
+
+/// Runs both checks concurrently, through `proxy` when one is configured.
+pub async fn test_connections(ollama_url: &str, proxy: Option<&str>) -> ConnectionReport {
+    let api_client = JobSearchClient::with_proxy(proxy);
+    let api_check = async {
+        let start = Instant::now();
+        let api_client = match &api_client {
+            Ok(c) => c,
+            Err(e) => return ServiceCheck { ok: false, detail: format!("{:#}", e) },
+        };
+        match api_client.ping().await {
+            Ok(status) => ServiceCheck { ok: (200..300).contains(&status), detail: format!("HTTP {} på {} ms", status, start.elapsed().as_millis()) },
+            Err(e) => ServiceCheck { ok: false, detail: format!("{:#}", e) },
+        }
+    };
+    let ai_check = async {
+        let start = Instant::now();
+        let ranker = match AiRanker::new(ollama_url, "ollama").and_then(|r| r.with_proxy(proxy)) {
+            Ok(r) => r,
+            Err(e) => return ServiceCheck { ok: false, detail: format!("{:#}", e) },
+        };
+        match ranker.available_models().await {
+            Ok(models) if !models.iter().any(|m| m.split(':').next() == Some(crate::ai::DEFAULT_MODEL)) => {
+                ServiceCheck { ok: false, detail: crate::ai::AiError::ModelNotFound { model: crate::ai::DEFAULT_MODEL.to_string(), available: models }.to_string() }
+            }
+            Ok(models) => ServiceCheck { ok: true, detail: format!("{} modeller på {} ms", models.len(), start.elapsed().as_millis()) },
+            Err(e) => ServiceCheck { ok: false, detail: format!("{} ({:#})", ollama_url, e) },
+        }
+    };
+    let (api, ai) = tokio::join!(api_check, ai_check);
+    ConnectionReport { api, ai }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explain_lists_each_reason() {
+        let mut stats = SearchFilterStats { api_returned: 40, already_saved: 5, post_filtered: 20, ..Default::default() };
+        stats.blacklisted_words.insert("körkort".to_string(), 12);
+        assert_eq!(stats.explain(), "API:t returnerade 40, 12 borttagna av svartlistat ord 'körkort', 5 fanns redan, 20 dolda av kommunfiltret");
+    }
+}
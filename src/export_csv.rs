@@ -0,0 +1,178 @@
+//! Minimal CSV support shared by everything that writes or reads CSV. The delimiter is a
+//! parameter because Swedish Excel expects `;` while most other tools use `,`.
+
+use crate::models::{AdStatus, JobAd};
+
+/// Fields an ad export can contain, by the name used in the column list.
+pub const EXPORT_COLUMNS: &[&str] = &["id", "headline", "employer", "municipality", "occupation_label", "working_hours_label", "rating", "status", "applied_at", "applied_channel", "webpage_url", "tags", "starred"];
+/// The column set used when none is configured.
+pub const DEFAULT_EXPORT_COLUMNS: &str = "id,headline,employer,municipality,status,applied_at";
+
+/// Parses a comma-separated column list. Unknown names fail the whole list, with the valid
+/// names in the message, rather than silently producing a narrower file.
+pub fn parse_columns(spec: &str) -> Result<Vec<&'static str>, String> {
+    let spec = if spec.trim().is_empty() { DEFAULT_EXPORT_COLUMNS } else { spec };
+    spec.split(',').map(|c| c.trim().to_lowercase()).filter(|c| !c.is_empty()).map(|c| {
+        EXPORT_COLUMNS.iter().copied().find(|known| *known == c)
+            .ok_or_else(|| format!("Okänd kolumn '{}'. Giltiga kolumner: {}", c, EXPORT_COLUMNS.join(", ")))
+    }).collect()
+}
+
+fn column_value(ad: &JobAd, column: &str) -> String {
+    match column {
+        "id" => ad.id.clone(),
+        "headline" => ad.headline.clone(),
+        "employer" => ad.employer.as_ref().and_then(|e| e.name.clone()).unwrap_or_default(),
+        "municipality" => ad.workplace_address.as_ref().and_then(|a| a.municipality.clone().or_else(|| a.city.clone())).unwrap_or_default(),
+        "occupation_label" => ad.occupation.as_ref().and_then(|o| o.label.clone()).unwrap_or_default(),
+        "working_hours_label" => ad.working_hours_type.as_ref().and_then(|w| w.label.clone()).unwrap_or_default(),
+        "rating" => ad.rating.map(|r| r.to_string()).unwrap_or_default(),
+        "status" => match ad.status { Some(AdStatus::Rejected) => "avvisad", Some(AdStatus::Bookmarked) => "sparad", Some(AdStatus::ThumbsUp) => "tumme upp", Some(AdStatus::Applied) => "sökt", _ => "ny" }.to_string(),
+        "applied_at" => ad.applied_at.map(|d| d.with_timezone(&chrono::Local).format("%Y-%m-%d").to_string()).unwrap_or_default(),
+        "applied_channel" => ad.applied_channel.clone().unwrap_or_default(),
+        "webpage_url" => ad.webpage_url.clone().unwrap_or_default(),
+        "tags" => ad.tags.join(", "),
+        "starred" => if ad.starred { "ja".to_string() } else { String::new() },
+        _ => String::new(),
+    }
+}
+
+/// One row per ad with the given columns (from `parse_columns`), in canonical order.
+pub fn ads_csv(ads: &[JobAd], columns: &[&str], delimiter: char) -> String {
+    let mut ads = ads.to_vec();
+    canonical_rows_for_export(&mut ads);
+    let mut out = csv_line(columns, delimiter);
+    out.push('\n');
+    for ad in &ads {
+        let values: Vec<String> = columns.iter().map(|c| column_value(ad, c)).collect();
+        out.push_str(&csv_line(&values.iter().map(String::as_str).collect::<Vec<_>>(), delimiter));
+        out.push('\n');
+    }
+    out
+}
+
+/// The one row order for exports: newest application first, ties (and ads without
+/// `applied_at`, which come last) by id. Exports that go through this produce identical
+/// output for the same data, whatever order the database happened to return.
+pub fn canonical_rows_for_export(ads: &mut [JobAd]) {
+    ads.sort_by(|a, b| b.applied_at.cmp(&a.applied_at).then_with(|| a.id.cmp(&b.id)));
+}
+
+/// Quotes a field if it contains the delimiter, a quote or a line break. Embedded quotes
+/// are doubled, as in RFC 4180.
+pub fn quote_csv_field(field: &str, delimiter: char) -> String {
+    if field.contains(delimiter) || field.contains('"') || field.contains('\n') || field.contains('\r') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Joins fields into one CSV line (without the trailing newline).
+pub fn csv_line(fields: &[&str], delimiter: char) -> String {
+    fields.iter().map(|f| quote_csv_field(f, delimiter)).collect::<Vec<_>>().join(&delimiter.to_string())
+}
+
+/// Parses CSV text into rows of fields. Handles quoted fields with embedded delimiters,
+/// doubled quotes and line breaks; `\r\n` and `\n` both end a row. A trailing newline does
+/// not produce an empty last row.
+pub fn parse_csv(input: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == '"' {
+            in_quotes = true;
+        } else if c == delimiter {
+            row.push(std::mem::take(&mut field));
+        } else if c == '\n' || c == '\r' {
+            if c == '\r' && chars.peek() == Some(&'\n') { chars.next(); }
+            row.push(std::mem::take(&mut field));
+            rows.push(std::mem::take(&mut row));
+        } else {
+            field.push(c);
+        }
+    }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_commas_quotes_and_newlines() {
+        let fields = ["Malmö, Lund", "Säg \"hej\"", "rad ett\nrad två", "vanlig"];
+        let text = format!("{}\n{}\n", csv_line(&fields, ','), csv_line(&["a", "b"], ','));
+        let rows = parse_csv(&text, ',');
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], fields.iter().map(|f| f.to_string()).collect::<Vec<_>>());
+        assert_eq!(rows[1], vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn quotes_only_when_needed() {
+        assert_eq!(quote_csv_field("Helsingborg", ';'), "Helsingborg");
+        assert_eq!(quote_csv_field("a;b", ';'), "\"a;b\"");
+        assert_eq!(quote_csv_field("a,b", ';'), "a,b");
+    }
+
+    #[test]
+    fn canonical_order_is_independent_of_input_order() {
+        let ad = |id: &str, day: Option<u32>| {
+            let mut ad: JobAd = serde_json::from_value(serde_json::json!({ "id": id, "headline": "x", "publication_date": "2026-01-01" })).unwrap();
+            ad.applied_at = day.map(|d| chrono::TimeZone::with_ymd_and_hms(&chrono::Utc, 2026, 1, d, 12, 0, 0).unwrap());
+            ad
+        };
+        let mut a = vec![ad("b", Some(5)), ad("c", None), ad("a", Some(5)), ad("d", Some(9))];
+        let mut b = a.clone();
+        b.reverse();
+        canonical_rows_for_export(&mut a);
+        canonical_rows_for_export(&mut b);
+        let ids = |v: &[JobAd]| v.iter().map(|x| x.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&a), vec!["d", "a", "b", "c"]);
+        assert_eq!(ids(&a), ids(&b));
+    }
+
+    #[test]
+    fn column_list_is_validated() {
+        assert_eq!(parse_columns("").unwrap().len(), 6);
+        assert_eq!(parse_columns(" id, Rating ,webpage_url").unwrap(), vec!["id", "rating", "webpage_url"]);
+        let err = parse_columns("id,lön").unwrap_err();
+        assert!(err.contains("'lön'") && err.contains("occupation_label"));
+    }
+
+    #[test]
+    fn missing_values_are_empty_cells() {
+        let mut ad: JobAd = serde_json::from_value(serde_json::json!({ "id": "1", "headline": "x", "working_hours_type": { "label": "Heltid" } })).unwrap();
+        let columns = parse_columns("id,occupation_label,working_hours_label,municipality").unwrap();
+        assert_eq!(ads_csv(std::slice::from_ref(&ad), &columns, ';').lines().nth(1), Some("1;;Heltid;"));
+        ad.working_hours_type = None;
+        assert_eq!(ads_csv(&[ad], &columns, ';').lines().nth(1), Some("1;;;"));
+    }
+
+    #[test]
+    fn parses_crlf_and_empty_fields() {
+        let rows = parse_csv("a;;c\r\n;x;\r\n", ';');
+        assert_eq!(rows, vec![vec!["a".to_string(), String::new(), "c".to_string()], vec![String::new(), "x".to_string(), String::new()]]);
+    }
+}
@@ -7,6 +7,7 @@ use slint::ComponentHandle;
 use slint::Model;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::runtime::Runtime;
 use regex::Regex;
 use chrono::Datelike;
@@ -20,22 +21,49 @@ fn swedish_month_name(month: u32) -> &'static str {
     }
 }
 
+/// Moves `month` (1–12) of `year` by `offset` months, wrapping across years in both
+/// directions.
+pub fn shift_month(year: i32, month: u32, offset: i32) -> (i32, u32) {
+    let total = year * 12 + (month as i32 - 1) + offset;
+    (total.div_euclid(12), total.rem_euclid(12) as u32 + 1)
+}
+
 pub mod models;
 pub mod api;
 pub mod db;
 pub mod ai;
+pub mod reports;
+pub mod presets;
+pub mod export_csv;
+pub mod diagnostics;
+pub mod logos;
+pub mod paths;
+pub mod undo;
+pub mod search;
+pub mod local_api;
+pub mod actions;
+pub mod opener;
+
+pub use crate::db::{open_db_exclusive, open_existing_db, DbError};
 
 use crate::api::JobSearchClient;
-use crate::db::Db;
+use crate::db::{Db, JobFilter};
+use crate::opener::Opener;
 use crate::ui::*;
-use crate::models::AdStatus;
+use crate::models::{AdStatus, GoalPeriod, LanguageFilter, MonthBasis, ThemeMode};
 
 use std::sync::mpsc;
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::EnvFilter;
 
 // Global sender for clipboard operations to keep the provider alive on Linux
-static CLIPBOARD_SENDER: std::sync::OnceLock<mpsc::Sender<String>> = std::sync::OnceLock::new();
+static CLIPBOARD_SENDER: std::sync::OnceLock<mpsc::Sender<ClipboardContent>> = std::sync::OnceLock::new();
+
+enum ClipboardContent {
+    Text(String),
+    /// Rich text with a plain-text alternative for targets that only take text.
+    Html { html: String, alt: String },
+}
 
 // Log buffer to keep track of recent logs for the UI
 static LOG_SENDER: std::sync::OnceLock<mpsc::Sender<String>> = std::sync::OnceLock::new();
@@ -60,28 +88,66 @@ impl std::io::Write for SlintLogWriter {
 /// By spawning a long-lived thread that manages a single Clipboard instance,
 /// we ensure that the OS and other applications have enough time to fetch the data.
 fn setup_clipboard_manager() {
-    let (tx, rx) = mpsc::channel::<String>();
+    let (tx, rx) = mpsc::channel::<ClipboardContent>();
     let _ = CLIPBOARD_SENDER.set(tx);
     
     std::thread::spawn(move || {
         #[cfg(not(target_os = "android"))]
         let mut clipboard = arboard::Clipboard::new().ok();
         
-        while let Ok(text) = rx.recv() {
+        while let Ok(content) = rx.recv() {
             #[cfg(not(target_os = "android"))]
             if let Some(ref mut cb) = clipboard {
-                let _ = cb.set_text(text);
+                match content {
+                    ClipboardContent::Text(text) => { let _ = cb.set_text(text); }
+                    // arboard offers HTML on Windows, macOS and X11/Wayland; when the platform
+                    // clipboard refuses it the plain text is copied instead
+                    ClipboardContent::Html { html, alt } => {
+                        if let Err(e) = cb.set_html(html, Some(alt.clone())) {
+                            tracing::warn!("HTML clipboard unavailable ({}), copying plain text", e);
+                            let _ = cb.set_text(alt);
+                        }
+                    }
+                }
                 tracing::info!("Text copied to clipboard and kept alive.");
             }
             #[cfg(target_os = "android")]
-            let _ = text; // Silence unused warning on Android
+            let _ = content; // Silence unused warning on Android
         }
     });
 }
 
+/// Where exports are written: the user's Downloads folder, or the working directory when
+/// the platform has none.
+fn download_path(file_name: &str) -> std::path::PathBuf {
+    directories::UserDirs::new()
+        .and_then(|u| u.download_dir().map(|d| d.join(file_name)))
+        .unwrap_or_else(|| std::path::PathBuf::from(file_name))
+}
+
+/// Shows `msg` in the status bar; safe to call from the runtime threads.
+fn send_status(ui_weak: &slint::Weak<App>, msg: String) {
+    let ui_weak = ui_weak.clone();
+    let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_status_msg(msg.into()); } });
+}
+
+/// Logs the error and shows it in the error bar until the user closes it. `status_msg` is
+/// overwritten by the next update, so real failures go through here to stay visible.
+fn report_error(ui_weak: &slint::Weak<App>, msg: String) {
+    tracing::error!("{}", msg);
+    let ui_weak = ui_weak.clone();
+    let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_last_error(msg.into()); } });
+}
+
 fn copy_to_clipboard(text: String) {
     if let Some(sender) = CLIPBOARD_SENDER.get() {
-        let _ = sender.send(text);
+        let _ = sender.send(ClipboardContent::Text(text));
+    }
+}
+
+fn copy_html_to_clipboard(html: String, alt: String) {
+    if let Some(sender) = CLIPBOARD_SENDER.get() {
+        let _ = sender.send(ClipboardContent::Html { html, alt });
     }
 }
 
@@ -94,7 +160,7 @@ async fn trigger_sync(db: &Db) {
             let sync_dir = std::path::PathBuf::from(&settings.sync_path);
             if sync_dir.exists() && sync_dir.is_dir() {
                 let db_path = get_db_path();
-                let target_path = sync_dir.join("jobseeker.redb");
+                let target_path = sync_dir.join(paths::DB_FILE_NAME);
                 if let Err(e) = std::fs::copy(&db_path, &target_path) {
                     tracing::error!("Automatisk synk misslyckades: {}", e);
                 } else {
@@ -105,7 +171,15 @@ async fn trigger_sync(db: &Db) {
     }
 }
 
-fn setup_logging() -> (Option<tracing_appender::non_blocking::WorkerGuard>, mpsc::Receiver<String>) {
+/// True when the environment variable is set to "1" or "true".
+fn env_flag(name: &str) -> bool {
+    std::env::var(name).is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+}
+
+/// stdout and the in-app log buffer always get the output. The daily log file in
+/// `paths::log_dir` is skipped when `file_logging` is off or `JOBSEEKER_NO_FILE_LOG=1`;
+/// a second copy in `./logs` under the launch directory is opt-in via `JOBSEEKER_LOCAL_LOG=1`.
+fn setup_logging(file_logging: bool) -> (Option<tracing_appender::non_blocking::WorkerGuard>, mpsc::Receiver<String>) {
     let (tx, rx) = mpsc::channel();
     let _ = LOG_SENDER.set(tx.clone());
     let slint_writer = SlintLogWriter { sender: tx };
@@ -117,16 +191,23 @@ fn setup_logging() -> (Option<tracing_appender::non_blocking::WorkerGuard>, mpsc
 
     #[cfg(not(target_os = "android"))]
     {
-        let log_dir = directories::ProjectDirs::from("com", "GnawSoftware", "Jobseeker").map(|p| p.data_dir().join("logs")).unwrap_or_else(|| std::path::PathBuf::from("logs"));
-        let _ = std::fs::create_dir_all(&log_dir);
-        let file_appender = tracing_appender::rolling::daily(&log_dir, "jobseeker.log");
-        let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
-        let r = registry.with(tracing_subscriber::fmt::layer().with_writer(non_blocking).with_ansi(false));
-        r.init();
-        (Some(guard), rx)
+        let file_logging = file_logging && !env_flag("JOBSEEKER_NO_FILE_LOG");
+        let file_layer = |dir: &std::path::Path| {
+            let _ = std::fs::create_dir_all(dir);
+            let (non_blocking, guard) = tracing_appender::non_blocking(tracing_appender::rolling::daily(dir, "jobseeker.log"));
+            (tracing_subscriber::fmt::layer().with_writer(non_blocking).with_ansi(false), guard)
+        };
+        let (main_layer, guard) = if file_logging { let (layer, guard) = file_layer(&paths::log_dir()); (Some(layer), Some(guard)) } else { (None, None) };
+        let local_layer = (file_logging && env_flag("JOBSEEKER_LOCAL_LOG")).then(|| {
+            let (layer, guard) = file_layer(std::path::Path::new("logs"));
+            let _ = LOCAL_LOG_GUARD.set(guard);
+            layer
+        });
+        registry.with(main_layer).with(local_layer).init();
+        (guard, rx)
     }
     #[cfg(target_os = "android")]
-    { registry.init(); (None, rx) }
+    { let _ = file_logging; registry.init(); (None, rx) }
 }
 
 trait ToWriter { fn into_writer(self) -> mpsc_writer::MpscWriter; }
@@ -140,22 +221,7 @@ mod mpsc_writer {
     }
 }
 
-fn get_db_path() -> std::path::PathBuf {
-    #[cfg(target_os = "android")]
-    {
-        let path = std::path::PathBuf::from("/data/data/com.gnawsoftware.jobseeker/files"); 
-        let _ = std::fs::create_dir_all(&path); 
-        return path.join("jobseeker.redb"); 
-    }
-    #[cfg(not(target_os = "android"))]
-    {
-        directories::ProjectDirs::from("com", "GnawSoftware", "Jobseeker").map(|p| {
-            let d = p.data_dir();
-            let _ = std::fs::create_dir_all(d);
-            d.join("jobseeker.redb")
-        }).unwrap_or_else(|| std::path::PathBuf::from("jobseeker.redb"))
-    }
-}
+fn get_db_path() -> std::path::PathBuf { paths::db_path() }
 
 fn normalize_locations(input: &str) -> String {
     input.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()).map(|s| {
@@ -164,9 +230,522 @@ fn normalize_locations(input: &str) -> String {
     }).filter(|s| !s.is_empty()).collect::<Vec<_>>().join(", ")
 }
 
+/// Dropdown entry for "no occupation field".
+const OCCUPATION_FIELD_ALL: &str = "Alla yrkesområden";
+
+/// The only place that maps stored settings to the Slint `AppSettings` struct. Both
+/// directions destructure exhaustively, so a field added on either side without being
+/// mapped here fails to compile instead of silently being dropped on save.
+fn to_ui_settings(s: &crate::models::AppSettings) -> AppSettings {
+    let crate::models::AppSettings {
+        version: _,
+        keywords,
+        blacklist_keywords,
+        blacklist_employers,
+        locations_p1,
+        locations_p2,
+        locations_p3,
+        my_profile,
+        ollama_url,
+        sync_path,
+        app_min_count,
+        app_goal_count,
+        show_motivation,
+        month_basis,
+        ai_system_prompt,
+        ai_scale_max,
+        ai_concurrency,
+        auto_archive_days,
+        goal_period,
+        max_inbox_rows,
+        http_proxy,
+        export_columns,
+        occupation_field,
+        applied_line_template,
+        hide_applied_in_all,
+        use_synonyms,
+        synonyms,
+        hide_expired,
+        theme,
+        disable_file_log,
+        min_vacancies,
+        auto_reject_rules,
+        min_rating_filter,
+        keywords_p1,
+        keywords_p2,
+        keywords_p3,
+        blacklist_p1,
+        blacklist_p2,
+        blacklist_p3,
+        max_experience_years,
+        max_batch_open,
+        developer_mode,
+        request_delay_ms,
+        min_salary,
+        hide_unknown_salary,
+        sort_by_salary,
+        logo_cache_max_mb,
+        language_filter,
+        auto_bookmark_rating,
+        local_api_port,
+        default_age_days,
+        open_marks_applied,
+    } = s.clone();
+    AppSettings {
+        keywords: keywords.into(),
+        blacklist_keywords: blacklist_keywords.into(),
+        blacklist_employers: blacklist_employers.into(),
+        locations_p1: normalize_locations(&locations_p1).into(),
+        locations_p2: normalize_locations(&locations_p2).into(),
+        locations_p3: normalize_locations(&locations_p3).into(),
+        my_profile: my_profile.into(),
+        ollama_url: ollama_url.into(),
+        sync_path: sync_path.into(),
+        app_min_count,
+        app_goal_count,
+        show_motivation,
+        month_basis: match month_basis { MonthBasis::Activity => 0, MonthBasis::Publication => 1 },
+        ai_system_prompt: ai_system_prompt.into(),
+        ai_scale_max: ai_scale_max as i32,
+        ai_concurrency: ai_concurrency as i32,
+        auto_archive_days: auto_archive_days as i32,
+        goal_period: match goal_period { GoalPeriod::Month => 0, GoalPeriod::Week => 1 },
+        max_inbox_rows: max_inbox_rows as i32,
+        http_proxy: http_proxy.unwrap_or_default().into(),
+        export_columns: export_columns.into(),
+        // The dropdown shows labels; an id we have no label for is shown as is
+        occupation_field: occupation_field
+            .map(|id| crate::api::occupation_field_label(&id).map(|l| l.to_string()).unwrap_or(id))
+            .unwrap_or_default()
+            .into(),
+        applied_line_template: applied_line_template.into(),
+        hide_applied_in_all,
+        use_synonyms,
+        synonyms: synonyms.into(),
+        hide_expired,
+        theme: match theme { ThemeMode::Dark => 0, ThemeMode::Light => 1, ThemeMode::System => 2 },
+        disable_file_log,
+        min_vacancies: min_vacancies as i32,
+        auto_reject_keywords: auto_reject_rules.keywords.into(),
+        auto_reject_expired: auto_reject_rules.expired_deadline,
+        min_rating_filter: min_rating_filter as i32,
+        keywords_p1: keywords_p1.into(),
+        keywords_p2: keywords_p2.into(),
+        keywords_p3: keywords_p3.into(),
+        blacklist_p1: blacklist_p1.into(),
+        blacklist_p2: blacklist_p2.into(),
+        blacklist_p3: blacklist_p3.into(),
+        max_experience_years: max_experience_years as i32,
+        max_batch_open: max_batch_open as i32,
+        developer_mode,
+        request_delay_ms: request_delay_ms as i32,
+        min_salary: min_salary as i32,
+        hide_unknown_salary,
+        sort_by_salary,
+        logo_cache_max_mb: logo_cache_max_mb as i32,
+        language_filter: match language_filter { LanguageFilter::All => 0, LanguageFilter::Swedish => 1, LanguageFilter::English => 2 },
+        auto_bookmark_rating: auto_bookmark_rating as i32,
+        local_api_port: local_api_port as i32,
+        default_age_days: default_age_days as i32,
+        open_marks_applied,
+    }
+}
+
+fn from_ui_settings(s: &AppSettings) -> crate::models::AppSettings {
+    let AppSettings {
+        keywords,
+        blacklist_keywords,
+        blacklist_employers,
+        locations_p1,
+        locations_p2,
+        locations_p3,
+        my_profile,
+        ollama_url,
+        sync_path,
+        app_min_count,
+        app_goal_count,
+        show_motivation,
+        month_basis,
+        ai_system_prompt,
+        ai_scale_max,
+        ai_concurrency,
+        auto_archive_days,
+        goal_period,
+        max_inbox_rows,
+        http_proxy,
+        export_columns,
+        occupation_field,
+        applied_line_template,
+        hide_applied_in_all,
+        use_synonyms,
+        synonyms,
+        hide_expired,
+        theme,
+        disable_file_log,
+        min_vacancies,
+        auto_reject_keywords,
+        auto_reject_expired,
+        min_rating_filter,
+        keywords_p1,
+        keywords_p2,
+        keywords_p3,
+        blacklist_p1,
+        blacklist_p2,
+        blacklist_p3,
+        max_experience_years,
+        max_batch_open,
+        developer_mode,
+        request_delay_ms,
+        min_salary,
+        hide_unknown_salary,
+        sort_by_salary,
+        logo_cache_max_mb,
+        language_filter,
+        auto_bookmark_rating,
+        local_api_port,
+        default_age_days,
+        open_marks_applied,
+    } = s.clone();
+    crate::models::AppSettings {
+        version: crate::models::SETTINGS_VERSION,
+        keywords: keywords.to_string(),
+        blacklist_keywords: blacklist_keywords.to_string(),
+        blacklist_employers: blacklist_employers.to_string(),
+        locations_p1: locations_p1.to_string(),
+        locations_p2: locations_p2.to_string(),
+        locations_p3: locations_p3.to_string(),
+        my_profile: my_profile.to_string(),
+        ollama_url: ollama_url.to_string(),
+        sync_path: sync_path.to_string(),
+        app_min_count,
+        app_goal_count,
+        show_motivation,
+        month_basis: if month_basis == 1 { MonthBasis::Publication } else { MonthBasis::Activity },
+        ai_system_prompt: ai_system_prompt.to_string(),
+        ai_scale_max: ai_scale_max.clamp(1, 100) as u8,
+        ai_concurrency: ai_concurrency.clamp(1, 16) as u32,
+        auto_archive_days: auto_archive_days.max(0) as u32,
+        goal_period: if goal_period == 1 { GoalPeriod::Week } else { GoalPeriod::Month },
+        max_inbox_rows: max_inbox_rows.max(0) as u32,
+        http_proxy: Some(http_proxy.trim().to_string()).filter(|p| !p.is_empty()),
+        export_columns: export_columns.to_string(),
+        occupation_field: match occupation_field.as_str() {
+            "" | OCCUPATION_FIELD_ALL => None,
+            label => Some(crate::api::occupation_field_id(label).unwrap_or(label).to_string()),
+        },
+        // An emptied field falls back to the default rather than copying blank lines
+        applied_line_template: if applied_line_template.trim().is_empty() {
+            crate::reports::DEFAULT_APPLIED_LINE_TEMPLATE.to_string()
+        } else {
+            applied_line_template.to_string()
+        },
+        hide_applied_in_all,
+        use_synonyms,
+        synonyms: synonyms.to_string(),
+        hide_expired,
+        theme: match theme { 1 => ThemeMode::Light, 2 => ThemeMode::System, _ => ThemeMode::Dark },
+        disable_file_log,
+        min_vacancies: min_vacancies.max(0) as u32,
+        auto_reject_rules: crate::models::AutoRejectRules {
+            keywords: auto_reject_keywords.to_string(),
+            expired_deadline: auto_reject_expired,
+        },
+        min_rating_filter: min_rating_filter.clamp(0, 100) as u8,
+        keywords_p1: keywords_p1.to_string(),
+        keywords_p2: keywords_p2.to_string(),
+        keywords_p3: keywords_p3.to_string(),
+        blacklist_p1: blacklist_p1.to_string(),
+        blacklist_p2: blacklist_p2.to_string(),
+        blacklist_p3: blacklist_p3.to_string(),
+        max_experience_years: max_experience_years.max(0) as u32,
+        max_batch_open: max_batch_open.max(0) as u32,
+        developer_mode,
+        request_delay_ms: request_delay_ms.max(0) as u32,
+        min_salary: min_salary.max(0) as u32,
+        hide_unknown_salary,
+        sort_by_salary,
+        logo_cache_max_mb: logo_cache_max_mb.max(0) as u32,
+        language_filter: match language_filter { 1 => LanguageFilter::Swedish, 2 => LanguageFilter::English, _ => LanguageFilter::All },
+        auto_bookmark_rating: auto_bookmark_rating.clamp(0, 100) as u8,
+        local_api_port: local_api_port.clamp(0, u16::MAX as i32) as u16,
+        default_age_days: default_age_days.max(0) as u32,
+        open_marks_applied,
+    }
+}
+
+/// Drops ads rated below `min_rating_filter` from every month list, and applied ads
+/// (`hide_applied_in_all`), expired new ones (`hide_expired`) and ads with too few positions
+/// (`min_vacancies`) from the All view. A tag filter is a view of its own and keeps them; count applied ads before
+/// calling this.
+fn filter_all_view(ads: &mut Vec<crate::models::JobAd>, settings: &crate::models::AppSettings, tag: Option<&str>) {
+    if settings.min_rating_filter > 0 {
+        ads.retain(|ad| ad.pinned || ad.meets_min_rating(settings.min_rating_filter));
+    }
+    if tag.is_some() { return; }
+    if settings.hide_applied_in_all {
+        ads.retain(|ad| ad.pinned || !ad.is_applied());
+    }
+    if settings.hide_expired {
+        let today = chrono::Local::now().date_naive();
+        // Ads the user acted on stay, so a saved ad doesn't vanish the day after its deadline
+        ads.retain(|ad| ad.pinned || ad.status.is_some() || ad.is_applied() || !ad.is_expired_on(today));
+    }
+    if settings.min_vacancies > 1 {
+        ads.retain(|ad| ad.pinned || ad.has_min_vacancies(settings.min_vacancies));
+    }
+    if settings.max_experience_years > 0 {
+        ads.retain(|ad| ad.pinned || ad.status.is_some() || ad.within_experience(settings.max_experience_years));
+    }
+    if settings.min_salary > 0 {
+        ads.retain(|ad| ad.pinned || ad.status.is_some() || ad.meets_min_salary(settings.min_salary, settings.hide_unknown_salary));
+    }
+    if settings.language_filter != LanguageFilter::All {
+        ads.retain(|ad| ad.pinned || ad.status.is_some() || settings.language_filter.allows(ad.language().as_deref()));
+    }
+}
+
+/// Whether the ad's municipality is one of `zone_codes`. The ad may carry a code or a name,
+/// so both sides are compared as codes; ads without a municipality are outside every zone.
+fn in_zone(ad: &crate::models::JobAd, zone_codes: &[String]) -> bool {
+    ad.workplace_address.as_ref()
+        .and_then(|a| a.municipality.as_deref())
+        .and_then(JobSearchClient::municipality_code)
+        .is_some_and(|code| zone_codes.contains(&code))
+}
+
+/// Tags whose text the detail view shows bold or italic; every other tag is stripped.
+const BOLD_TAGS: &[&str] = &["strong", "b"];
+const ITALIC_TAGS: &[&str] = &["em", "i"];
+/// Markers `format_description` leaves where an emphasis tag opened or closed.
+const BOLD_ON: char = '\u{2}';
+const BOLD_OFF: char = '\u{3}';
+const ITALIC_ON: char = '\u{4}';
+const ITALIC_OFF: char = '\u{5}';
+
+/// The description as text: list markup becomes bullets/newlines, emphasis tags become the
+/// markers above and the remaining tags are stripped.
+fn format_description(ad: &crate::models::JobAd, re_html: &Regex) -> String {
+    let raw_desc = ad.description.as_ref().and_then(|d| d.text.as_ref()).map(|s| s.as_str()).unwrap_or("");
+    let mut formatted_desc = raw_desc.replace("<li>", "\n • ").replace("</li>", "").replace("<ul>", "\n").replace("</ul>", "\n").replace("<br>", "\n").replace("<br/>", "\n").replace("<br />", "\n").replace("<p>", "\n\n").replace("</p>", "");
+    for (tags, on, off) in [(BOLD_TAGS, BOLD_ON, BOLD_OFF), (ITALIC_TAGS, ITALIC_ON, ITALIC_OFF)] {
+        for tag in tags { formatted_desc = formatted_desc.replace(&format!("<{}>", tag), &on.to_string()).replace(&format!("</{}>", tag), &off.to_string()); }
+    }
+    let mut desc = re_html.replace_all(&formatted_desc, "").to_string();
+    if ad.driving_license_required { desc.push_str(&format!("\n\n{}KÖRKORT:{}\n • Krav på körkort\n", BOLD_ON, BOLD_OFF)); }
+    desc
+}
+
+/// Splits the description into the lines the detail view shows. A Slint Text has one font
+/// weight and style, so a line is bold (or italic) only when all of its text is; a line with a
+/// few emphasized words stays plain. Runs of blank lines shrink to one paragraph gap.
+fn description_runs(ad: &crate::models::JobAd, re_html: &Regex) -> Vec<DescRun> {
+    let (mut bold, mut italic) = (false, false);
+    let mut runs: Vec<DescRun> = Vec::new();
+    for line in format_description(ad, re_html).split('\n') {
+        let mut text = String::new();
+        let (mut all_bold, mut all_italic) = (true, true);
+        for c in line.chars() {
+            match c {
+                BOLD_ON => bold = true,
+                BOLD_OFF => bold = false,
+                ITALIC_ON => italic = true,
+                ITALIC_OFF => italic = false,
+                c => {
+                    if !c.is_whitespace() { all_bold &= bold; all_italic &= italic; }
+                    text.push(c);
+                }
+            }
+        }
+        let text = text.trim_end();
+        if text.trim().is_empty() {
+            if runs.last().is_some_and(|r| !r.text.is_empty()) { runs.push(DescRun::default()); }
+        } else {
+            runs.push(DescRun { text: text.into(), bold: all_bold, italic: all_italic });
+        }
+    }
+    if runs.last().is_some_and(|r| r.text.is_empty()) { runs.pop(); }
+    runs
+}
+
+/// Converts a stored ad into the Slint list/detail entry. Slint plain text can't show
+/// HTML, so the description is flattened; the detail view gets its emphasis separately
+/// from `description_runs`.
+fn job_to_entry(ad: crate::models::JobAd, re_html: &Regex) -> JobEntry {
+    let clean_desc: String = format_description(&ad, re_html)
+        .chars()
+        .filter(|c| ![BOLD_ON, BOLD_OFF, ITALIC_ON, ITALIC_OFF].contains(c))
+        .collect();
+    let expired = ad.is_expired_on(chrono::Local::now().date_naive());
+    JobEntry {
+        id: ad.id.into(),
+        title: ad.headline.into(),
+        employer: ad.employer.and_then(|e| e.name).unwrap_or_default().into(),
+        location: ad
+            .workplace_address
+            .and_then(|a| a.city)
+            .unwrap_or_default()
+            .into(),
+        occupation: ad
+            .occupation
+            .and_then(|o| o.label)
+            .unwrap_or_default()
+            .into(),
+        occupation_field: ad
+            .occupation_field
+            .and_then(|o| o.label)
+            .unwrap_or_default()
+            .into(),
+        description: clean_desc.into(),
+        date: ad.publication_date.split('T').next().unwrap_or("").into(),
+        apply_url: ad
+            .application_details
+            .and_then(|d| d.url)
+            .unwrap_or_default()
+            .into(),
+        webpage_url: ad.webpage_url.unwrap_or_default().into(),
+        rating: ad.rating.unwrap_or(0) as i32,
+        status: match ad.status {
+            Some(AdStatus::Rejected) => 1,
+            Some(AdStatus::Bookmarked) => 2,
+            Some(AdStatus::ThumbsUp) => 3,
+            Some(AdStatus::Applied) => 4,
+            _ => 0,
+        },
+        status_text: "".into(),
+        tags: ad.tags.join(", ").into(),
+        applied_channel: ad.applied_channel.unwrap_or_default().into(),
+        starred: ad.starred,
+        pinned: ad.pinned,
+        vacancies: ad.vacancies.unwrap_or(0) as i32,
+        salary: ad.salary_max.unwrap_or(0) as i32,
+        salary_text: ad.salary_description.unwrap_or_default().into(),
+        expired,
+        filtered_out: false,
+        checked: false,
+    }
+}
+
+/// Puts every pinned ad at the front of `ads`, whether or not the month, tag or "published
+/// from" filter loaded it; ads already in the list aren't repeated.
+fn merge_pinned(ads: &mut Vec<crate::models::JobAd>, pinned: Vec<crate::models::JobAd>) {
+    if pinned.is_empty() { return; }
+    ads.retain(|ad| !ad.pinned);
+    ads.splice(0..0, pinned);
+}
+
+/// Rows in the "Senast visade" list.
+const RECENT_VIEWS_SHOWN: usize = 20;
+
+/// Ids of `views` in order with repeats dropped, so an ad opened twice is listed once, at
+/// its latest view.
+fn distinct_view_ids(views: &[crate::db::RecentView]) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    views.iter().filter(|v| seen.insert(v.id.as_str())).map(|v| v.id.clone()).collect()
+}
+
+/// Inbox order: pinned rows first, then newest publication date first.
+fn inbox_order(a: &JobEntry, b: &JobEntry) -> std::cmp::Ordering {
+    b.pinned.cmp(&a.pinned).then_with(|| b.date.cmp(&a.date))
+}
+
+/// Like `inbox_order`, but by highest salary after the pinned rows; rows without a read
+/// salary (0) come last, newest first among themselves.
+fn salary_order(a: &JobEntry, b: &JobEntry) -> std::cmp::Ordering {
+    b.pinned.cmp(&a.pinned).then_with(|| b.salary.cmp(&a.salary)).then_with(|| b.date.cmp(&a.date))
+}
+
+/// The inbox ordering the settings ask for.
+fn list_order(settings: &crate::models::AppSettings) -> fn(&JobEntry, &JobEntry) -> std::cmp::Ordering {
+    if settings.sort_by_salary { salary_order } else { inbox_order }
+}
+
+/// Hides entries whose title, employer or occupation don't contain the quick-filter text, or
+/// that are outside the chosen occupation field (`facet`, empty = all); pinned entries always
+/// stay visible. Purely client-side: the loaded month stays in the model, so selection
+/// indices survive.
+fn apply_quick_filter(entries: &mut [JobEntry], filter: &str, facet: &str, settings: &AppSettings) {
+    let needles = if filter.trim().is_empty() { Vec::new() }
+        else if settings.use_synonyms { crate::models::expand_keyword(filter, &settings.synonyms) }
+        else { vec![filter.trim().to_lowercase()] };
+    for e in entries.iter_mut() {
+        let outside_facet = !facet.is_empty() && e.occupation_field != facet;
+        e.filtered_out = !e.pinned && (outside_facet || !needles.is_empty() && ![&e.title, &e.employer, &e.occupation].iter().any(|f| { let f = f.to_lowercase(); needles.iter().any(|n| f.contains(n.as_str())) }));
+    }
+}
+
+/// Keeps the first `max` rows (0 = all); the rows are already sorted pinned and then newest
+/// first, so those are kept. Returns the notice for the status bar when something was cut.
+fn cap_inbox_rows(entries: &mut Vec<JobEntry>, max: u32) -> Option<String> {
+    let total = entries.len();
+    if max == 0 || total <= max as usize { return None; }
+    entries.truncate(max as usize);
+    Some(format!("visar {} av {} – förfina sökningen", max, total))
+}
+
+/// Pause between tabs when several ads are opened at once; some browsers and desktops drop
+/// part of a burst.
+const BATCH_OPEN_DELAY: std::time::Duration = std::time::Duration::from_millis(700);
+
+/// Today's file from the daily rolling appender in `setup_logging`; its suffix is the UTC date.
+fn current_log_file() -> std::path::PathBuf {
+    paths::log_dir().join(format!("jobseeker.log.{}", chrono::Utc::now().format("%Y-%m-%d")))
+}
+
+/// Opens a file or folder with the desktop's default program. Android has no file manager
+/// to hand a path to, so nothing is opened there.
+fn open_path(opener: &dyn Opener, path: &std::path::Path) -> bool {
+    #[cfg(target_os = "android")]
+    { let _ = (opener, path); false }
+    #[cfg(not(target_os = "android"))]
+    { path.exists() && opener.open(&path.to_string_lossy()).is_ok() }
+}
+
+/// Links to open for the saved and thumbs-up rows the list shows, at most `max` (0 = all):
+/// the ad page, or the application link for ads without one.
+fn saved_links(entries: &[JobEntry], max: u32) -> Vec<String> {
+    let links = entries.iter()
+        .filter(|e| !e.filtered_out && (e.status == 2 || e.status == 3))
+        .filter_map(|e| [&e.webpage_url, &e.apply_url].into_iter().find(|u| !u.is_empty()).map(|u| u.to_string()));
+    if max == 0 { links.collect() } else { links.take(max as usize).collect() }
+}
+
+/// Remembers a status change for "Ångra" and enables it.
+fn record_undo(undo_stack: &std::sync::Mutex<undo::UndoStack>, ui_weak: &slint::Weak<App>, change: Vec<undo::PreviousStatus>) {
+    if change.is_empty() { return; }
+    if let Ok(mut stack) = undo_stack.lock() { stack.push(change); }
+    let u = ui_weak.clone();
+    let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = u.upgrade() { ui.set_can_undo(true); } });
+}
+
+/// Puts an ad whose status change was undone back into the list: its row is replaced, or,
+/// if a reject had removed it, re-inserted where the inbox order puts it. An ad that is
+/// rejected again is taken out, as a reject does.
+fn restore_row(rows: &mut Vec<JobEntry>, entry: JobEntry) {
+    let existing = rows.iter().position(|j| j.id == entry.id);
+    match (existing, entry.status == 1) {
+        (Some(pos), true) => { rows.remove(pos); }
+        (Some(pos), false) => rows[pos] = entry,
+        (None, true) => {}
+        (None, false) => {
+            let pos = rows.iter().position(|j| inbox_order(&entry, j) == std::cmp::Ordering::Less).unwrap_or(rows.len());
+            rows.insert(pos, entry);
+        }
+    }
+}
+
+/// Replaces the inbox model, keeping the current quick filter applied to the new rows.
+fn set_job_list(ui: &App, mut entries: Vec<JobEntry>) {
+    apply_quick_filter(&mut entries, &ui.get_quick_filter(), &ui.get_active_facet(), &ui.get_settings());
+    ui.set_jobs(Rc::new(slint::VecModel::from(entries)).into());
+}
+
 fn setup_ui(ui: &App, rt: Arc<Runtime>, db: Arc<Db>, log_rx: mpsc::Receiver<String>) {
     let ui_weak = ui.as_weak();
     spawn_log_task(ui_weak.clone(), log_rx);
+    let undo_stack = Arc::new(std::sync::Mutex::new(undo::UndoStack::default()));
+    let opener: Arc<dyn Opener> = Arc::new(crate::opener::SystemOpener);
+    rt.spawn(watch_goal_period(db.clone(), ui_weak.clone()));
+    rt.spawn(start_local_api(db.clone()));
 
     let db_for_stats = db.clone();
     let ui_for_stats = ui.as_weak();
@@ -183,18 +762,26 @@ fn setup_ui(ui: &App, rt: Arc<Runtime>, db: Arc<Db>, log_rx: mpsc::Receiver<Stri
 
         if let Some((year, month)) = month_info {
             rt.spawn(async move {
-                if let Ok(ads) = db.get_filtered_jobs(&[], Some(year), Some(month)).await {
+                let month_basis = db.load_settings().await.unwrap_or_default().unwrap_or_default().month_basis;
+                if let Ok(ads) = db.get_filtered_jobs(JobFilter { year: Some(year), month: Some(month), month_basis, ..Default::default() }).await {
                     let total_count = ads.len() as i32;
                     let (mut applied, mut bookmarked, mut thumbsup, mut rejected) = (0, 0, 0, 0);
                     let mut counts = std::collections::HashMap::new();
                     for ad in ads {
-                        match ad.status { Some(AdStatus::Applied) => applied += 1, Some(AdStatus::Bookmarked) => bookmarked += 1, Some(AdStatus::ThumbsUp) => thumbsup += 1, Some(AdStatus::Rejected) => rejected += 1, _ => {} }
+                        match ad.status { _ if ad.is_applied() => applied += 1, Some(AdStatus::Bookmarked) => bookmarked += 1, Some(AdStatus::ThumbsUp) => thumbsup += 1, Some(AdStatus::Rejected) => rejected += 1, _ => {} }
                         if let Some(kw) = ad.search_keyword { *counts.entry(kw).or_insert(0) += 1; }
                     }
                     let mut stats_vec: Vec<KeywordStat> = counts.into_iter().map(|(name, count)| KeywordStat { name: name.into(), count }).collect();
                     stats_vec.sort_by(|a, b| b.count.cmp(&a.count)); stats_vec.truncate(10);
+                    let drafts = db.list_drafts().await.unwrap_or_default();
+                    let heat = db.applied_by_weekday_hour().await.unwrap_or_default();
                     let _ = slint::invoke_from_event_loop(move || {
                         if let Some(ui) = ui_weak.upgrade() {
+                            set_apply_heat(&ui, &heat);
+                            // Keep what the user already ticked in the drafts picker
+                            let checked: std::collections::HashSet<String> = ui.get_report_drafts().iter().filter(|d| d.checked).map(|d| d.id.to_string()).collect();
+                            let drafts: Vec<DraftEntry> = drafts.into_iter().map(|(id, headline)| DraftEntry { checked: checked.contains(&id), id: id.into(), headline: headline.into() }).collect();
+                            ui.set_report_drafts(Rc::new(slint::VecModel::from(drafts)).into());
                             ui.set_total_ads_count(total_count); ui.set_applied_count(applied); ui.set_bookmarked_count(bookmarked);
                             ui.set_thumbsup_count(thumbsup); ui.set_rejected_count(rejected); ui.set_top_keywords(Rc::new(slint::VecModel::from(stats_vec)).into());
                         }
@@ -207,77 +794,163 @@ fn setup_ui(ui: &App, rt: Arc<Runtime>, db: Arc<Db>, log_rx: mpsc::Receiver<Stri
     let rs_cmd = refresh_stats.clone();
     ui.on_stats_requested(move || rs_cmd());
 
+    // Callback: tick a draft for the report's ANSÖKNINGSBREV section
+    let ui_rd = ui.as_weak();
+    ui.on_toggle_report_draft(move |idx| {
+        if let Some(ui) = ui_rd.upgrade() {
+            let drafts = ui.get_report_drafts();
+            if let Some(mut entry) = drafts.row_data(idx as usize) { entry.checked = !entry.checked; drafts.set_row_data(idx as usize, entry); }
+        }
+    });
+
+    // Callback: Board (tavla per status för aktiv månad)
+    let (db_br, ui_br, rt_br) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_board_requested(move || {
+        let (db, ui_weak) = (db_br.clone(), ui_br.clone());
+        let (y, m) = active_year_month(&ui_weak);
+        rt_br.spawn(async move { refresh_board(&db, ui_weak, y, m).await; });
+    });
+
+    // Callback: Board Move (kort till föregående/nästa kolumn)
+    let (db_bm, ui_bm, rt_bm, undo_bm) = (db.clone(), ui.as_weak(), rt.clone(), undo_stack.clone());
+    ui.on_board_move(move |id, status, direction| {
+        let Some(target) = board_step(status, direction) else { return };
+        let (db, ui_weak, id_str, undo_stack) = (db_bm.clone(), ui_bm.clone(), id.to_string(), undo_bm.clone());
+        let (y, m) = active_year_month(&ui_weak);
+        rt_bm.spawn(async move {
+            let new_status = match target { 2 => Some(AdStatus::Bookmarked), 3 => Some(AdStatus::ThumbsUp), 4 => Some(AdStatus::Applied), _ => None };
+            let before = db.get_job_ad(&id_str).await.ok().flatten();
+            match db.update_ad_status(&id_str, new_status).await {
+                Ok(()) => {
+                    record_undo(&undo_stack, &ui_weak, before.iter().map(undo::PreviousStatus::of).collect());
+                    trigger_sync(&db).await;
+                    if status == 4 || target == 4 { refresh_applied_counts(&db, ui_weak.clone(), y, m).await; }
+                    refresh_board(&db, ui_weak, y, m).await;
+                }
+                Err(e) => report_error(&ui_weak, format!("Kunde inte flytta kortet: {}", e)),
+            }
+        });
+    });
+
     // Callback: Export Report
     let db_export = db.clone();
     let ui_export = ui.as_weak();
     let rt_export = rt.clone();
-    ui.on_export_requested(move |method, _format, include_jobs, include_params, include_analysis| {
+    let opener_export = opener.clone();
+    ui.on_export_requested(move |method, format, include_jobs, include_params, include_analysis| {
         let db = db_export.clone();
+        let opener = opener_export.clone();
         let ui_weak = ui_export.clone();
         let method = method.to_string();
-        let data = if let Some(ui) = ui_weak.upgrade() { Some((ui.get_active_month().to_string(), ui.get_active_month_display().to_string())) } else { None };
+        let format = format.to_string();
+        let data = if let Some(ui) = ui_weak.upgrade() { Some((ui.get_active_month().to_string(), ui.get_active_month_display().to_string(), ui.get_report_drafts().iter().filter(|d| d.checked).map(|d| d.id.to_string()).collect::<Vec<_>>())) } else { None };
 
-        if let Some((month_str, month_display)) = data {
+        if let Some((month_str, month_display, picked_drafts)) = data {
             rt_export.spawn(async move {
                 let parts: Vec<&str> = month_str.split('-').collect();
                 let year = parts[0].parse().unwrap_or(2026);
                 let month = parts[1].parse().unwrap_or(1);
                 let settings = db.load_settings().await.unwrap_or_default().unwrap_or_default();
-                
-                let mut report = format!("AKTIVITETSRAPPORT - {}
-==========================================\n\n", month_display.to_uppercase());
-                if include_params {
-                    report.push_str(&format!("SÖKPARAMETRAR:\n• Sökord: {}
-• Prio 1: {}
-• Prio 2: {}
-\n", settings.keywords, normalize_locations(&settings.locations_p1), normalize_locations(&settings.locations_p2)));
-                }
-                if include_jobs {
-                    if let Ok(ads) = db.get_filtered_jobs(&[AdStatus::Applied], Some(year), Some(month)).await {
-                        report.push_str(&format!("SÖKTA JOBB ({} st):\n", ads.len()));
-                        for ad in ads {
-                            let date = ad.applied_at.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "Okänt datum".to_string());
-                            report.push_str(&format!("• {}: {}, {} ({})\n", date, ad.employer.and_then(|e| e.name).unwrap_or_default(), ad.headline, ad.workplace_address.and_then(|a| a.city).unwrap_or_default()));
-                            if let Some(url) = ad.webpage_url { report.push_str(&format!("  Länk: {}\n", url)); }
+                let msg = match method.as_str() {
+                    "summary" => {
+                        // Veckosammanställningen gäller hela historiken, inte bara vald månad
+                        let ads = db.get_filtered_jobs(JobFilter { statuses: &[AdStatus::Applied], month_basis: settings.month_basis, ..Default::default() }).await.unwrap_or_default();
+                        let file_name = format!("summary-{}.csv", chrono::Local::now().format("%Y-%m-%d"));
+                        let file_path = download_path(&file_name);
+                        match reports::write_if_changed(&file_path, &reports::application_summary_csv(&ads)) {
+                            Ok(true) => format!("Sammanställning sparad: {}", file_path.display()),
+                            Ok(false) => format!("Sammanställningen är oförändrad: {}", file_name),
+                            Err(e) => {
+                                report_error(&ui_weak, format!("Kunde inte spara sammanställning: {}", e));
+                                "Kunde inte spara sammanställningen".to_string()
+                            }
                         }
-                        report.push_str("\n");
                     }
-                }
-                if include_analysis {
-                    if let Ok(ads) = db.get_filtered_jobs(&[], Some(year), Some(month)).await {
-                        let app = ads.iter().filter(|a| a.status == Some(AdStatus::Applied)).count();
-                        let rej = ads.iter().filter(|a| a.status == Some(AdStatus::Rejected)).count();
-                        report.push_str(&format!("AKTIVITETSANALYS:\n• Totalt granskade: {}
-• Konvertering: {} sökta, {} avvisade\n", ads.len(), app, rej));
+                    "stats" => {
+                        // Helårsstatistik som DOCX inför möten med handläggaren
+                        let file_path = download_path(&format!("ansokningsstatistik-{}.docx", year));
+                        match reports::generate_stats_report(&db, year, &file_path).await {
+                            Ok(()) => format!("Statistik sparad: {}", file_path.display()),
+                            Err(e) => {
+                                report_error(&ui_weak, format!("Kunde inte spara statistik: {:#}", e));
+                                "Kunde inte spara statistiken".to_string()
+                            }
+                        }
                     }
-                }
-                report.push_str("\nGenererad via Jobseeker 2026\n");
-
-                if method == "clipboard" || method == "email" {
-                    copy_to_clipboard(report.clone());
-                    if method == "clipboard" {
-                        let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_status_msg("Rapport kopierad till urklipp!".into()); } });
-                    } else {
-                        let subject_text = format!("Aktivitetsrapport - {}", month_display);
-                        let subject = urlencoding::encode(&subject_text);
-                        let body_text = if report.len() > 1500 { 
-                            format!("Rapporten är kopierad till ditt urklipp - klistra in den här!\n\n(Texten var för lång för direktlänk: {} tecken)", report.len()) 
-                        } else { 
-                            report 
-                        };
-                        let body_encoded = urlencoding::encode(&body_text);
-                        let mailto = format!("mailto:?subject={}&body={}", subject, body_encoded);
-                        let _ = webbrowser::open(&mailto);
-                        let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_status_msg("Öppnar e-post (rapport kopierad till urklipp)".into()); } });
+                    "applied" => {
+                        // Månadens sökta jobb som rader för att klistra in i ett mejl, ingen fil
+                        let ads = db.get_filtered_jobs(JobFilter { statuses: &[AdStatus::Applied], year: Some(year), month: Some(month), month_basis: settings.month_basis, ..Default::default() }).await.unwrap_or_default();
+                        if ads.is_empty() {
+                            format!("Inga sökta jobb i {}", month_display)
+                        } else {
+                            copy_to_clipboard(reports::applied_lines(&ads, &settings.applied_line_template));
+                            format!("{} sökta jobb kopierade till urklipp", ads.len())
+                        }
                     }
-                } else if method == "file" {
-                    let file_name = format!("jobb-rapport-{}.txt", month_str);
-                    let file_path = directories::UserDirs::new().and_then(|u| u.download_dir().map(|d| d.join(&file_name))).unwrap_or_else(|| std::path::PathBuf::from(&file_name));
-                    if std::fs::write(&file_path, report).is_ok() {
-                        tracing::info!("Rapport sparad till: {:?}", file_path);
-                        let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_status_msg(format!("Rapport sparad: {}", file_name).into()); } });
+                    "ads" => {
+                        // Alla annonser i vald månad, med kolumnerna från inställningarna
+                        match crate::export_csv::parse_columns(&settings.export_columns) {
+                            Err(e) => e,
+                            Ok(columns) => {
+                                let ads = db.get_filtered_jobs(JobFilter { year: Some(year), month: Some(month), month_basis: settings.month_basis, ..Default::default() }).await.unwrap_or_default();
+                                let file_name = format!("annonser-{}.csv", month_str);
+                                let file_path = download_path(&file_name);
+                                match reports::write_if_changed(&file_path, &crate::export_csv::ads_csv(&ads, &columns, ';')) {
+                                    Ok(true) => format!("Annonser sparade: {}", file_path.display()),
+                                    Ok(false) => format!("Annonsfilen är oförändrad: {}", file_name),
+                                    Err(e) => {
+                                        report_error(&ui_weak, format!("Kunde inte spara annonser: {}", e));
+                                        "Kunde inte spara annonsfilen".to_string()
+                                    }
+                                }
+                            }
+                        }
                     }
-                }
+                    "clipboard" | "email" | "file" => {
+                        // Nothing ticked keeps the default: the letters of the month's applied jobs
+                        let options = reports::ReportOptions { include_jobs, include_params, include_analysis, selected_drafts: (!picked_drafts.is_empty()).then_some(picked_drafts) };
+                        let structured = reports::activity_report(&db, &settings, year, month, &month_display, options).await;
+                        match method.as_str() {
+                            "clipboard" => {
+                                copy_to_clipboard(structured.to_text());
+                                "Rapport kopierad till urklipp!".to_string()
+                            }
+                            "email" => {
+                                let report = structured.to_text();
+                                copy_to_clipboard(report.clone());
+                                let body_text = if report.len() > 1500 {
+                                    format!("Rapporten är kopierad till ditt urklipp - klistra in den här!\n\n(Texten var för lång för direktlänk: {} tecken)", report.len())
+                                } else {
+                                    report
+                                };
+                                let mailto = reports::mailto_link(&format!("Aktivitetsrapport - {}", month_display), &body_text);
+                                match opener.open(&mailto) {
+                                    Ok(()) => "Öppnar e-post (rapport kopierad till urklipp)".to_string(),
+                                    Err(e) => {
+                                        report_error(&ui_weak, format!("Kunde inte öppna e-postprogrammet: {}", e));
+                                        "Rapport kopierad till urklipp (e-post kunde inte öppnas)".to_string()
+                                    }
+                                }
+                            }
+                            _ => {
+                                let report_format = reports::ReportFormat::from_ui(&format);
+                                let file_path = download_path(&format!("jobb-rapport-{}.{}", month_str, report_format.extension()));
+                                match structured.write_to(&file_path, report_format) {
+                                    Ok(()) => {
+                                        tracing::info!("Rapport sparad till: {:?}", file_path);
+                                        format!("Rapport sparad: {}", file_path.display())
+                                    }
+                                    Err(e) => {
+                                        report_error(&ui_weak, format!("Kunde inte spara rapporten: {:#}", e));
+                                        "Kunde inte spara rapporten".to_string()
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    _ => return,
+                };
+                send_status(&ui_weak, msg);
             });
         }
     });
@@ -287,83 +960,940 @@ fn setup_ui(ui: &App, rt: Arc<Runtime>, db: Arc<Db>, log_rx: mpsc::Receiver<Stri
     ui.on_month_offset(move |offset| {
         rs_month();
         let (db, rt, ui_weak) = (db_month.clone(), rt_month.clone(), ui_month.clone());
-        let data = if let Some(ui) = ui_weak.upgrade() { Some(ui.get_active_month().to_string()) } else { None };
-        if let Some(cm) = data {
-            let mut parts = cm.split('-');
-            let year = parts.next().and_then(|s| s.parse::<i32>().ok()).unwrap_or(2026);
-            let month = parts.next().and_then(|s| s.parse::<i32>().ok()).unwrap_or(1);
-            let mut nm = month + offset as i32; let mut ny = year;
-            while nm <= 0 { nm += 12; ny -= 1; } while nm > 12 { nm -= 12; ny += 1; }
-            let nms = format!("{:04}-{:02}", ny, nm as u32);
-            let nmd = format!("{} {}", swedish_month_name(nm as u32), ny);
-            if let Some(ui) = ui_weak.upgrade() { ui.set_active_month(nms.clone().into()); ui.set_active_month_display(nmd.clone().into()); }
+        let active_tag = ui_weak.upgrade().map(|ui| ui.get_active_tag().to_string());
+        if let Some(active_tag) = active_tag {
+            let (year, month) = active_year_month(&ui_weak);
+            let (ny, nm) = shift_month(year, month, offset);
+            let since = since_date(&ui_weak);
+            let nms = format!("{:04}-{:02}", ny, nm);
+            let nmd = format!("{} {}", swedish_month_name(nm), ny);
+            if let Some(ui) = ui_weak.upgrade() { ui.set_active_month(nms.clone().into()); ui.set_active_month_display(nmd.clone().into()); ui.set_filter_explanation("".into()); ui.set_starred_only(false); ui.set_recent_only(false); }
             let ui_f = ui_weak.clone();
             rt.spawn(async move {
-                if let Ok(ads) = db.get_filtered_jobs(&[], Some(ny), Some(nm as u32)).await {
-                    let app_count = ads.iter().filter(|ad| ad.status == Some(AdStatus::Applied)).count() as i32;
+                let settings = db.load_settings().await.unwrap_or_default().unwrap_or_default();
+                let employer_blacklist = settings.blacklisted_employers();
+                let tag = (!active_tag.is_empty()).then_some(active_tag);
+                if let Ok(mut ads) = db.get_filtered_jobs(JobFilter { year: Some(ny), month: Some(nm), employer_blacklist: Some(employer_blacklist.as_slice()), tag: tag.as_deref(), month_basis: settings.month_basis, published_after: since, ..Default::default() }).await {
+                    let app_count = ads.iter().filter(|ad| ad.is_applied()).count() as i32;
+                    let goal_count = goal_applied_count(&db, settings.goal_period, ny, nm).await;
+                    let new_since = new_since_last_view(&db, ny, nm, &ads).await;
+                    if let Ok(pinned) = db.get_pinned_jobs().await { merge_pinned(&mut ads, pinned); }
+                    filter_all_view(&mut ads, &settings, tag.as_deref());
                     let re_html = Regex::new(r"<[^>]*>").expect("Invalid regex");
-                    let entries: Vec<JobEntry> = ads.into_iter().map(|ad| {
-                        let raw_desc = ad.description.as_ref().and_then(|d| d.text.as_ref()).map(|s| s.as_str()).unwrap_or("");
-                        let formatted_desc = raw_desc.replace("<li>", "\n • ").replace("</li>", "").replace("<ul>", "\n").replace("</ul>", "\n").replace("<br>", "\n").replace("<br/>", "\n").replace("<br />", "\n").replace("<p>", "\n\n").replace("</p>", "").replace("<strong>", "").replace("</strong>", "").replace("<b>", "").replace("</b>", "");
-                        let mut clean_desc = re_html.replace_all(&formatted_desc, "").to_string();
-                        if ad.driving_license_required { clean_desc.push_str("\n\nKÖRKORT:\n • Krav på körkort\n"); }
-                        JobEntry { id: ad.id.into(), title: ad.headline.into(), employer: ad.employer.and_then(|e| e.name).unwrap_or_default().into(), location: ad.workplace_address.and_then(|a| a.city).unwrap_or_default().into(), description: clean_desc.into(), date: ad.publication_date.split('T').next().unwrap_or("").into(), apply_url: ad.application_details.and_then(|d| d.url).unwrap_or_default().into(), rating: ad.rating.unwrap_or(0) as i32, status: match ad.status { Some(AdStatus::Rejected) => 1, Some(AdStatus::Bookmarked) => 2, Some(AdStatus::ThumbsUp) => 3, Some(AdStatus::Applied) => 4, _ => 0 }, status_text: "".into() }
-                    }).collect();
-                    let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_f.upgrade() { ui.set_jobs(Rc::new(slint::VecModel::from(entries)).into()); ui.set_applied_count(app_count); } });
+                    let mut entries: Vec<JobEntry> = ads.into_iter().map(|ad| job_to_entry(ad, &re_html)).collect();
+                    if settings.sort_by_salary { entries.sort_by(salary_order); }
+                    let notice = cap_inbox_rows(&mut entries, settings.max_inbox_rows);
+                    let empty_hint = empty_inbox_hint(&EmptyInbox::NotSearched, &settings);
+                    let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_f.upgrade() { ui.set_empty_hint(empty_hint.into()); set_job_list(&ui, entries); ui.set_applied_count(app_count); ui.set_goal_applied_count(goal_count); ui.set_new_since_visit(new_since); if let Some(n) = notice { ui.set_status_msg(n.into()); } } });
                 }
             });
         }
     });
 
     // Callback: Free Search
-    let (api_s, db_s, ui_s, rt_s) = (Arc::new(JobSearchClient::new()), db.clone(), ui.as_weak(), rt.clone());
-    ui.on_search_pressed(move |q| { let (api, db, ui_weak, q_str) = (api_s.clone(), db_s.clone(), ui_s.clone(), q.to_string()); rt_s.spawn(async move { let settings = db.load_settings().await.unwrap_or_default().unwrap_or_default(); perform_search(api, db, ui_weak, None, Some(q_str), settings).await; }); });
+    let (db_s, ui_s, rt_s) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_search_pressed(move |q| { let (db, ui_weak, q_str) = (db_s.clone(), ui_s.clone(), q.to_string()); let since = since_date(&ui_weak); rt_s.spawn(async move { let settings = db.load_settings().await.unwrap_or_default().unwrap_or_default(); perform_search(db, ui_weak, None, Some(q_str), false, since, settings).await; }); });
 
     // Callback: Prio Search
-    let (api_p, db_p, ui_p, rt_p) = (Arc::new(JobSearchClient::new()), db.clone(), ui.as_weak(), rt.clone());
-    ui.on_search_prio(move |p| { let (api, db, ui_weak) = (api_p.clone(), db_p.clone(), ui_p.clone()); rt_p.spawn(async move { let settings = db.load_settings().await.unwrap_or_default().unwrap_or_default(); perform_search(api, db, ui_weak, Some(p), None, settings).await; }); });
+    let (db_p, ui_p, rt_p) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_search_prio(move |p, full_refresh| { let (db, ui_weak) = (db_p.clone(), ui_p.clone()); let since = since_date(&ui_weak); rt_p.spawn(async move { let settings = db.load_settings().await.unwrap_or_default().unwrap_or_default(); perform_search(db, ui_weak, Some(p), None, full_refresh, since, settings).await; }); });
 
     // Callback: Job Action
-    let (db_a, ui_a, rt_a) = (db.clone(), ui.as_weak(), rt.clone());
+    let (db_a, ui_a, rt_a, undo_a, opener_a) = (db.clone(), ui.as_weak(), rt.clone(), undo_stack.clone(), opener.clone());
     ui.on_job_action(move |id, act| {
-        let (db, ui_weak, id_str, action) = (db_a.clone(), ui_a.clone(), id.to_string(), act.to_string());
+        let (db, ui_weak, id_str, action, undo_stack, opener) = (db_a.clone(), ui_a.clone(), id.to_string(), act.to_string(), undo_a.clone(), opener_a.clone());
+        let (y, m) = active_year_month(&ui_weak);
         rt_a.spawn(async move {
-            if action == "open" || action == "apply_direct" { if let Ok(Some(ad)) = db.get_job_ad(&id_str).await { let url = if action == "open" { ad.webpage_url } else { ad.application_details.and_then(|d| d.url) }; if let Some(u) = url { let _ = webbrowser::open(&u); } } return; }
-            let target = match action.as_str() { "reject" => AdStatus::Rejected, "save" => AdStatus::Bookmarked, "thumbsup" => AdStatus::ThumbsUp, "apply" => AdStatus::Applied, _ => return };
-            let current = db.get_job_ad(&id_str).await.ok().flatten().and_then(|ad| ad.status);
-            let new_status = if current == Some(target) { None } else { Some(target) };
+            // "open" goes straight to the application when there is a direct link; "open_ad" always shows the ad page
+            if action == "copy_draft_html" {
+                match db.get_application_draft(&id_str).await {
+                    Ok(Some(draft)) if !draft.trim().is_empty() => {
+                        copy_html_to_clipboard(reports::draft_to_html(&draft), draft);
+                        let _ = slint::invoke_from_event_loop(move || {
+                            if let Some(ui) = ui_weak.upgrade() {
+                                ui.set_status_msg("Ansökningsbrevet kopierat (formaterat)".into());
+                            }
+                        });
+                    }
+                    Ok(_) => {
+                        let _ = slint::invoke_from_event_loop(move || {
+                            if let Some(ui) = ui_weak.upgrade() {
+                                ui.set_status_msg(
+                                    "Inget ansökningsbrev sparat för annonsen".into(),
+                                );
+                            }
+                        });
+                    }
+                    Err(e) => {
+                        report_error(&ui_weak, format!("Kunde inte läsa ansökningsbrevet: {}", e))
+                    }
+                }
+                return;
+            }
+            if let Some(link) = actions::AdLink::from_action(&action) {
+                if let Ok(Some(ad)) = db.get_job_ad(&id_str).await {
+                    if let Err(e) = actions::open_ad_link(opener.as_ref(), &ad, link) {
+                        tracing::warn!("Kunde inte öppna länken för {}: {:#}", id_str, e);
+                    }
+                }
+                return;
+            }
+            if action == "open_apply" {
+                let msg = match actions::open_and_mark_applied(&db, &id_str, opener.as_ref()).await
+                {
+                    Ok(actions::OpenApplied::Marked { previous }) => {
+                        record_undo(&undo_stack, &ui_weak, vec![previous]);
+                        trigger_sync(&db).await;
+                        refresh_applied_counts(&db, ui_weak.clone(), y, m).await;
+                        let (id, ui_j) = (id_str.clone(), ui_weak.clone());
+                        let _ = slint::invoke_from_event_loop(move || {
+                            if let Some(ui) = ui_j.upgrade() {
+                                let mut vec: Vec<JobEntry> = ui.get_jobs().iter().collect();
+                                if let Some(job) = vec.iter_mut().find(|j| j.id == id) {
+                                    job.status = 4;
+                                    ui.set_jobs(Rc::new(slint::VecModel::from(vec)).into());
+                                }
+                            }
+                        });
+                        "Ansökan öppnad och annonsen markerad som sökt".to_string()
+                    }
+                    Ok(actions::OpenApplied::AlreadyApplied) => {
+                        "Ansökan öppnad (redan markerad som sökt)".to_string()
+                    }
+                    Ok(actions::OpenApplied::NoLink) => {
+                        "Annonsen har ingen länk att öppna".to_string()
+                    }
+                    Ok(actions::OpenApplied::OpenFailed) => {
+                        "Kunde inte öppna webbläsaren – annonsen markerades inte som sökt"
+                            .to_string()
+                    }
+                    Ok(actions::OpenApplied::NotFound) => "Annonsen finns inte längre".to_string(),
+                    Err(e) => {
+                        report_error(&ui_weak, format!("Kunde inte markera som sökt: {}", e));
+                        return;
+                    }
+                };
+                let _ = slint::invoke_from_event_loop(move || {
+                    if let Some(ui) = ui_weak.upgrade() {
+                        ui.set_status_msg(msg.into());
+                    }
+                });
+                return;
+            }
+            let target = match action.as_str() {
+                "reject" => AdStatus::Rejected,
+                "save" => AdStatus::Bookmarked,
+                "thumbsup" => AdStatus::ThumbsUp,
+                "apply" => AdStatus::Applied,
+                _ => return,
+            };
+            let before = db.get_job_ad(&id_str).await.ok().flatten();
+            let current = before.as_ref().and_then(|ad| ad.status);
+            let new_status = if current == Some(target) {
+                None
+            } else {
+                Some(target)
+            };
             if db.update_ad_status(&id_str, new_status).await.is_ok() {
+                record_undo(
+                    &undo_stack,
+                    &ui_weak,
+                    before.iter().map(undo::PreviousStatus::of).collect(),
+                );
+                trigger_sync(&db).await;
+                // Rejecting or saving an applied ad takes it out of the count too
+                if target == AdStatus::Applied || before.as_ref().is_some_and(|ad| ad.is_applied())
+                {
+                    refresh_applied_counts(&db, ui_weak.clone(), y, m).await;
+                }
+                let status_int = match new_status {
+                    Some(AdStatus::Rejected) => 1,
+                    Some(AdStatus::Bookmarked) => 2,
+                    Some(AdStatus::ThumbsUp) => 3,
+                    Some(AdStatus::Applied) => 4,
+                    _ => 0,
+                };
+                let _ = slint::invoke_from_event_loop(move || {
+                    if let Some(ui) = ui_weak.upgrade() {
+                        let jobs = ui.get_jobs();
+                        let mut vec: Vec<JobEntry> = jobs.iter().collect();
+                        if let Some(pos) = vec.iter().position(|j| j.id == id_str) {
+                            if status_int == 1 {
+                                vec.remove(pos);
+                            } else {
+                                vec[pos].status = status_int;
+                            }
+                            ui.set_jobs(Rc::new(slint::VecModel::from(vec)).into());
+                        }
+                    }
+                });
+            }
+        });
+    });
+
+    // Callback: Share Job (sammanfattning till urklipp eller som e-post)
+    let (db_sh, ui_sh, rt_sh, opener_sh) = (db.clone(), ui.as_weak(), rt.clone(), opener.clone());
+    ui.on_share_job(move |id, how| {
+        let (db, ui_weak, id_str, how, opener) = (db_sh.clone(), ui_sh.clone(), id.to_string(), how.to_string(), opener_sh.clone());
+        rt_sh.spawn(async move {
+            if how == "json" {
+                // The record exactly as stored, for attaching to bug reports
+                let msg = match db.get_raw_job_ad(&id_str).await {
+                    Ok(Some(json)) => { copy_to_clipboard(json); "Annonsens JSON kopierad till urklipp".to_string() }
+                    Ok(None) => "Annonsen finns inte längre".to_string(),
+                    Err(e) => { report_error(&ui_weak, format!("Kunde inte läsa annonsen: {}", e)); "Kunde inte kopiera annonsen".to_string() }
+                };
+                let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_status_msg(msg.into()); } });
+                return;
+            }
+            let Ok(Some(ad)) = db.get_job_ad(&id_str).await else { return };
+            let text = reports::ad_share_text(&ad);
+            let msg = if how == "checklist" {
+                match reports::requirements_checklist(&ad) {
+                    Some(list) => { copy_to_clipboard(list); "Kraven kopierade som checklista".to_string() }
+                    None => "Annonsen listar inga krav".to_string(),
+                }
+            } else if how == "email" {
+                match actions::email_ad(opener.as_ref(), &ad) {
+                    Ok(()) => "Öppnar e-post".to_string(),
+                    Err(e) => { copy_to_clipboard(text); report_error(&ui_weak, format!("Kunde inte öppna e-postprogrammet: {}", e)); "Annonsen kopierad till urklipp".to_string() }
+                }
+            } else {
+                copy_to_clipboard(text);
+                "Annonsen kopierad till urklipp".to_string()
+            };
+            let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_status_msg(msg.into()); } });
+        });
+    });
+
+    // Callback: Apply With Date (sökt utanför appen, t.ex. förra veckan)
+    let (db_ad, ui_ad, rt_ad) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_apply_with_date(move |id, date, channel| {
+        let (db, ui_weak, id_str) = (db_ad.clone(), ui_ad.clone(), id.to_string());
+        let (y, m) = active_year_month(&ui_weak);
+        let applied_at = match parse_applied_date(&date) {
+            Ok(d) => d,
+            Err(msg) => {
+                if let Some(ui) = ui_weak.upgrade() {
+                    ui.set_status_msg(msg.into());
+                }
+                return;
+            }
+        };
+        rt_ad.spawn(async move {
+            let channel = crate::models::parse_applied_channel(&channel);
+            let channel_ui = channel.clone().unwrap_or_default();
+            if db.mark_applied(&id_str, applied_at, channel).await.is_ok() {
                 trigger_sync(&db).await;
-                let status_int = match new_status { Some(AdStatus::Rejected) => 1, Some(AdStatus::Bookmarked) => 2, Some(AdStatus::ThumbsUp) => 3, Some(AdStatus::Applied) => 4, _ => 0 };
-                let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { let jobs = ui.get_jobs(); let mut vec: Vec<JobEntry> = jobs.iter().collect(); if let Some(pos) = vec.iter().position(|j| j.id == id_str) { if status_int == 1 { vec.remove(pos); } else { vec[pos].status = status_int; } ui.set_jobs(Rc::new(slint::VecModel::from(vec)).into()); } } });
+                refresh_applied_counts(&db, ui_weak.clone(), y, m).await;
+                let _ = slint::invoke_from_event_loop(move || {
+                    if let Some(ui) = ui_weak.upgrade() {
+                        let mut vec: Vec<JobEntry> = ui.get_jobs().iter().collect();
+                        if let Some(pos) = vec.iter().position(|j| j.id == id_str) {
+                            vec[pos].status = 4;
+                            vec[pos].applied_channel = channel_ui.into();
+                            ui.set_jobs(Rc::new(slint::VecModel::from(vec)).into());
+                        }
+                        ui.set_status_msg("Markerad som sökt".into());
+                    }
+                });
+            }
+        });
+    });
+
+    // Callback: Toggle Checked (flerval i inkorgen)
+    let ui_tc = ui.as_weak();
+    ui.on_toggle_checked(move |idx| {
+        if let Some(ui) = ui_tc.upgrade() {
+            let jobs = ui.get_jobs();
+            if let Some(mut entry) = jobs.row_data(idx as usize) { entry.checked = !entry.checked; jobs.set_row_data(idx as usize, entry); }
+            ui.set_checked_count(jobs.iter().filter(|j| j.checked).count() as i32);
+        }
+    });
+
+    // Callback: Bulk Action (avvisa/spara/tumme upp på alla markerade). Deliberately no bulk
+    // "apply", so the goal counter only moves for real applications.
+    let (db_bulk, ui_bulk, rt_bulk, undo_bulk) = (db.clone(), ui.as_weak(), rt.clone(), undo_stack.clone());
+    ui.on_bulk_action(move |act| {
+        let (db, ui_weak, undo_stack) = (db_bulk.clone(), ui_bulk.clone(), undo_bulk.clone());
+        let (target, status_int) = match act.as_str() {
+            "reject" => (AdStatus::Rejected, 1),
+            "save" => (AdStatus::Bookmarked, 2),
+            "thumbsup" => (AdStatus::ThumbsUp, 3),
+            _ => return,
+        };
+        let ids: Vec<String> = match ui_weak.upgrade() {
+            Some(ui) => ui.get_jobs().iter().filter(|j| j.checked).map(|j| j.id.to_string()).collect(),
+            None => return,
+        };
+        if ids.is_empty() {
+            return;
+        }
+        let (y, m) = active_year_month(&ui_weak);
+        rt_bulk.spawn(async move {
+            let mut before = Vec::new();
+            for id in &ids {
+                if let Ok(Some(ad)) = db.get_job_ad(id).await {
+                    before.push(undo::PreviousStatus::of(&ad));
+                }
+            }
+            match db.update_ad_status_bulk(&ids, Some(target)).await {
+                Ok(n) => {
+                    let touched_applied = before.iter().any(|p| p.status == Some(AdStatus::Applied));
+                    record_undo(&undo_stack, &ui_weak, before);
+                    trigger_sync(&db).await;
+                    if touched_applied {
+                        refresh_applied_counts(&db, ui_weak.clone(), y, m).await;
+                    }
+                    let _ = slint::invoke_from_event_loop(move || {
+                        if let Some(ui) = ui_weak.upgrade() {
+                            let mut vec: Vec<JobEntry> = ui.get_jobs().iter().collect();
+                            if status_int == 1 {
+                                vec.retain(|j| !ids.contains(&j.id.to_string()));
+                            }
+                            for j in vec.iter_mut() {
+                                if ids.contains(&j.id.to_string()) {
+                                    j.status = status_int;
+                                }
+                                j.checked = false;
+                            }
+                            ui.set_selected_index(-1);
+                            ui.set_jobs(Rc::new(slint::VecModel::from(vec)).into());
+                            ui.set_checked_count(0);
+                            ui.set_select_mode(false);
+                            ui.set_status_msg(format!("Uppdaterade {} annonser", n).into());
+                        }
+                    });
+                }
+                Err(e) => report_error(&ui_weak, format!("Massuppdatering misslyckades: {}", e)),
+            }
+        });
+    });
+
+    // Callback: Open Saved (the count is asked for first, for the confirmation)
+    let ui_sc = ui.as_weak();
+    ui.on_saved_link_count(move || ui_sc.upgrade().map(|ui| saved_links(&ui.get_jobs().iter().collect::<Vec<_>>(), ui.get_settings().max_batch_open.max(0) as u32).len() as i32).unwrap_or(0));
+    let (ui_os, rt_os, opener_os) = (ui.as_weak(), rt.clone(), opener.clone());
+    ui.on_open_saved(move || {
+        let (ui_weak, opener) = (ui_os.clone(), opener_os.clone());
+        let links = match ui_weak.upgrade() { Some(ui) => saved_links(&ui.get_jobs().iter().collect::<Vec<_>>(), ui.get_settings().max_batch_open.max(0) as u32), None => return };
+        if links.is_empty() { return; }
+        rt_os.spawn(async move {
+            let mut opened = 0;
+            for (i, link) in links.iter().enumerate() {
+                if i > 0 { tokio::time::sleep(BATCH_OPEN_DELAY).await; }
+                if opener.open(&reports::clean_url(link)).is_ok() { opened += 1; } else { tracing::warn!("Kunde inte öppna {}", link); }
+            }
+            let total = links.len();
+            let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_status_msg(format!("Öppnade {} av {} sparade annonser i webbläsaren", opened, total).into()); } });
+        });
+    });
+
+    // Callback: Undo (Ångra; puts the ads of the last status change back and shows them again)
+    let (db_u, ui_u, rt_u, undo_u) = (db.clone(), ui.as_weak(), rt.clone(), undo_stack.clone());
+    ui.on_undo(move || {
+        let (db, ui_weak) = (db_u.clone(), ui_u.clone());
+        let (change, more) = match undo_u.lock() { Ok(mut stack) => (stack.pop(), !stack.is_empty()), Err(_) => return };
+        let Some(change) = change else { return };
+        let (y, m) = active_year_month(&ui_weak);
+        if let Some(ui) = ui_weak.upgrade() { ui.set_can_undo(more); }
+        rt_u.spawn(async move {
+            match undo::restore(&db, &change).await {
+                Ok(ads) => {
+                    trigger_sync(&db).await;
+                    refresh_applied_counts(&db, ui_weak.clone(), y, m).await;
+                    let re_html = Regex::new(r"<[^>]*>").expect("Invalid regex");
+                    let entries: Vec<JobEntry> = ads.into_iter().map(|ad| job_to_entry(ad, &re_html)).collect();
+                    let _ = slint::invoke_from_event_loop(move || {
+                        if let Some(ui) = ui_weak.upgrade() {
+                            let n = entries.len();
+                            let mut vec: Vec<JobEntry> = ui.get_jobs().iter().collect();
+                            for entry in entries { restore_row(&mut vec, entry); }
+                            ui.set_selected_index(-1);
+                            set_job_list(&ui, vec);
+                            ui.set_status_msg(format!("Ångrade ändringen för {} annons{}", n, if n == 1 { "" } else { "er" }).into());
+                        }
+                    });
+                }
+                Err(e) => report_error(&ui_weak, format!("Kunde inte ångra: {}", e)),
+            }
+        });
+    });
+
+    // Callback: Compare (exakt två markerade annonser sida vid sida)
+    let (db_cmp, ui_cmp, rt_cmp) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_compare_checked(move || {
+        let (db, ui_weak) = (db_cmp.clone(), ui_cmp.clone());
+        let ids: Vec<String> = match ui_weak.upgrade() { Some(ui) => ui.get_jobs().iter().filter(|j| j.checked).map(|j| j.id.to_string()).collect(), None => return };
+        let [left, right] = ids.as_slice() else {
+            if let Some(ui) = ui_weak.upgrade() { ui.set_status_msg("Markera exakt två annonser att jämföra".into()); }
+            return;
+        };
+        let (left, right) = (left.clone(), right.clone());
+        rt_cmp.spawn(async move {
+            let (Ok(Some(a)), Ok(Some(b))) = (db.get_job_ad(&left).await, db.get_job_ad(&right).await) else {
+                report_error(&ui_weak, "Kunde inte läsa annonserna som ska jämföras".to_string());
+                return;
+            };
+            let scale_max = db.load_settings().await.unwrap_or_default().unwrap_or_default().ai_scale_max;
+            let rows: Vec<(&str, String, String)> = compare_values(&a, scale_max).into_iter().zip(compare_values(&b, scale_max)).map(|((label, l), (_, r))| (label, l, r)).collect();
+            let _ = slint::invoke_from_event_loop(move || {
+                if let Some(ui) = ui_weak.upgrade() {
+                    let rows: Vec<CompareRow> = rows.into_iter().map(|(label, left, right)| CompareRow { label: label.into(), left: left.into(), right: right.into() }).collect();
+                    ui.set_compare_rows(Rc::new(slint::VecModel::from(rows)).into());
+                    ui.set_compare_open(true);
+                }
+            });
+        });
+    });
+
+    // Callback: Move Selection (piltangenter / j k), hoppar över rader dolda av snabbfiltret
+    let ui_mv = ui.as_weak();
+    ui.on_move_selection(move |delta| {
+        if let Some(ui) = ui_mv.upgrade() {
+            let jobs: Vec<JobEntry> = ui.get_jobs().iter().collect();
+            let visible: Vec<usize> = jobs.iter().enumerate().filter(|(_, j)| !j.filtered_out).map(|(i, _)| i).collect();
+            if visible.is_empty() { return; }
+            let current = ui.get_selected_index();
+            let pos = visible.iter().position(|&i| i as i32 == current);
+            let next = match pos {
+                Some(p) => visible[(p as i32 + delta).clamp(0, visible.len() as i32 - 1) as usize],
+                None => if delta < 0 { visible[visible.len() - 1] } else { visible[0] },
+            };
+            if next as i32 != current {
+                ui.set_selected_index(next as i32);
+                ui.invoke_job_selected(jobs[next].id.clone(), next as i32);
+            }
+        }
+    });
+
+    // Callback: Job Selected (Liknande jobb)
+    let (db_sel, ui_sel, rt_sel) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_job_selected(move |id, _idx| {
+        let (db, ui_weak, id_str) = (db_sel.clone(), ui_sel.clone(), id.to_string());
+        if let Some(ui) = ui_weak.upgrade() {
+            ui.set_similar_jobs(Rc::new(slint::VecModel::from(Vec::<JobEntry>::new())).into());
+            ui.set_description_runs(Rc::new(slint::VecModel::from(Vec::<DescRun>::new())).into());
+            ui.set_detail_logo(slint::Image::default());
+        }
+        rt_sel.spawn(async move {
+            if let Err(e) = db.record_view(&id_str).await {
+                tracing::warn!("Kunde inte spara visad annons: {}", e);
+            }
+            let mut ad = db.get_job_ad(&id_str).await.ok().flatten();
+            if let Some(cached) = ad.as_ref().filter(|a| a.description_looks_truncated()) {
+                let proxy = db.load_settings().await.ok().flatten().and_then(|s| s.http_proxy);
+                if let Ok(api) = JobSearchClient::with_proxy(proxy.as_deref()) {
+                    if let Some(full) = fetch_full_description(&api, &db, &cached.id).await {
+                        let re_html = Regex::new(r"<[^>]*>").expect("Invalid regex");
+                        let description = job_to_entry(full.clone(), &re_html).description;
+                        let (u, shown_id) = (ui_weak.clone(), id_str.clone());
+                        let _ = slint::invoke_from_event_loop(move || {
+                            if let Some(ui) = u.upgrade() {
+                                let mut vec: Vec<JobEntry> = ui.get_jobs().iter().collect();
+                                if let Some(pos) = vec.iter().position(|j| j.id == shown_id) {
+                                    vec[pos].description = description;
+                                    ui.set_jobs(Rc::new(slint::VecModel::from(vec)).into());
+                                }
+                            }
+                        });
+                        ad = Some(full);
+                    }
+                }
+            }
+            if let Some(ad) = ad {
+                let runs = description_runs(&ad, &Regex::new(r"<[^>]*>").expect("Invalid regex"));
+                let (u, shown_id) = (ui_weak.clone(), id_str.clone());
+                // The model is built on the UI thread; skip it if the user already moved on
+                let _ = slint::invoke_from_event_loop(move || {
+                    if let Some(ui) = u.upgrade() {
+                        let idx = ui.get_selected_index();
+                        if ui.get_jobs().row_data(idx.max(0) as usize).is_some_and(|j| j.id == shown_id) {
+                            ui.set_description_runs(Rc::new(slint::VecModel::from(runs)).into());
+                        }
+                    }
+                });
+                let employer = ad.employer.as_ref().and_then(|e| e.name.clone()).unwrap_or_default();
+                if let Some(url) = ad.logo_url.as_deref() {
+                    let settings = db.load_settings().await.unwrap_or_default().unwrap_or_default();
+                    let max_bytes = u64::from(settings.logo_cache_max_mb) * 1024 * 1024;
+                    if let Some(path) = logos::cached_logo(&employer, url, settings.http_proxy.as_deref(), max_bytes).await {
+                        let (u, shown_id) = (ui_weak.clone(), id_str.clone());
+                        // slint::Image isn't Send, so it is loaded on the UI thread. Skip it if the user already moved on.
+                        let _ = slint::invoke_from_event_loop(move || {
+                            if let Some(ui) = u.upgrade() {
+                                let idx = ui.get_selected_index();
+                                if ui.get_jobs().row_data(idx.max(0) as usize).is_some_and(|j| j.id == shown_id) {
+                                    if let Ok(img) = slint::Image::load_from_path(&path) {
+                                        ui.set_detail_logo(img);
+                                    }
+                                }
+                            }
+                        });
+                    }
+                }
+            }
+            if let Ok(similar) = db.find_similar(&id_str, 5).await {
+                let re_html = Regex::new(r"<[^>]*>").expect("Invalid regex");
+                let entries: Vec<JobEntry> = similar.into_iter().map(|ad| job_to_entry(ad, &re_html)).collect();
+                let _ = slint::invoke_from_event_loop(move || {
+                    if let Some(ui) = ui_weak.upgrade() {
+                        ui.set_similar_jobs(Rc::new(slint::VecModel::from(entries)).into());
+                    }
+                });
+            }
+        });
+    });
+
+    // Callback: Show Job (from the similar list, may not be in the current month view)
+    let (db_show, ui_show, rt_show) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_show_job(move |id| {
+        let (db, ui_weak, id_str) = (db_show.clone(), ui_show.clone(), id.to_string());
+        rt_show.spawn(async move {
+            if let Ok(Some(ad)) = db.get_job_ad(&id_str).await {
+                let entry = job_to_entry(ad, &Regex::new(r"<[^>]*>").expect("Invalid regex"));
+                let _ = slint::invoke_from_event_loop(move || {
+                    if let Some(ui) = ui_weak.upgrade() {
+                        let mut vec: Vec<JobEntry> = ui.get_jobs().iter().collect();
+                        let pos = match vec.iter().position(|j| j.id == id_str) { Some(p) => p, None => { vec.insert(0, entry); 0 } };
+                        ui.set_jobs(Rc::new(slint::VecModel::from(vec)).into());
+                        ui.set_selected_index(pos as i32);
+                        ui.invoke_job_selected(id_str.into(), pos as i32);
+                    }
+                });
+            }
+        });
+    });
+
+    // Callback: Quick Filter (filters the already loaded list, no DB or API query)
+    let ui_qf = ui.as_weak();
+    ui.on_quick_filter_changed(move |text| {
+        if let Some(ui) = ui_qf.upgrade() {
+            let mut vec: Vec<JobEntry> = ui.get_jobs().iter().collect();
+            apply_quick_filter(&mut vec, &text, &ui.get_active_facet(), &ui.get_settings());
+            ui.set_jobs(Rc::new(slint::VecModel::from(vec)).into());
+        }
+    });
+
+    // Callback: Occupation field facet (empty = all; filters the loaded list like the quick filter)
+    let ui_fc = ui.as_weak();
+    ui.on_facet_filter_changed(move |field| {
+        if let Some(ui) = ui_fc.upgrade() {
+            ui.set_active_facet(field.clone());
+            let mut vec: Vec<JobEntry> = ui.get_jobs().iter().collect();
+            apply_quick_filter(&mut vec, &ui.get_quick_filter(), &field, &ui.get_settings());
+            ui.set_jobs(Rc::new(slint::VecModel::from(vec)).into());
+        }
+    });
+
+    // Callback: Tag Filter (empty tag = show all)
+    let (db_tf, ui_tf, rt_tf) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_tag_filter_changed(move |tag| {
+        let (db, ui_weak, tag) = (db_tf.clone(), ui_tf.clone(), tag.to_string());
+        let month = if let Some(ui) = ui_weak.upgrade() { ui.set_active_tag(tag.clone().into()); ui.set_starred_only(false); ui.set_recent_only(false); Some(ui.get_active_month().to_string()) } else { None };
+        if let Some(month_str) = month {
+            let mut parts = month_str.split('-');
+            let year = parts.next().and_then(|s| s.parse::<i32>().ok()).unwrap_or(2026);
+            let month = parts.next().and_then(|s| s.parse::<u32>().ok()).unwrap_or(1);
+            let since = since_date(&ui_weak);
+            rt_tf.spawn(async move {
+                let settings = db.load_settings().await.unwrap_or_default().unwrap_or_default();
+                let employer_blacklist = settings.blacklisted_employers();
+                let tag_filter = (!tag.is_empty()).then_some(tag.as_str());
+                if let Ok(mut ads) = db.get_filtered_jobs(JobFilter { year: Some(year), month: Some(month), employer_blacklist: Some(employer_blacklist.as_slice()), tag: tag_filter, month_basis: settings.month_basis, published_after: since, ..Default::default() }).await {
+                    if let Ok(pinned) = db.get_pinned_jobs().await { merge_pinned(&mut ads, pinned); }
+                    filter_all_view(&mut ads, &settings, tag_filter);
+                    let re_html = Regex::new(r"<[^>]*>").expect("Invalid regex");
+                    let mut entries: Vec<JobEntry> = ads.into_iter().map(|ad| job_to_entry(ad, &re_html)).collect();
+                    if settings.sort_by_salary { entries.sort_by(salary_order); }
+                    let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_selected_index(-1); set_job_list(&ui, entries); } });
+                }
+            });
+        }
+    });
+
+    // Callback: Hide Applied in All (sparas direkt, listan laddas om)
+    let (db_ha, ui_ha, rt_ha) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_hide_applied_toggled(move |hide| {
+        let (db, ui_weak) = (db_ha.clone(), ui_ha.clone());
+        rt_ha.spawn(async move {
+            let mut settings = db.load_settings().await.unwrap_or_default().unwrap_or_default();
+            settings.hide_applied_in_all = hide;
+            if db.save_settings(&settings).await.is_ok() {
+                let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_settings(to_ui_settings(&settings)); ui.invoke_tag_filter_changed(ui.get_active_tag()); } });
+            }
+        });
+    });
+
+    // Callback: minimum rating stepper in the inbox toolbar (saved, like "Dölj sökta")
+    let (db_mr, ui_mr, rt_mr) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_min_rating_changed(move |min| {
+        let (db, ui_weak) = (db_mr.clone(), ui_mr.clone());
+        rt_mr.spawn(async move {
+            let mut settings = db.load_settings().await.unwrap_or_default().unwrap_or_default();
+            settings.min_rating_filter = min.clamp(0, settings.ai_scale_max as i32) as u8;
+            if db.save_settings(&settings).await.is_ok() {
+                let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_settings(to_ui_settings(&settings)); ui.invoke_tag_filter_changed(ui.get_active_tag()); } });
+            }
+        });
+    });
+
+    // Callback: "Publicerad från" set or cleared (transient, not saved in settings)
+    let ui_sd = ui.as_weak();
+    ui.on_since_date_changed(move |date| {
+        let Some(ui) = ui_sd.upgrade() else { return };
+        let date = date.trim();
+        if date.is_empty() {
+            ui.set_since_date("".into());
+        } else {
+            match chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+                Ok(d) => ui.set_since_date(d.format("%Y-%m-%d").to_string().into()),
+                Err(_) => { ui.set_status_msg("Ogiltigt datum, använd ÅÅÅÅ-MM-DD".into()); return; }
+            }
+        }
+        ui.invoke_tag_filter_changed(ui.get_active_tag());
+    });
+
+    // Callback: AI rating of the listed ads that have no rating yet. Each rating is saved as
+    // it arrives, so "Avbryt" keeps everything finished so far.
+    let rating_cancel: Arc<std::sync::Mutex<Option<tokio::sync::watch::Sender<bool>>>> = Arc::default();
+    let (db_rl, ui_rl, rt_rl, cancel_rl) = (db.clone(), ui.as_weak(), rt.clone(), rating_cancel.clone());
+    ui.on_rate_list(move || {
+        let Some(ui) = ui_rl.upgrade() else { return };
+        if ui.get_rating_batch() { return; }
+        let ids: Vec<String> = ui.get_jobs().iter().filter(|j| j.rating == 0).map(|j| j.id.to_string()).collect();
+        if ids.is_empty() { ui.set_status_msg("Alla annonser i listan har redan betyg".into()); return; }
+        let (tx, rx) = tokio::sync::watch::channel(false);
+        if let Ok(mut slot) = cancel_rl.lock() { *slot = Some(tx); }
+        ui.set_rating_batch(true);
+        let (db, ui_weak, cancel) = (db_rl.clone(), ui_rl.clone(), cancel_rl.clone());
+        rt_rl.spawn(async move {
+            let settings = db.load_settings().await.unwrap_or_default().unwrap_or_default();
+            let finish = move |ui_weak: slint::Weak<App>, msg: String| {
+                if let Ok(mut slot) = cancel.lock() { *slot = None; }
+                let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_rating_batch(false); ui.set_status_msg(msg.into()); } });
+            };
+            let ranker = match crate::ai::AiRanker::new(&settings.ollama_url, "ollama").and_then(|r| r.with_proxy(settings.http_proxy.as_deref())) {
+                Ok(r) => Arc::new(r.with_scoring(&settings.ai_system_prompt, settings.ai_scale_max)),
+                Err(e) => { report_error(&ui_weak, format!("AI-betyg kunde inte starta: {:#}", e)); finish(ui_weak, "AI-betyg avbrutet".to_string()); return; }
+            };
+            let mut ads = Vec::new();
+            for id in &ids { if let Ok(Some(ad)) = db.get_job_ad(id).await { ads.push(ad); } }
+            let total = ads.len();
+            let profile: Arc<str> = settings.my_profile.clone().into();
+            let rate = |ad: crate::models::JobAd| { let (ranker, profile) = (ranker.clone(), profile.clone()); async move { ranker.rate_job(&ad, &profile).await } };
+            let mut done = 0;
+            let tally = crate::ai::rate_batch(ads, settings.ai_concurrency as usize, rx, rate, |id, result| {
+                done += 1;
+                let progress = format!("AI-betyg: {} av {} klara", done, total);
+                let (db, ui_weak) = (db.clone(), ui_weak.clone());
+                async move {
+                    let rating = match result {
+                        Ok(r) => db.update_rating(&id, r).await.ok().map(|_| r as i32),
+                        Err(e) => { tracing::warn!("AI-betyg misslyckades för {}: {}", id, e); None }
+                    };
+                    let _ = slint::invoke_from_event_loop(move || {
+                        let Some(ui) = ui_weak.upgrade() else { return };
+                        let jobs = ui.get_jobs();
+                        if let (Some(rating), Some(pos)) = (rating, jobs.iter().position(|j| j.id == id.as_str())) {
+                            if let Some(mut entry) = jobs.row_data(pos) { entry.rating = rating; jobs.set_row_data(pos, entry); }
+                        }
+                        ui.set_status_msg(progress.into());
+                    });
+                }
+            }).await;
+            let bookmarked = if settings.auto_bookmark_rating > 0 {
+                match db.auto_bookmark_rated(&ids, settings.auto_bookmark_rating).await {
+                    Ok(ids) => ids,
+                    Err(e) => { report_error(&ui_weak, format!("Kunde inte spara högt betygsatta annonser: {}", e)); Vec::new() }
+                }
+            } else { Vec::new() };
+            if !bookmarked.is_empty() {
+                tracing::info!("{} annonser med AI-betyg minst {} sparades automatiskt", bookmarked.len(), settings.auto_bookmark_rating);
+                trigger_sync(&db).await;
+                let u = ui_weak.clone(); let marked = bookmarked.clone();
+                let _ = slint::invoke_from_event_loop(move || {
+                    let Some(ui) = u.upgrade() else { return };
+                    let jobs = ui.get_jobs();
+                    for pos in 0..jobs.row_count() {
+                        if let Some(mut entry) = jobs.row_data(pos).filter(|j| marked.iter().any(|id| j.id == id.as_str())) { entry.status = 2; jobs.set_row_data(pos, entry); }
+                    }
+                });
+            }
+            if tally.rated == 0 && tally.failed > 0 {
+                report_error(&ui_weak, "Inga annonser fick AI-betyg. Kontrollera AI-anslutningen under Inställningar.".to_string());
+            }
+            let failed = if tally.failed > 0 { format!(", {} misslyckades", tally.failed) } else { String::new() };
+            let saved = if bookmarked.is_empty() { String::new() } else { format!(", {} sparade automatiskt", bookmarked.len()) };
+            let msg = if tally.cancelled { format!("AI-betyg avbrutet: {} av {} betygsatta{}{}", tally.rated, total, failed, saved) } else { format!("AI-betyg klart: {} av {} betygsatta{}{}", tally.rated, total, failed, saved) };
+            finish(ui_weak, msg);
+        });
+    });
+    ui.on_cancel_rating(move || {
+        if let Ok(Some(tx)) = rating_cancel.lock().as_deref() { let _ = tx.send(true); }
+    });
+
+    // Callback: Theme toggled in the nav bar (saved at once, like hide-applied)
+    let (db_th, rt_th) = (db.clone(), rt.clone());
+    ui.on_theme_toggled(move |theme| {
+        let db = db_th.clone();
+        rt_th.spawn(async move {
+            let mut settings = db.load_settings().await.unwrap_or_default().unwrap_or_default();
+            settings.theme = match theme { 1 => ThemeMode::Light, 2 => ThemeMode::System, _ => ThemeMode::Dark };
+            let _ = db.save_settings(&settings).await;
+        });
+    });
+
+    // Callback: Show Starred (över alla statusar och månader)
+    let (db_st, ui_st, rt_st) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_show_starred(move || {
+        let (db, ui_weak) = (db_st.clone(), ui_st.clone());
+        if let Some(ui) = ui_weak.upgrade() { ui.set_starred_only(true); ui.set_recent_only(false); ui.set_active_tag("".into()); }
+        rt_st.spawn(async move {
+            if let Ok(mut ads) = db.get_starred_jobs().await {
+                if let Ok(pinned) = db.get_pinned_jobs().await { merge_pinned(&mut ads, pinned); }
+                let re_html = Regex::new(r"<[^>]*>").expect("Invalid regex");
+                let entries: Vec<JobEntry> = ads.into_iter().map(|ad| job_to_entry(ad, &re_html)).collect();
+                let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_selected_index(-1); set_job_list(&ui, entries); } });
+            }
+        });
+    });
+
+    // Callback: Show Recent (the last opened ads, newest first, each ad once)
+    let (db_rv, ui_rv, rt_rv) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_show_recent(move || {
+        let (db, ui_weak) = (db_rv.clone(), ui_rv.clone());
+        if let Some(ui) = ui_weak.upgrade() { ui.set_recent_only(true); ui.set_starred_only(false); ui.set_active_tag("".into()); }
+        rt_rv.spawn(async move {
+            match db.recent_views(RECENT_VIEWS_SHOWN).await {
+                Ok(views) => {
+                    let mut ads = Vec::new();
+                    // Ads removed since they were viewed (archived, cleared) are skipped
+                    for id in distinct_view_ids(&views) { if let Ok(Some(ad)) = db.get_job_ad(&id).await { ads.push(ad); } }
+                    let re_html = Regex::new(r"<[^>]*>").expect("Invalid regex");
+                    let entries: Vec<JobEntry> = ads.into_iter().map(|ad| job_to_entry(ad, &re_html)).collect();
+                    let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_selected_index(-1); set_job_list(&ui, entries); } });
+                }
+                Err(e) => report_error(&ui_weak, format!("Kunde inte läsa senast visade: {}", e)),
+            }
+        });
+    });
+
+    // Callback: Toggle Star
+    let (db_ts, ui_ts, rt_ts) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_toggle_star(move |id| {
+        let (db, ui_weak, id_str) = (db_ts.clone(), ui_ts.clone(), id.to_string());
+        let starred = match ui_weak.upgrade() { Some(ui) => !ui.get_jobs().iter().any(|j| j.id == id_str && j.starred), None => return };
+        rt_ts.spawn(async move {
+            if db.set_starred(&id_str, starred).await.is_ok() {
+                trigger_sync(&db).await;
+                let _ = slint::invoke_from_event_loop(move || {
+                    if let Some(ui) = ui_weak.upgrade() {
+                        let mut vec: Vec<JobEntry> = ui.get_jobs().iter().collect();
+                        if let Some(pos) = vec.iter().position(|j| j.id == id_str) {
+                            vec[pos].starred = starred;
+                            ui.set_jobs(Rc::new(slint::VecModel::from(vec)).into());
+                        }
+                    }
+                });
+            }
+        });
+    });
+
+    // Callback: Toggle Pin (the row moves to the top on the next list load)
+    let (db_tp, ui_tp, rt_tp) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_toggle_pin(move |id| {
+        let (db, ui_weak, id_str) = (db_tp.clone(), ui_tp.clone(), id.to_string());
+        let pinned = match ui_weak.upgrade() { Some(ui) => !ui.get_jobs().iter().any(|j| j.id == id_str && j.pinned), None => return };
+        rt_tp.spawn(async move {
+            match db.set_pinned(&id_str, pinned).await {
+                Ok(()) => {
+                    trigger_sync(&db).await;
+                    let _ = slint::invoke_from_event_loop(move || {
+                        if let Some(ui) = ui_weak.upgrade() {
+                            let mut vec: Vec<JobEntry> = ui.get_jobs().iter().collect();
+                            if let Some(pos) = vec.iter().position(|j| j.id == id_str) {
+                                vec[pos].pinned = pinned;
+                                ui.set_jobs(Rc::new(slint::VecModel::from(vec)).into());
+                            }
+                        }
+                    });
+                }
+                Err(e) => report_error(&ui_weak, format!("Kunde inte fästa annonsen: {}", e)),
+            }
+        });
+    });
+
+    // Callback: Set Tags (comma-separated input from the detail view)
+    let (db_tags, ui_tags, rt_tags) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_set_tags(move |id, text| {
+        let (db, ui_weak, id_str) = (db_tags.clone(), ui_tags.clone(), id.to_string());
+        let tags = crate::models::parse_tags(&text);
+        rt_tags.spawn(async move {
+            let joined = tags.join(", ");
+            if db.set_tags(&id_str, tags).await.is_ok() {
+                trigger_sync(&db).await;
+                let u = ui_weak.clone();
+                let _ = slint::invoke_from_event_loop(move || {
+                    if let Some(ui) = u.upgrade() {
+                        let mut vec: Vec<JobEntry> = ui.get_jobs().iter().collect();
+                        if let Some(pos) = vec.iter().position(|j| j.id == id_str) {
+                            vec[pos].tags = joined.into();
+                            ui.set_jobs(Rc::new(slint::VecModel::from(vec)).into());
+                        }
+                    }
+                });
+                refresh_tag_stats(&db, ui_weak).await;
             }
         });
     });
 
     ui.on_copy_text(|text| copy_to_clipboard(text.to_string()));
+    ui.on_copy_link(|url| copy_to_clipboard(reports::clean_url(&url)));
 
     // Callback: Save Settings
     let (db_set, ui_set, rt_set) = (db.clone(), ui.as_weak(), rt.clone());
     ui.on_save_settings(move |s| {
         let (db, ui_weak) = (db_set.clone(), ui_set.clone());
-        let settings = crate::models::AppSettings { 
-            keywords: s.keywords.to_string(), 
-            blacklist_keywords: s.blacklist_keywords.to_string(), 
-            locations_p1: s.locations_p1.to_string(), 
-            locations_p2: s.locations_p2.to_string(), 
-            locations_p3: s.locations_p3.to_string(), 
-            my_profile: s.my_profile.to_string(), 
-            ollama_url: s.ollama_url.to_string(), 
-            sync_path: s.sync_path.to_string(),
-            app_min_count: s.app_min_count, 
-            app_goal_count: s.app_goal_count, 
-            show_motivation: s.show_motivation 
-        };
+        let settings = from_ui_settings(&s);
         let s_ui = settings.clone();
         rt_set.spawn(async move {
+            let previous = db.load_settings().await.unwrap_or_default().unwrap_or_default();
+            if db.save_settings(&settings).await.is_ok() {
+                // Changed keywords/zones may match older ads, so the next search is a full one
+                if previous.search_scope_changed(&settings) { let _ = db.clear_search_watermarks().await; }
+                trigger_sync(&db).await;
+                let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_settings(to_ui_settings(&s_ui)); ui.set_status_msg("Inställningar sparade".into()); } });
+            }
+        });
+    });
+
+    // Callback: Apply Preset (overwrites keywords and P1–P3, then saves)
+    ui.set_occupation_field_names(Rc::new(slint::VecModel::from(std::iter::once(OCCUPATION_FIELD_ALL).chain(crate::api::OCCUPATION_FIELDS.iter().map(|(label, _)| *label)).map(slint::SharedString::from).collect::<Vec<_>>())).into());
+    ui.set_region_names(Rc::new(slint::VecModel::from(crate::api::REGIONS.iter().map(|(name, _)| slint::SharedString::from(*name)).collect::<Vec<_>>())).into());
+    ui.on_region_codes(|region| crate::api::region_municipality_codes(&region).join(", ").into());
+    ui.set_preset_names(Rc::new(slint::VecModel::from(presets::PRESETS.iter().map(|p| slint::SharedString::from(p.name)).collect::<Vec<_>>())).into());
+    let (db_pre, ui_pre, rt_pre) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_apply_preset(move |name| {
+        let (db, ui_weak) = (db_pre.clone(), ui_pre.clone());
+        let Some(preset) = presets::find(&name) else { return };
+        rt_pre.spawn(async move {
+            let mut settings = db.load_settings().await.unwrap_or_default().unwrap_or_default();
+            let previous = settings.clone();
+            preset.apply_to(&mut settings);
             if db.save_settings(&settings).await.is_ok() {
+                if previous.search_scope_changed(&settings) { let _ = db.clear_search_watermarks().await; }
                 trigger_sync(&db).await;
-                let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_settings(AppSettings { keywords: s_ui.keywords.into(), blacklist_keywords: s_ui.blacklist_keywords.into(), locations_p1: normalize_locations(&s_ui.locations_p1).into(), locations_p2: normalize_locations(&s_ui.locations_p2).into(), locations_p3: normalize_locations(&s_ui.locations_p3).into(), my_profile: s_ui.my_profile.into(), ollama_url: s_ui.ollama_url.into(), sync_path: s_ui.sync_path.into(), app_min_count: s_ui.app_min_count, app_goal_count: s_ui.app_goal_count, show_motivation: s_ui.show_motivation }); ui.set_status_msg("Inställningar sparade".into()); } });
+                let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_settings(to_ui_settings(&settings)); ui.set_status_msg(format!("Förval \"{}\" tillämpat", preset.name).into()); } });
+            }
+        });
+    });
+
+    // Callback: Connection Self-Test
+    let (db_diag, ui_diag, rt_diag) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_test_connection(move || {
+        let (db, ui_weak) = (db_diag.clone(), ui_diag.clone());
+        if let Some(ui) = ui_weak.upgrade() { ui.set_connection_status("Testar anslutning...".into()); }
+        rt_diag.spawn(async move {
+            let settings = db.load_settings().await.unwrap_or_default().unwrap_or_default();
+            let report = diagnostics::test_connections(&settings.ollama_url, settings.http_proxy.as_deref()).await;
+            tracing::info!("Anslutningstest:\n{}", report.to_text());
+            let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_connection_status(report.to_text().into()); } });
+        });
+    });
+
+    // Callback: Test Keywords (hit counts per keyword for a zone, nothing is saved)
+    let (ui_tk, rt_tk) = (ui.as_weak(), rt.clone());
+    ui.on_test_keywords(move |keywords, locations| {
+        let ui_weak = ui_tk.clone();
+        let Some(ui) = ui_weak.upgrade() else { return };
+        let settings = from_ui_settings(&ui.get_settings());
+        let keywords: Vec<String> = keywords.split(',').map(|s| s.trim().replace('"', "")).filter(|s| !s.is_empty()).collect();
+        if keywords.is_empty() { ui.set_keyword_test_result("Inga sökord att testa".into()); return; }
+        let municipalities = JobSearchClient::parse_locations(&locations);
+        ui.set_keyword_test_result("Räknar träffar...".into());
+        rt_tk.spawn(async move {
+            let msg = match JobSearchClient::with_proxy(settings.http_proxy.as_deref()) {
+                Ok(client) => {
+                    let client = client.with_occupation_field(settings.occupation_field.as_deref()).with_request_delay(std::time::Duration::from_millis(settings.request_delay_ms as u64));
+                    let mut counts = Vec::new();
+                    for kw in keywords {
+                        let count = client.count_hits(&kw, &municipalities).await;
+                        if let Err(e) = &count { tracing::warn!("Träfftest för '{}' misslyckades: {:#}", kw, e); }
+                        counts.push((kw, count.ok()));
+                    }
+                    format_hit_counts(&counts)
+                }
+                Err(e) => format!("{:#}", e),
+            };
+            let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_keyword_test_result(msg.into()); } });
+        });
+    });
+
+    // Callback: Purge Blacklisted (tillämpar svartlistan på redan sparade annonser)
+    let (db_pb, ui_pb, rt_pb) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_purge_blacklisted(move || {
+        let (db, ui_weak) = (db_pb.clone(), ui_pb.clone());
+        rt_pb.spawn(async move {
+            let settings = db.load_settings().await.unwrap_or_default().unwrap_or_default();
+            let msg = match db.purge_blacklisted(&settings.blacklisted_keywords(), &settings.blacklisted_employers()).await {
+                Ok(n) => { tracing::info!("Svartlista: tog bort {} sparade annonser", n); trigger_sync(&db).await; format!("Tog bort {} annonser enligt svartlistan", n) }
+                Err(e) => { report_error(&ui_weak, format!("Rensning enligt svartlista misslyckades: {}", e)); "Rensningen misslyckades".to_string() }
+            };
+            let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_status_msg(msg.into()); } });
+        });
+    });
+
+    // Callbacks: Open Log / Open Data Folder (for bug reports)
+    let (ui_ol, opener_ol) = (ui.as_weak(), opener.clone());
+    ui.on_open_log(move || {
+        let log = current_log_file();
+        // No file yet today (or file logging off): show the folder with the earlier days
+        let target = if log.exists() { log } else { paths::log_dir() };
+        let msg = if open_path(opener_ol.as_ref(), &target) { format!("Öppnade {}", target.display()) } else { format!("Kunde inte öppna {}", target.display()) };
+        if let Some(ui) = ui_ol.upgrade() { ui.set_status_msg(msg.into()); }
+    });
+    let (ui_od, opener_od) = (ui.as_weak(), opener.clone());
+    ui.on_open_data_dir(move || {
+        let dir = get_db_path().parent().map(std::path::Path::to_path_buf).unwrap_or_default();
+        let msg = if open_path(opener_od.as_ref(), &dir) { format!("Öppnade {}", dir.display()) } else { format!("Kunde inte öppna {}", dir.display()) };
+        if let Some(ui) = ui_od.upgrade() { ui.set_status_msg(msg.into()); }
+    });
+
+    // Callback: Clear Image Cache
+    let ui_ic = ui.as_weak();
+    ui.on_clear_image_cache(move || {
+        let msg = match logos::clear_cache() {
+            Ok(n) => format!("Bildcachen rensad ({} logotyper)", n),
+            Err(e) => { report_error(&ui_ic, format!("Kunde inte rensa bildcachen: {}", e)); "Kunde inte rensa bildcachen".to_string() }
+        };
+        if let Some(ui) = ui_ic.upgrade() { ui.set_status_msg(msg.into()); }
+    });
+
+    // Callbacks: Textbitar (snippets) in settings; the profile is offered as the first one
+    let (db_sn, ui_sn) = (db.clone(), ui.as_weak());
+    rt.spawn(async move {
+        let profile = db_sn.load_settings().await.unwrap_or_default().unwrap_or_default().my_profile;
+        if let Err(e) = db_sn.seed_profile_snippet(&profile).await { tracing::warn!("Kunde inte lägga till profilen som textbit: {}", e); }
+        refresh_snippets(&db_sn, ui_sn).await;
+    });
+    let (db_ss, ui_ss, rt_ss) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_save_snippet(move |name, body| {
+        let (db, ui_weak, name, body) = (db_ss.clone(), ui_ss.clone(), name.to_string(), body.to_string());
+        rt_ss.spawn(async move {
+            match db.save_snippet(&name, &body).await {
+                Ok(()) => { trigger_sync(&db).await; refresh_snippets(&db, ui_weak.clone()).await; let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_status_msg(format!("Textbiten \"{}\" sparad", name.trim()).into()); } }); }
+                Err(e) => report_error(&ui_weak, format!("Kunde inte spara textbiten: {}", e)),
+            }
+        });
+    });
+    let (db_sd, ui_sd, rt_sd) = (db.clone(), ui.as_weak(), rt.clone());
+    ui.on_delete_snippet(move |name| {
+        let (db, ui_weak, name) = (db_sd.clone(), ui_sd.clone(), name.to_string());
+        rt_sd.spawn(async move {
+            match db.delete_snippet(&name).await {
+                Ok(_) => { trigger_sync(&db).await; refresh_snippets(&db, ui_weak).await; }
+                Err(e) => report_error(&ui_weak, format!("Kunde inte ta bort textbiten: {}", e)),
             }
         });
     });
@@ -375,19 +1905,17 @@ fn setup_ui(ui: &App, rt: Arc<Runtime>, db: Arc<Db>, log_rx: mpsc::Receiver<Stri
         if act == "backup" {
             let db_path = get_db_path();
             let backup_name = format!("jobseeker_backup_{}.redb", chrono::Local::now().format("%Y%m%d_%H%M"));
-            let backup_path = directories::UserDirs::new()
-                .and_then(|u| u.download_dir().map(|d| d.join(&backup_name)))
-                .unwrap_or_else(|| std::path::PathBuf::from(&backup_name));
+            let backup_path = download_path(&backup_name);
             
-            if std::fs::copy(&db_path, &backup_path).is_ok() {
+            if let Err(e) = std::fs::copy(&db_path, &backup_path) {
+                report_error(&ui_weak, format!("Misslyckades att skapa backup: {}", e));
+            } else {
                 tracing::info!("Backup skapad: {:?}", backup_path);
                 let _ = slint::invoke_from_event_loop(move || {
                     if let Some(ui) = ui_weak.upgrade() {
                         ui.set_status_msg(format!("Backup sparad: {}", backup_name).into());
                     }
                 });
-            } else {
-                tracing::error!("Misslyckades att skapa backup!");
             }
         }
     });
@@ -397,33 +1925,62 @@ fn setup_ui(ui: &App, rt: Arc<Runtime>, db: Arc<Db>, log_rx: mpsc::Receiver<Stri
     let db_path_str = get_db_path().to_string_lossy().to_string();
     rt_i.spawn(async move {
         let settings = db_i.load_settings().await.unwrap_or_default().unwrap_or_default();
+        if settings.auto_archive_days > 0 {
+            match db_i.archive_old(settings.auto_archive_days).await {
+                Ok(n) => tracing::info!("Arkivering: tog bort {} annonser äldre än {} dagar", n, settings.auto_archive_days),
+                Err(e) => report_error(&ui_i, format!("Arkivering misslyckades: {}", e)),
+            }
+        }
         let (s, u_s) = (settings.clone(), ui_i.clone());
         let d_path = db_path_str.clone();
         let _ = slint::invoke_from_event_loop(move || { 
             if let Some(ui) = u_s.upgrade() { 
                 ui.set_database_path(d_path.into());
-                ui.set_settings(AppSettings { 
-                    keywords: s.keywords.into(), 
-                    blacklist_keywords: s.blacklist_keywords.into(), 
-                    locations_p1: normalize_locations(&s.locations_p1).into(), 
-                    locations_p2: normalize_locations(&s.locations_p2).into(), 
-                    locations_p3: normalize_locations(&s.locations_p3).into(), 
-                    my_profile: s.my_profile.into(), 
-                    ollama_url: s.ollama_url.into(), 
-                    sync_path: s.sync_path.into(),
-                    app_min_count: s.app_min_count, 
-                    app_goal_count: s.app_goal_count, 
-                    show_motivation: s.show_motivation 
-                }); 
+                ui.set_log_file_path(if s.disable_file_log { "Loggfil skrivs inte".into() } else { current_log_file().to_string_lossy().to_string().into() });
+                ui.set_settings(to_ui_settings(&s));
+                ui.set_empty_hint(empty_inbox_hint(&EmptyInbox::NotSearched, &s).into());
             } 
         });
         let now = chrono::Utc::now();
         let (ms, md, u_m) = (format!("{:04}-{:02}", now.year(), now.month()), format!("{} {}", swedish_month_name(now.month()), now.year()), ui_i.clone());
         let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = u_m.upgrade() { ui.set_active_month(ms.into()); ui.set_active_month_display(md.into()); } });
-        perform_search(Arc::new(JobSearchClient::new()), db_i, ui_i, Some(1), None, settings).await;
+        refresh_tag_stats(&db_i, ui_i.clone()).await;
+        perform_search(db_i, ui_i, Some(1), None, false, None, settings).await;
     });
 }
 
+/// How the ads break down by occupation field, largest first, for the row under the search
+/// summary. Ads without a field are left out.
+fn occupation_facets(ads: &[crate::models::JobAd]) -> Vec<KeywordStat> {
+    let mut counts: std::collections::HashMap<&str, i32> = std::collections::HashMap::new();
+    for label in ads.iter().filter_map(|ad| ad.occupation_field.as_ref().and_then(|o| o.label.as_deref())) { *counts.entry(label).or_insert(0) += 1; }
+    let mut facets: Vec<KeywordStat> = counts.into_iter().map(|(name, count)| KeywordStat { name: name.into(), count }).collect();
+    facets.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    facets.truncate(8);
+    facets
+}
+
+const WEEKDAY_SHORT: [&str; 7] = ["Mån", "Tis", "Ons", "Tor", "Fre", "Lör", "Sön"];
+
+/// Fills the weekday × hour table on the statistics page from `Db::applied_by_weekday_hour`.
+fn set_apply_heat(ui: &App, heat: &[[u32; 24]; 7]) {
+    let rows: Vec<HeatRow> = WEEKDAY_SHORT.iter().zip(heat).map(|(day, hours)| HeatRow {
+        day: (*day).into(),
+        counts: Rc::new(slint::VecModel::from(hours.iter().map(|&n| n as i32).collect::<Vec<_>>())).into(),
+    }).collect();
+    ui.set_apply_heat_max(heat.iter().flatten().copied().max().unwrap_or(0) as i32);
+    ui.set_apply_heat(Rc::new(slint::VecModel::from(rows)).into());
+}
+
+/// Updates the tag filter row in the inbox with the most used tags.
+async fn refresh_tag_stats(db: &Db, ui_weak: slint::Weak<App>) {
+    if let Ok(counts) = db.tag_counts().await {
+        let mut stats: Vec<KeywordStat> = counts.into_iter().map(|(name, count)| KeywordStat { name: name.into(), count: count as i32 }).collect();
+        stats.truncate(8);
+        let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_tag_stats(Rc::new(slint::VecModel::from(stats)).into()); } });
+    }
+}
+
 fn spawn_log_task(ui_weak: slint::Weak<App>, log_rx: mpsc::Receiver<String>) {
     std::thread::spawn(move || {
         let mut log_lines: Vec<String> = Vec::new();
@@ -435,75 +1992,458 @@ fn spawn_log_task(ui_weak: slint::Weak<App>, log_rx: mpsc::Receiver<String>) {
     });
 }
 
-async fn perform_search(api_client: Arc<JobSearchClient>, db: Arc<Db>, ui_weak: slint::Weak<App>, prio: Option<i32>, free_query: Option<String>, settings: crate::models::AppSettings) {
+/// Status line while a search runs. A free-text search has no zone and no municipalities,
+/// so it is reported as nationwide instead of naming an empty location.
+fn search_scope_message(prio: Option<i32>, locations: &str) -> String {
+    match prio {
+        None => "Fritextsökning i hela landet. Söker efter nytt...".to_string(),
+        Some(0) => format!("Visar sparade jobb för alla zoner ({}). Söker efter nytt...", locations),
+        Some(_) if locations.trim().is_empty() => "Inga orter angivna för zonen. Söker efter nytt...".to_string(),
+        Some(_) => format!("Visar sparade jobb för {}. Söker efter nytt...", locations),
+    }
+}
+
+/// Why the inbox is empty, for the hint shown in its place.
+#[derive(Debug, Clone, PartialEq)]
+enum EmptyInbox {
+    /// No search has run for the month shown.
+    NotSearched,
+    /// The last search finished without anything to list.
+    NothingFound(crate::search::SearchProfile),
+    /// Every keyword of the last search failed, so the API was out of reach.
+    Unreachable,
+}
+
+/// The empty inbox's suggestion of what to do next: search a zone, widen the zone's keywords
+/// or locations (which are spelled out), or check the connection.
+fn empty_inbox_hint(case: &EmptyInbox, settings: &crate::models::AppSettings) -> String {
+    let no_keywords = (1..=3).all(|p| settings.zone_keywords(Some(p)).trim().is_empty());
+    if no_keywords && !matches!(case, EmptyInbox::NothingFound(crate::search::SearchProfile::FreeText(_))) {
+        return "Inga sökord angivna. Lägg till sökord under Inställningar och tryck sedan på en zon.".to_string();
+    }
+    match case {
+        EmptyInbox::NotSearched => "Inga annonser här ännu. Tryck på P1, P2 eller P3 för att söka i en zon.".to_string(),
+        EmptyInbox::Unreachable => format!("Kunde inte nå JobTech API{}. Kontrollera internetanslutningen och försök igen.", if settings.http_proxy.is_some() { " via proxyn" } else { "" }),
+        EmptyInbox::NothingFound(crate::search::SearchProfile::FreeText(q)) => format!("Fritextsökningen på \"{}\" gav inga annonser. Prova färre eller bredare ord.", q.trim()),
+        EmptyInbox::NothingFound(profile) => {
+            let (keywords, locations) = profile.query_and_locations(settings);
+            let zone = match profile.prio() { Some(0) | None => "alla zoner".to_string(), Some(p) => format!("P{}", p) };
+            let locations = if locations.trim().is_empty() { "hela landet".to_string() } else { locations.trim().to_string() };
+            format!("Sökningen i {} gav inga annonser.\nSökord: {}\nOrter: {}\nBredda sökorden eller orterna för att hitta fler.", zone, keywords.trim(), locations)
+        }
+    }
+}
+
+/// How long a search may keep the UI in its searching state before the watchdog frees it.
+const SEARCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(180);
+/// Generation of the latest search started, and the latest one that got to clear the searching
+/// state. A search only updates the UI while it is the latest, so one that finishes after the
+/// watchdog gave up on it can't overwrite the results of a newer search.
+static SEARCH_STARTED: AtomicU64 = AtomicU64::new(0);
+static SEARCH_FINISHED: AtomicU64 = AtomicU64::new(0);
+
+fn is_current_search(generation: u64) -> bool {
+    SEARCH_STARTED.load(Ordering::SeqCst) == generation
+}
+
+/// Ends search `generation` in the UI. Call it on the UI thread, after checking `is_current_search`.
+fn finish_search(ui: &App, generation: u64) {
+    SEARCH_FINISHED.fetch_max(generation, Ordering::SeqCst);
+    ui.set_searching(false);
+}
+
+/// Frees the UI when search `generation` is still the latest and hasn't finished after
+/// `SEARCH_TIMEOUT`, i.e. it panicked, hangs or its task was dropped.
+async fn search_watchdog(ui_weak: slint::Weak<App>, generation: u64) {
+    tokio::time::sleep(SEARCH_TIMEOUT).await;
+    // Checked on the UI thread, where the search itself finishes, so the two can't interleave
+    let _ = slint::invoke_from_event_loop(move || {
+        if let Some(ui) = ui_weak.upgrade().filter(|_| is_current_search(generation) && SEARCH_FINISHED.load(Ordering::SeqCst) < generation) {
+            tracing::warn!("Sökningen blev inte klar på {} s, släpper sökläget", SEARCH_TIMEOUT.as_secs());
+            finish_search(&ui, generation);
+            ui.set_status_msg("Sökningen tog för lång tid".into());
+        }
+    });
+}
+
+/// Runs a search through `search::run_search` and shows it: the month's saved ads at once,
+/// then the list, counts and keyword breakdown once every keyword is done. Priority searches
+/// are incremental unless `full_refresh` is set; `prio` 0 searches all three zones at once.
+/// The client is built per search so a changed proxy setting applies right away.
+async fn perform_search(db: Arc<Db>, ui_weak: slint::Weak<App>, prio: Option<i32>, free_query: Option<String>, full_refresh: bool, since: Option<chrono::NaiveDate>, settings: crate::models::AppSettings) {
+    let generation = SEARCH_STARTED.fetch_add(1, Ordering::SeqCst) + 1;
+    tokio::spawn(search_watchdog(ui_weak.clone(), generation));
+    let api_client = match JobSearchClient::with_proxy(settings.http_proxy.as_deref()) {
+        Ok(c) => c.with_occupation_field(settings.occupation_field.as_deref()).with_request_delay(std::time::Duration::from_millis(settings.request_delay_ms as u64)),
+        Err(e) => {
+            report_error(&ui_weak, format!("{:#}", e));
+            let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade().filter(|_| is_current_search(generation)) { ui.set_status_msg("Sökningen kunde inte starta".into()); finish_search(&ui, generation); } });
+            return;
+        }
+    };
     let now = chrono::Utc::now();
+    let profile = match free_query { Some(q) => crate::search::SearchProfile::FreeText(q), None => crate::search::SearchProfile::Zone(prio.unwrap_or(1)) };
     let (y, m) = if let Some(ui) = ui_weak.upgrade() { let month_str = ui.get_active_month().to_string(); let parts: Vec<&str> = month_str.split('-').collect(); if parts.len() == 2 { (parts[0].parse().unwrap_or(now.year()), parts[1].parse().unwrap_or(now.month())) } else { (now.year(), now.month()) } } else { (now.year(), now.month()) };
-    let (raw_query, locations_str) = match (free_query.clone(), prio) { (Some(q), _) => (q, String::new()), (None, Some(p)) => { let locs = match p { 1 => &settings.locations_p1, 2 => &settings.locations_p2, 3 => &settings.locations_p3, _ => &settings.locations_p1 }; (settings.keywords.clone(), locs.clone()) }, _ => (String::new(), String::new()) };
+    let (_, locations_str) = profile.query_and_locations(&settings);
     let municipalities = JobSearchClient::parse_locations(&locations_str);
-    let query_parts: Vec<_> = raw_query.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()).map(|s| s.replace("\"", "")).collect();
-    let ui_early = ui_weak.clone(); let p_early = prio;
-    let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_early.upgrade() { ui.set_searching(true); ui.set_status_msg(format!("Söker efter nytt... (Visar sparade jobb för P{})", p_early.unwrap_or(0)).into()); } });
+    let ui_early = ui_weak.clone(); let early_msg = search_scope_message(prio, &locations_str);
+    let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_early.upgrade() { ui.set_searching(true); ui.set_keyword_breakdown("".into()); ui.set_occupation_facets(Rc::new(slint::VecModel::from(Vec::<KeywordStat>::new())).into()); ui.set_active_facet("".into()); ui.set_filter_explanation("".into()); ui.set_status_msg(early_msg.into()); } });
 
-    let refresh_ui_from_db = |ui: &App, ads: Vec<crate::models::JobAd>, p: Option<i32>, muns: Vec<String>, msg: String| {
+    let max_rows = settings.max_inbox_rows;
+    let list_settings = settings.clone();
+    // Returns (rows shown, rows hidden by the municipality post-filter)
+    let refresh_ui_from_db = move |ui: &App, mut ads: Vec<crate::models::JobAd>, p: Option<i32>, muns: Vec<String>, msg: String| -> (usize, usize) {
         let re_html = Regex::new(r"<[^>]*>").expect("Invalid regex");
-        let pmn: Vec<String> = if p.is_some() { muns.iter().filter_map(|code| JobSearchClient::get_municipality_name(code)).map(|s| s.to_lowercase()).collect() } else { Vec::new() };
+        // All zones (prio 0) and free search (no prio) skip the per-zone post-filter, so nothing
+        // from another zone is hidden
+        let pmn: Vec<String> = if p.is_some_and(|p| p != 0) { muns.iter().filter_map(|m| JobSearchClient::municipality_code(m)).collect() } else { Vec::new() };
         
-        let applied_count = ads.iter().filter(|ad| ad.status == Some(AdStatus::Applied)).count() as i32;
-
-        let mut entries: Vec<JobEntry> = ads.into_iter().filter(|ad| { 
-            if !pmn.is_empty() { 
-                if let Some(ref addr) = ad.workplace_address { 
-                    if let Some(ref mun) = addr.municipality { return pmn.contains(&mun.to_lowercase()); } 
-                } 
-                return false; 
-            } 
-            true 
-        }).map(|ad| {
-            let raw_desc = ad.description.as_ref().and_then(|d| d.text.as_ref()).map(|s| s.as_str()).unwrap_or("");
-            let formatted_desc = raw_desc.replace("<li>", "\n • ").replace("</li>", "").replace("<ul>", "\n").replace("</ul>", "\n").replace("<br>", "\n").replace("<br/>", "\n").replace("<br />", "\n").replace("<p>", "\n\n").replace("</p>", "").replace("<strong>", "").replace("</strong>", "").replace("<b>", "").replace("</b>", "");
-            let mut clean_desc = re_html.replace_all(&formatted_desc, "").to_string();
-            if ad.driving_license_required { clean_desc.push_str("\n\nKÖRKORT:\n • Krav på körkort\n"); }
-            JobEntry { id: ad.id.into(), title: ad.headline.into(), employer: ad.employer.and_then(|e| e.name).unwrap_or_default().into(), location: ad.workplace_address.and_then(|a| a.city).unwrap_or_default().into(), description: clean_desc.into(), date: ad.publication_date.split('T').next().unwrap_or("").into(), apply_url: ad.application_details.and_then(|d| d.url).unwrap_or_default().into(), rating: ad.rating.unwrap_or(0) as i32, status: match ad.status { Some(AdStatus::Rejected) => 1, Some(AdStatus::Bookmarked) => 2, Some(AdStatus::ThumbsUp) => 3, Some(AdStatus::Applied) => 4, _ => 0 }, status_text: "".into() }
-        }).collect();
+        let applied_count = ads.iter().filter(|ad| ad.is_applied()).count() as i32;
+        filter_all_view(&mut ads, &list_settings, None);
+        let loaded = ads.len();
+
+        let mut entries: Vec<JobEntry> = ads.into_iter().filter(|ad| ad.pinned || pmn.is_empty() || in_zone(ad, &pmn)).map(|ad| job_to_entry(ad, &re_html)).collect();
         
-        entries.sort_by(|a, b| b.date.cmp(&a.date));
+        entries.sort_by(list_order(&list_settings));
+        let counts = (entries.len(), loaded - entries.len());
+        let msg = match cap_inbox_rows(&mut entries, max_rows) { Some(notice) => format!("{} ({})", msg, notice), None => msg };
         
-        ui.set_jobs(std::rc::Rc::new(slint::VecModel::from(entries)).into()); 
+        // A search lists everything for the month, so any tag filter no longer applies
+        ui.set_active_tag("".into());
+        ui.set_starred_only(false); ui.set_recent_only(false);
+        set_job_list(ui, entries); 
         ui.set_applied_count(applied_count);
         ui.set_status_msg(msg.into());
+        counts
     };
 
-    if let Ok(existing_ads) = db.get_filtered_jobs(&[], Some(y), Some(m)).await {
-        let ui_e2 = ui_weak.clone(); let muns_e2 = municipalities.clone(); let loc_d = locations_str.clone();
-        let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_e2.upgrade() { let msg = format!("Visar sparade jobb för {}. Söker efter nytt...", loc_d); refresh_ui_from_db(&ui, existing_ads, prio, muns_e2, msg); } });
+    let employer_blacklist = settings.blacklisted_employers();
+    let pinned = db.get_pinned_jobs().await.unwrap_or_default();
+    if let Ok(mut existing_ads) = db.get_filtered_jobs(JobFilter { year: Some(y), month: Some(m), employer_blacklist: Some(employer_blacklist.as_slice()), month_basis: settings.month_basis, published_after: since, ..Default::default() }).await {
+        merge_pinned(&mut existing_ads, pinned.clone());
+        let ui_e2 = ui_weak.clone(); let muns_e2 = municipalities.clone(); let msg = search_scope_message(prio, &locations_str);
+        let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_e2.upgrade().filter(|_| is_current_search(generation)) { let _ = refresh_ui_from_db(&ui, existing_ads, prio, muns_e2, msg); } });
     }
 
-    let mut new_count = 0; let blacklist: Vec<String> = settings.blacklist_keywords.split(',').map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()).collect();
-    for keyword in &query_parts {
-        match api_client.search(keyword, &municipalities, 100).await {
-            Ok(ads) => { for mut ad in ads { ad.search_keyword = Some(keyword.clone()); let is_blacklisted = blacklist.iter().any(|word| ad.headline.to_lowercase().contains(word) || ad.description.as_ref().and_then(|d| d.text.as_deref()).map(|t| t.to_lowercase().contains(word)).unwrap_or(false)); if !is_blacklisted { if let Ok(None) = db.get_job_ad(&ad.id).await { if db.save_job_ad(&ad).await.is_ok() { new_count += 1; } } } } },
-            Err(e) => { tracing::error!("Sökning på '{}' misslyckades: {:?}", keyword, e); }
-        }
-    }
+    let options = crate::search::SearchOptions { full_refresh, since };
+    let crate::search::SearchOutcome { new_count, auto_rejected, failed_keywords, per_keyword, rate_limited, mut filter_stats } = crate::search::run_search(&api_client, &db, &profile, &settings, &options).await;
+    for (keyword, e) in &failed_keywords { report_error(&ui_weak, format!("Sökning på '{}' misslyckades: {}", keyword, e)); }
+    let any_failed = !failed_keywords.is_empty();
+    let unreachable = any_failed && per_keyword.iter().all(|(_, n)| n.is_none());
+    let breakdown = format_keyword_breakdown(&per_keyword);
 
-    if let Ok(final_ads) = db.get_filtered_jobs(&[], Some(y), Some(m)).await {
+    if let Ok(mut final_ads) = db.get_filtered_jobs(JobFilter { year: Some(y), month: Some(m), employer_blacklist: Some(employer_blacklist.as_slice()), month_basis: settings.month_basis, published_after: since, ..Default::default() }).await {
+        merge_pinned(&mut final_ads, pinned);
         trigger_sync(&db).await;
+        let truncated: Vec<String> = final_ads.iter().filter(|ad| ad.description_looks_truncated()).map(|ad| ad.id.clone()).collect();
+        if !truncated.is_empty() { tokio::spawn(prefetch_descriptions(api_client, db.clone(), truncated)); }
         let ui_f = ui_weak.clone(); let muns_f = municipalities.clone();
-        let msg = if new_count > 0 { format!("Klar! Hittade {} nya annonser.", new_count) } else { "Inga nya annonser hittades just nu.".to_string() };
-        let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_f.upgrade() { refresh_ui_from_db(&ui, final_ads, prio, muns_f, msg); ui.set_searching(false); } });
+        let msg = if let Some(limit) = rate_limited { if new_count > 0 { format!("Hittade {} nya annonser. {}", new_count, limit) } else { limit } }
+            else if new_count > 0 && auto_rejected > 0 { format!("Klar! Hittade {} nya annonser, {} avvisades automatiskt.", new_count, auto_rejected) }
+            else if new_count > 0 { format!("Klar! Hittade {} nya annonser.", new_count) }
+            else if unreachable { format!("Kunde inte nå JobTech API{} – se loggen.", if settings.http_proxy.is_some() { " via proxyn" } else { "" }) }
+            else { "Inga nya annonser hittades just nu.".to_string() };
+        let goal_count = goal_applied_count(&db, settings.goal_period, y, m).await;
+        let new_since = new_since_last_view(&db, y, m, &final_ads).await;
+        let facets = occupation_facets(&final_ads);
+        let empty_case = if unreachable { EmptyInbox::Unreachable } else { EmptyInbox::NothingFound(profile) };
+        let empty_hint = empty_inbox_hint(&empty_case, &settings);
+        let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_f.upgrade().filter(|_| is_current_search(generation)) { ui.set_empty_hint(empty_hint.into()); ui.set_occupation_facets(Rc::new(slint::VecModel::from(facets)).into()); let (shown, post_filtered) = refresh_ui_from_db(&ui, final_ads, prio, muns_f, msg); if prio.is_some() && new_count == 0 && shown == 0 { filter_stats.post_filtered = post_filtered; ui.set_filter_explanation(filter_stats.explain().into()); } ui.set_goal_applied_count(goal_count); ui.set_new_since_visit(new_since); ui.set_keyword_breakdown(breakdown.into()); finish_search(&ui, generation); } });
     } else {
-        let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_searching(false); } });
+        let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade().filter(|_| is_current_search(generation)) { ui.set_keyword_breakdown(breakdown.into()); finish_search(&ui, generation); } });
+    }
+}
+
+/// Starts `local_api` when a port is set. It runs on the app's runtime and ends with it.
+async fn start_local_api(db: Arc<Db>) {
+    let port = db.load_settings().await.unwrap_or_default().unwrap_or_default().local_api_port;
+    if port == 0 { return; }
+    match local_api::bind(port).await {
+        Ok(listener) => { tracing::info!("Lokalt API på http://127.0.0.1:{}", port); local_api::serve(listener, db).await; }
+        Err(e) => tracing::warn!("Kunde inte starta lokalt API på port {}: {}", port, e),
+    }
+}
+
+/// Dates `[from, to)` the goal counts: the ISO week `today` is in, or the month `y`/`m`.
+fn goal_period_range(period: GoalPeriod, today: chrono::NaiveDate, y: i32, m: u32) -> (chrono::NaiveDate, chrono::NaiveDate) {
+    use chrono::{Duration, NaiveDate};
+    match period {
+        GoalPeriod::Week => {
+            let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+            (monday, monday + Duration::days(7))
+        }
+        GoalPeriod::Month => {
+            let first = NaiveDate::from_ymd_opt(y, m, 1).unwrap_or(today);
+            let next = if m == 12 { NaiveDate::from_ymd_opt(y + 1, 1, 1) } else { NaiveDate::from_ymd_opt(y, m + 1, 1) };
+            (first, next.unwrap_or(first + Duration::days(31)))
+        }
+    }
+}
+
+/// Names the goal period `today` falls in ("2026-W42", "2026-10"). When it changes, the
+/// progress starts over.
+fn goal_period_key(period: GoalPeriod, today: chrono::NaiveDate) -> String {
+    match period {
+        GoalPeriod::Week => { let week = today.iso_week(); format!("{}-W{:02}", week.year(), week.week()) }
+        GoalPeriod::Month => format!("{:04}-{:02}", today.year(), today.month()),
+    }
+}
+
+/// How often `watch_goal_period` looks at the clock.
+const GOAL_PERIOD_CHECK: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Restarts the goal progress when a new week or month begins while the app is open. A week
+/// goal is recounted at once. When the month the inbox shows ends, the inbox moves on to the
+/// new month, which recounts a month goal as well; an older month on screen stays as it is.
+async fn watch_goal_period(db: Arc<Db>, ui_weak: slint::Weak<App>) {
+    let mut last = chrono::Local::now().date_naive();
+    loop {
+        tokio::time::sleep(GOAL_PERIOD_CHECK).await;
+        let today = chrono::Local::now().date_naive();
+        let period = db.load_settings().await.unwrap_or_default().unwrap_or_default().goal_period;
+        if matches!(period, GoalPeriod::Week) && goal_period_key(period, today) != goal_period_key(period, last) {
+            tracing::info!("Ny målperiod {}, målräknaren börjar om", goal_period_key(period, today));
+            refresh_goal_count(&db, ui_weak.clone(), today.year(), today.month()).await;
+        }
+        if (today.year(), today.month()) != (last.year(), last.month()) {
+            let ended = format!("{:04}-{:02}", last.year(), last.month());
+            let u = ui_weak.clone();
+            let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = u.upgrade() { if ui.get_active_month() == ended.as_str() { ui.invoke_month_offset(1); } } });
+        }
+        last = today;
+    }
+}
+
+/// Applications counted towards the goal: the current ISO week, or the displayed month `y`/`m`.
+/// Always based on `applied_at`, in local time.
+async fn goal_applied_count(db: &Db, period: GoalPeriod, y: i32, m: u32) -> i32 {
+    use chrono::{Local, NaiveDate, TimeZone};
+    let (from, to) = goal_period_range(period, Local::now().date_naive(), y, m);
+    let to_utc = |d: NaiveDate| Local.from_local_datetime(&d.and_hms_opt(0, 0, 0).unwrap_or_default()).earliest().map(|dt| dt.with_timezone(&chrono::Utc));
+    match (to_utc(from), to_utc(to)) {
+        (Some(from), Some(to)) => db.applied_count_between(from, to).await.unwrap_or(0) as i32,
+        _ => 0,
+    }
+}
+
+/// Number of `ads` fetched since the month was last viewed, and marks it as viewed now.
+/// The first visit of a month shows nothing rather than "everything is new".
+async fn new_since_last_view(db: &Db, y: i32, m: u32, ads: &[crate::models::JobAd]) -> i32 {
+    match db.touch_month_view(&format!("{:04}-{:02}", y, m)).await {
+        Ok(Some(since)) => ads.iter().filter(|ad| ad.internal_created_at > since).count() as i32,
+        _ => 0,
+    }
+}
+
+/// The inbox's "Publicerad från" floor. It lives only in the UI (never in settings), so it
+/// is read before spawning and passed along.
+fn since_date(ui_weak: &slint::Weak<App>) -> Option<chrono::NaiveDate> {
+    ui_weak.upgrade().and_then(|ui| chrono::NaiveDate::parse_from_str(ui.get_since_date().trim(), "%Y-%m-%d").ok())
+}
+
+/// The inbox's active month as (year, month); today's month if the UI is gone or the value
+/// is malformed. Call on the UI thread.
+fn active_year_month(ui_weak: &slint::Weak<App>) -> (i32, u32) {
+    let now = chrono::Local::now();
+    ui_weak.upgrade()
+        .and_then(|ui| { let s = ui.get_active_month().to_string(); let (y, m) = s.split_once('-')?; Some((y.parse().ok()?, m.parse().ok()?)) })
+        .unwrap_or((now.year(), now.month()))
+}
+
+/// Board columns left to right, as (title, status number as in `JobEntry::status`).
+const BOARD_COLUMNS: &[(&str, i32)] = &[("Nya", 0), ("Sparade", 2), ("Tumme upp", 3), ("Sökta", 4)];
+
+/// The status a card gets when moved one column in `direction`; None past either end.
+fn board_step(status: i32, direction: i32) -> Option<i32> {
+    let pos = BOARD_COLUMNS.iter().position(|(_, s)| *s == status)? as i32 + direction.signum();
+    BOARD_COLUMNS.get(usize::try_from(pos).ok()?).map(|(_, s)| *s)
+}
+
+/// Reloads the board from the month's ads (rejected ones are left out, like in the inbox).
+async fn refresh_board(db: &Db, ui_weak: slint::Weak<App>, y: i32, m: u32) {
+    let settings = db.load_settings().await.unwrap_or_default().unwrap_or_default();
+    let Ok(ads) = db.get_filtered_jobs(JobFilter { year: Some(y), month: Some(m), employer_blacklist: Some(settings.blacklisted_employers().as_slice()), month_basis: settings.month_basis, ..Default::default() }).await else { return };
+    let re_html = Regex::new(r"<[^>]*>").expect("Invalid regex");
+    let mut buckets: Vec<Vec<JobEntry>> = vec![Vec::new(); BOARD_COLUMNS.len()];
+    for ad in ads {
+        // An ad counted as applied by its date alone still belongs in the Sökta column
+        let applied = ad.is_applied();
+        let entry = job_to_entry(ad, &re_html);
+        let status = if applied { 4 } else { entry.status };
+        if let Some(pos) = BOARD_COLUMNS.iter().position(|(_, s)| *s == status) { buckets[pos].push(entry); }
+    }
+    let _ = slint::invoke_from_event_loop(move || {
+        if let Some(ui) = ui_weak.upgrade() {
+            let columns: Vec<BoardColumn> = BOARD_COLUMNS.iter().zip(buckets).map(|((title, status), jobs)| BoardColumn { title: (*title).into(), status: *status, jobs: Rc::new(slint::VecModel::from(jobs)).into() }).collect();
+            ui.set_board_columns(Rc::new(slint::VecModel::from(columns)).into());
+        }
+    });
+}
+
+/// One value per compared field for the "Jämför" view, from stored data only. Empty values
+/// show as "–" so both columns keep the same rows.
+fn compare_values(ad: &crate::models::JobAd, scale_max: u8) -> Vec<(&'static str, String)> {
+    let or_dash = |v: Option<String>| v.filter(|s| !s.trim().is_empty()).unwrap_or_else(|| "–".to_string());
+    let mut requirements: Vec<String> = ad.must_have.iter().flat_map(|r| {
+        r.skills.iter().map(|s| s.label.clone())
+            .chain(r.languages.iter().map(|l| l.label.clone()))
+            .chain(r.work_experiences.iter().map(|w| w.label.clone()))
+    }).collect();
+    if ad.driving_license_required { requirements.push("Körkort".to_string()); }
+    vec![
+        ("Rubrik", ad.headline.clone()),
+        ("Arbetsgivare", or_dash(ad.employer.as_ref().and_then(|e| e.name.clone()))),
+        ("Ort", or_dash(ad.workplace_address.as_ref().and_then(|a| a.municipality.clone().or_else(|| a.city.clone())))),
+        ("Omfattning", or_dash(ad.working_hours_type.as_ref().and_then(|w| w.label.clone()))),
+        ("Sista ansökningsdag", or_dash(ad.last_application_date.as_deref().map(|d| d.get(..10).unwrap_or(d).to_string()))),
+        ("Krav", or_dash(Some(requirements.join(", ")))),
+        ("AI-betyg", or_dash(ad.rating.filter(|r| *r > 0).map(|r| format!("{} / {}", r, scale_max)))),
+    ]
+}
+
+/// Reloads the snippet list in settings.
+async fn refresh_snippets(db: &Db, ui_weak: slint::Weak<App>) {
+    let snippets = match db.list_snippets().await {
+        Ok(list) => list,
+        Err(e) => { tracing::warn!("Kunde inte läsa textbitar: {}", e); return; }
+    };
+    let _ = slint::invoke_from_event_loop(move || {
+        if let Some(ui) = ui_weak.upgrade() {
+            let rows: Vec<Snippet> = snippets.into_iter().map(|(name, body)| Snippet { name: name.into(), body: body.into() }).collect();
+            ui.set_snippets(Rc::new(slint::VecModel::from(rows)).into());
+        }
+    });
+}
+
+/// Recounts the goal progress after an ad was applied/unapplied, so the bar moves at once.
+async fn refresh_goal_count(db: &Db, ui_weak: slint::Weak<App>, y: i32, m: u32) {
+    let settings = db.load_settings().await.unwrap_or_default().unwrap_or_default();
+    let count = goal_applied_count(db, settings.goal_period, y, m).await;
+    let _ = slint::invoke_from_event_loop(move || { if let Some(ui) = ui_weak.upgrade() { ui.set_goal_applied_count(count); } });
+}
+
+/// After any status change in month `y`-`m`: recounts both the goal progress and the month's
+/// "Sökta" from the database instead of adjusting them, so they match after switching months.
+async fn refresh_applied_counts(db: &Db, ui_weak: slint::Weak<App>, y: i32, m: u32) {
+    let settings = db.load_settings().await.unwrap_or_default().unwrap_or_default();
+    let goal = goal_applied_count(db, settings.goal_period, y, m).await;
+    let month = match db.applied_count_in_month(y, m, settings.month_basis).await {
+        Ok(n) => Some(n as i32),
+        Err(e) => { tracing::warn!("Kunde inte räkna sökta för {}-{:02}: {}", y, m, e); None }
+    };
+    let _ = slint::invoke_from_event_loop(move || {
+        // The user may have moved on to another month while this was counted
+        if let Some(ui) = ui_weak.upgrade() {
+            ui.set_goal_applied_count(goal);
+            if let Some(n) = month.filter(|_| ui.get_active_month() == format!("{:04}-{:02}", y, m).as_str()) { ui.set_applied_count(n); }
+        }
+    });
+}
+
+/// Most full descriptions fetched in the background after one search, and the pause between
+/// them, so a big search doesn't turn into hundreds of back-to-back API calls.
+const PREFETCH_MAX: usize = 20;
+const PREFETCH_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Fetches the full description of one ad and stores it. Returns the updated ad.
+async fn fetch_full_description(api: &JobSearchClient, db: &Db, id: &str) -> Option<crate::models::JobAd> {
+    match api.get_ad(id).await {
+        Ok(full) if !full.description_looks_truncated() => db.set_description(id, full.description).await.ok(),
+        Ok(_) => None,
+        Err(e) => { tracing::warn!("Kunde inte hämta hela annonsen {}: {:#}", id, e); None }
+    }
+}
+
+/// Background pass after a search: fills in cut-short descriptions, a few at a time.
+async fn prefetch_descriptions(api: JobSearchClient, db: Arc<Db>, ids: Vec<String>) {
+    for id in ids.into_iter().take(PREFETCH_MAX) {
+        fetch_full_description(&api, &db, &id).await;
+        tokio::time::sleep(PREFETCH_DELAY).await;
+    }
+}
+
+/// Parses the optional "sökt datum" input (YYYY-MM-DD). Empty means now (`None`). The time is
+/// set to local noon so the ad lands on the chosen day in every timezone-aware view.
+fn parse_applied_date(input: &str) -> Result<Option<chrono::DateTime<chrono::Utc>>, String> {
+    let input = input.trim();
+    if input.is_empty() { return Ok(None); }
+    let date = chrono::NaiveDate::parse_from_str(input, "%Y-%m-%d").map_err(|_| format!("Ogiltigt datum: {} (använd ÅÅÅÅ-MM-DD)", input))?;
+    let local = date.and_hms_opt(12, 0, 0).and_then(|dt| dt.and_local_timezone(chrono::Local).single()).ok_or_else(|| format!("Ogiltigt datum: {}", input))?;
+    Ok(Some(local.with_timezone(&chrono::Utc)))
+}
+
+/// "it: 12 nya, support: 0 nya, helpdesk: fel" — shows which keywords actually bring in ads.
+fn format_keyword_breakdown(per_keyword: &[(String, Option<usize>)]) -> String {
+    per_keyword.iter().map(|(kw, count)| match count { Some(n) => format!("{}: {} nya", kw, n), None => format!("{}: fel", kw) }).collect::<Vec<_>>().join(", ")
+}
+
+/// "it → 1240 träffar, support → 310 träffar, helpdesk → fel" for the keyword test in settings.
+fn format_hit_counts(counts: &[(String, Option<u64>)]) -> String {
+    counts.iter().map(|(kw, count)| match count { Some(n) => format!("{} → {} träffar", kw, n), None => format!("{} → fel", kw) }).collect::<Vec<_>>().join(", ")
+}
+
+/// Tries at startup before a locked database counts as busy, and the wait between tries.
+const DB_OPEN_ATTEMPTS: u32 = 5;
+const DB_OPEN_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Opens the database, waiting out a short-lived lock. When that fails, a small window
+/// explains why instead of a crash ("Databasen används av ett annat program"), with
+/// "Försök igen" and "Avsluta". None when the user gives up.
+fn open_db_at_startup(rt: &Runtime) -> Option<Db> {
+    let db_path = get_db_path();
+    let path = db_path.to_string_lossy().to_string();
+    loop {
+        let error = match rt.block_on(Db::open_with_retry(&path, DB_OPEN_ATTEMPTS, DB_OPEN_RETRY_DELAY)) {
+            Ok(db) => return Some(db),
+            Err(e) => e,
+        };
+        eprintln!("Kunde inte öppna databasen: {}", error);
+        tracing::error!("Kunde inte öppna databasen: {}", error);
+        let message = match &error {
+            crate::db::DbError::BusyLock(_) => format!("Databasen används av ett annat program — stäng det och försök igen.\n\n{}", db_path.display()),
+            // The other variants already carry their own guidance
+            e => e.to_string(),
+        };
+        let window = match StartupErrorWindow::new() { Ok(w) => w, Err(e) => { eprintln!("Kunde inte visa felfönstret: {}", e); return None; } };
+        window.set_message(message.into());
+        let retry = Rc::new(std::cell::Cell::new(false));
+        let (w_retry, r) = (window.as_weak(), retry.clone());
+        window.on_retry(move || { r.set(true); if let Some(w) = w_retry.upgrade() { let _ = w.hide(); } });
+        let w_quit = window.as_weak();
+        window.on_quit(move || { if let Some(w) = w_quit.upgrade() { let _ = w.hide(); } });
+        if window.run().is_err() || !retry.get() { return None; }
+    }
+}
+
+/// Seeds the default settings on a fresh install; logged once, since later starts find the row.
+/// A settings.json left by the old iced frontend is imported first and wins over the defaults.
+fn seed_first_run(rt: &Runtime, db: &Db) {
+    if let Some(legacy) = paths::legacy_settings_path() {
+        match rt.block_on(db.import_legacy_settings(&legacy)) {
+            Ok(Some(imported)) => tracing::info!("Importerade gamla inställningar från {}; filen heter nu {}", legacy.display(), imported.display()),
+            Ok(None) => {}
+            // Only the old file is affected; the defaults are seeded below as on any first start
+            Err(e) => tracing::warn!("{}", e),
+        }
+    }
+    match rt.block_on(db.seed_first_run()) {
+        Ok(true) => tracing::info!("Första start: standardinställningar sparade i databasen"),
+        Ok(false) => {}
+        Err(e) => tracing::warn!("Kunde inte spara standardinställningar vid första start: {}", e),
     }
 }
 
 pub fn desktop_main() {
     setup_crash_handler();
-    let (guard, log_rx) = setup_logging();
+    let rt = Arc::new(Runtime::new().expect("Failed to create Tokio runtime"));
+    // The database is opened before logging starts, since the settings decide whether a log
+    // file is written; a failure here only goes to stderr and the startup error window
+    let Some(db) = open_db_at_startup(&rt) else { return };
+    let file_logging = !rt.block_on(db.load_settings()).ok().flatten().is_some_and(|s| s.disable_file_log);
+    let (guard, log_rx) = setup_logging(file_logging);
     setup_clipboard_manager();
     tracing::info!("Starting Jobseeker on Desktop");
-    let rt = Arc::new(Runtime::new().expect("Failed to create Tokio runtime"));
-    let db_path = get_db_path();
-    let db = rt.block_on(async { Db::new(db_path.to_str().unwrap()).await }).expect("Failed to initialize database");
+    seed_first_run(&rt, &db);
     let db = Arc::new(db);
     let ui = App::new().expect("Failed to create Slint UI");
     setup_ui(&ui, rt, db, log_rx);
@@ -521,10 +2461,10 @@ fn android_main(app: slint::android::AndroidApp) {
     slint::android::init(app).expect("Failed to initialize Slint on Android");
     
     let rt = Arc::new(Runtime::new().expect("Failed to create Tokio runtime"));
-    let (guard, log_rx) = setup_logging();
+    let (guard, log_rx) = setup_logging(false);
     
-    let db_path = get_db_path();
-    let db = rt.block_on(async { Db::new(db_path.to_str().unwrap()).await }).expect("Failed to initialize database");
+    let Some(db) = open_db_at_startup(&rt) else { return };
+    seed_first_run(&rt, &db);
     let db = Arc::new(db);
     
     let ui = App::new().expect("Failed to create Slint UI");
@@ -532,4 +2472,246 @@ fn android_main(app: slint::android::AndroidApp) {
     
     let _log_guard = guard;
     ui.run().expect("Failed to run Slint UI");
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shift_month_wraps_across_years() {
+        assert_eq!(shift_month(2026, 1, -1), (2025, 12));
+        assert_eq!(shift_month(2025, 12, 1), (2026, 1));
+        assert_eq!(shift_month(2026, 5, 13), (2027, 6));
+        assert_eq!(shift_month(2026, 5, -13), (2025, 4));
+        assert_eq!(shift_month(2026, 3, 0), (2026, 3));
+    }
+
+    #[test]
+    fn recent_list_shows_each_ad_once_at_its_latest_view() {
+        let view = |id: &str| crate::db::RecentView { id: id.to_string(), viewed_at: chrono::Utc::now() };
+        let views = [view("b"), view("a"), view("b"), view("c")];
+        assert_eq!(distinct_view_ids(&views), ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn free_search_is_reported_as_nationwide() {
+        assert_eq!(search_scope_message(None, ""), "Fritextsökning i hela landet. Söker efter nytt...");
+        assert_eq!(search_scope_message(Some(1), "Malmö, Lund"), "Visar sparade jobb för Malmö, Lund. Söker efter nytt...");
+        assert!(search_scope_message(Some(0), "Malmö, 1283").contains("alla zoner"));
+        assert!(!search_scope_message(Some(2), " ").contains("för ."));
+    }
+
+    #[test]
+    fn empty_inbox_hint_names_the_zone_searched() {
+        let settings = crate::models::AppSettings { keywords: "support, lager".to_string(), locations_p2: "Lund".to_string(), ..Default::default() };
+        assert!(empty_inbox_hint(&EmptyInbox::NotSearched, &settings).contains("P1, P2 eller P3"));
+        let found = empty_inbox_hint(&EmptyInbox::NothingFound(crate::search::SearchProfile::Zone(2)), &settings);
+        assert!(found.contains("P2") && found.contains("Sökord: support, lager") && found.contains("Orter: Lund"));
+        assert!(empty_inbox_hint(&EmptyInbox::Unreachable, &settings).contains("Kunde inte nå"));
+        // Without keywords there is nothing to search yet, whatever happened before
+        let empty = crate::models::AppSettings { keywords: " ".to_string(), ..Default::default() };
+        assert!(empty_inbox_hint(&EmptyInbox::NotSearched, &empty).contains("Inga sökord"));
+        assert!(empty_inbox_hint(&EmptyInbox::NothingFound(crate::search::SearchProfile::FreeText("kock".to_string())), &empty).contains("\"kock\""));
+    }
+
+    #[test]
+    fn headings_keep_their_emphasis_in_the_detail_view() {
+        let ad: crate::models::JobAd = serde_json::from_value(serde_json::json!({
+            "id": "d",
+            "description": { "text": "<p><strong>Om tjänsten</strong></p><p>Du arbetar med <b>support</b>.</p><p><em>Tillträde enligt överenskommelse</em></p>" }
+        })).unwrap();
+        let re_html = Regex::new(r"<[^>]*>").unwrap();
+        let runs: Vec<(String, bool, bool)> = description_runs(&ad, &re_html).into_iter().map(|r| (r.text.to_string(), r.bold, r.italic)).collect();
+        assert_eq!(runs, vec![
+            ("Om tjänsten".to_string(), true, false),
+            (String::new(), false, false),
+            ("Du arbetar med support.".to_string(), false, false),
+            (String::new(), false, false),
+            ("Tillträde enligt överenskommelse".to_string(), false, true),
+        ]);
+        // The flat text has no markers left
+        assert_eq!(job_to_entry(ad, &re_html).description.trim(), "Om tjänsten\n\nDu arbetar med support.\n\nTillträde enligt överenskommelse");
+    }
+
+    #[test]
+    fn occupation_facets_count_fields_and_filter_the_list() {
+        let ad = |id: &str, field: Option<&str>| -> crate::models::JobAd { serde_json::from_value(serde_json::json!({ "id": id, "occupation_field": field.map(|f| serde_json::json!({ "label": f })) })).unwrap() };
+        let ads = vec![ad("1", Some("Data/IT")), ad("2", Some("Administration")), ad("3", Some("Data/IT")), ad("4", None)];
+        let facets: Vec<(String, i32)> = occupation_facets(&ads).into_iter().map(|f| (f.name.to_string(), f.count)).collect();
+        assert_eq!(facets, vec![("Data/IT".to_string(), 2), ("Administration".to_string(), 1)]);
+
+        let re_html = Regex::new(r"<[^>]*>").unwrap();
+        let mut entries: Vec<JobEntry> = ads.into_iter().map(|a| job_to_entry(a, &re_html)).collect();
+        entries[3].pinned = true;
+        apply_quick_filter(&mut entries, "", "Data/IT", &AppSettings::default());
+        let visible: Vec<&str> = entries.iter().filter(|e| !e.filtered_out).map(|e| e.id.as_str()).collect();
+        assert_eq!(visible, vec!["1", "3", "4"]);
+    }
+
+    #[test]
+    fn saved_links_prefer_the_ad_page_and_respect_the_cap() {
+        let row = |id: &str, status: i32, page: &str, apply: &str| JobEntry { id: id.into(), status, webpage_url: page.into(), apply_url: apply.into(), ..Default::default() };
+        let mut entries = vec![
+            row("1", 2, "https://ad/1", "https://apply/1"),
+            row("2", 3, "", "https://apply/2"),
+            row("3", 0, "https://ad/3", ""),
+            row("4", 2, "", ""),
+            row("5", 3, "https://ad/5", ""),
+        ];
+        assert_eq!(saved_links(&entries, 0), vec!["https://ad/1", "https://apply/2", "https://ad/5"]);
+        assert_eq!(saved_links(&entries, 2), vec!["https://ad/1", "https://apply/2"]);
+        entries[0].filtered_out = true;
+        assert_eq!(saved_links(&entries, 0), vec!["https://apply/2", "https://ad/5"]);
+    }
+
+    #[test]
+    fn goal_period_resets_at_the_month_boundary() {
+        let day = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(goal_period_range(GoalPeriod::Month, day(2026, 12, 31), 2026, 12), (day(2026, 12, 1), day(2027, 1, 1)));
+        assert_eq!(goal_period_range(GoalPeriod::Month, day(2027, 1, 1), 2027, 1), (day(2027, 1, 1), day(2027, 2, 1)));
+        assert_ne!(goal_period_key(GoalPeriod::Month, day(2026, 12, 31)), goal_period_key(GoalPeriod::Month, day(2027, 1, 1)));
+        assert_eq!(goal_period_key(GoalPeriod::Month, day(2026, 10, 1)), goal_period_key(GoalPeriod::Month, day(2026, 10, 31)));
+    }
+
+    #[test]
+    fn goal_period_resets_at_the_week_boundary() {
+        let day = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        // Sunday 18 October 2026 ends week 42; Monday starts week 43
+        assert_eq!(goal_period_range(GoalPeriod::Week, day(2026, 10, 18), 2026, 10), (day(2026, 10, 12), day(2026, 10, 19)));
+        assert_eq!(goal_period_range(GoalPeriod::Week, day(2026, 10, 19), 2026, 10), (day(2026, 10, 19), day(2026, 10, 26)));
+        assert_eq!(goal_period_key(GoalPeriod::Week, day(2026, 10, 18)), "2026-W42");
+        assert_eq!(goal_period_key(GoalPeriod::Week, day(2026, 10, 19)), "2026-W43");
+        // A week that straddles New Year is one period, named after its ISO year
+        assert_eq!(goal_period_key(GoalPeriod::Week, day(2026, 12, 31)), goal_period_key(GoalPeriod::Week, day(2027, 1, 3)));
+        assert_eq!(goal_period_range(GoalPeriod::Week, day(2027, 1, 3), 2027, 1).0, day(2026, 12, 28));
+    }
+
+    #[test]
+    fn undone_reject_puts_the_row_back_in_order() {
+        let row = |id: &str, date: &str, status: i32| JobEntry { id: id.into(), date: date.into(), status, ..Default::default() };
+        let mut rows = vec![row("new", "2026-03-03", 0), row("old", "2026-03-01", 0)];
+        restore_row(&mut rows, row("mid", "2026-03-02", 0));
+        assert_eq!(rows.iter().map(|r| r.id.as_str()).collect::<Vec<_>>(), vec!["new", "mid", "old"]);
+        // Undoing an apply only changes the row's status
+        rows[0].status = 4;
+        restore_row(&mut rows, row("new", "2026-03-03", 2));
+        assert_eq!((rows.len(), rows[0].status), (3, 2));
+        restore_row(&mut rows, row("old", "2026-03-01", 1));
+        assert_eq!(rows.len(), 2);
+    }
+
+    #[test]
+    fn salary_order_puts_unknown_salaries_last() {
+        let row = |id: &str, date: &str, salary: i32| JobEntry { id: id.into(), date: date.into(), salary, ..Default::default() };
+        let mut rows = vec![row("unknown-new", "2026-03-20", 0), row("low", "2026-03-01", 28_000), row("high", "2026-02-01", 42_000), row("unknown-old", "2026-01-05", 0)];
+        rows.sort_by(list_order(&crate::models::AppSettings { sort_by_salary: true, ..Default::default() }));
+        assert_eq!(rows.iter().map(|r| r.id.to_string()).collect::<Vec<_>>(), vec!["high", "low", "unknown-new", "unknown-old"]);
+
+        let ad = |id: &str, salary_max: Option<u32>| crate::models::JobAd { salary_max, ..serde_json::from_value(serde_json::json!({ "id": id })).unwrap() };
+        let mut ads = vec![ad("low", Some(25_000)), ad("high", Some(40_000)), ad("unknown", None)];
+        let settings = crate::models::AppSettings { min_salary: 30_000, ..Default::default() };
+        filter_all_view(&mut ads, &settings, None);
+        assert_eq!(ads.iter().map(|a| a.id.as_str()).collect::<Vec<_>>(), vec!["high", "unknown"]);
+        filter_all_view(&mut ads, &crate::models::AppSettings { hide_unknown_salary: true, ..settings }, None);
+        assert_eq!(ads.iter().map(|a| a.id.as_str()).collect::<Vec<_>>(), vec!["high"]);
+    }
+
+    #[test]
+    fn language_filter_keeps_unclear_and_handled_ads() {
+        let ad = |id: &str, language: Option<&str>| crate::models::JobAd { detected_language: language.map(str::to_string), ..serde_json::from_value(serde_json::json!({ "id": id })).unwrap() };
+        let mut rejected = ad("rejected-en", Some("en"));
+        rejected.status = Some(AdStatus::Rejected);
+        let mut ads = vec![ad("sv", Some("sv")), ad("en", Some("en")), ad("unclear", None), rejected];
+        filter_all_view(&mut ads, &crate::models::AppSettings { language_filter: LanguageFilter::Swedish, ..Default::default() }, None);
+        assert_eq!(ads.iter().map(|a| a.id.as_str()).collect::<Vec<_>>(), vec!["sv", "unclear", "rejected-en"]);
+    }
+
+    #[test]
+    fn hit_counts_read_per_keyword() {
+        let counts = [("it".to_string(), Some(1240)), ("support".to_string(), Some(310)), ("helpdesk".to_string(), None)];
+        assert_eq!(format_hit_counts(&counts), "it → 1240 träffar, support → 310 träffar, helpdesk → fel");
+    }
+
+    #[test]
+    fn pinned_rows_stay_first_whatever_the_date() {
+        let row = |id: &str, date: &str, pinned: bool| JobEntry { id: id.into(), date: date.into(), pinned, ..Default::default() };
+        let mut rows = vec![row("new", "2026-03-05", false), row("old-pin", "2026-01-02", true), row("mid", "2026-02-10", false), row("new-pin", "2026-02-20", true)];
+        rows.sort_by(inbox_order);
+        let ids: Vec<String> = rows.iter().map(|r| r.id.to_string()).collect();
+        assert_eq!(ids, ["new-pin", "old-pin", "new", "mid"]);
+        // Capping the list never drops a pinned row while unpinned ones remain
+        cap_inbox_rows(&mut rows, 2);
+        assert!(rows.iter().all(|r| r.pinned));
+    }
+
+    #[test]
+    fn merge_pinned_adds_pinned_ads_from_outside_the_list_once() {
+        let mut pinned_here = ad_in("1283"); pinned_here.id = "here".into(); pinned_here.pinned = true;
+        let mut pinned_elsewhere = ad_in("0180"); pinned_elsewhere.id = "elsewhere".into(); pinned_elsewhere.pinned = true;
+        let mut plain = ad_in("1283"); plain.id = "plain".into();
+        let mut ads = vec![plain, pinned_here.clone()];
+        merge_pinned(&mut ads, vec![pinned_elsewhere, pinned_here]);
+        let ids: Vec<&str> = ads.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, ["elsewhere", "here", "plain"]);
+    }
+
+    fn ad_in(municipality: &str) -> crate::models::JobAd {
+        serde_json::from_value(serde_json::json!({ "id": "z", "workplace_address": { "municipality": municipality } })).unwrap()
+    }
+
+    #[test]
+    fn compare_values_fill_missing_fields_with_a_dash() {
+        let mut ad: crate::models::JobAd = serde_json::from_value(serde_json::json!({
+            "id": "c", "headline": "Lagerarbetare",
+            "employer": { "name": "Testbolaget AB" },
+            "must_have": { "skills": [{ "label": "Truckkort" }], "languages": [{ "label": "Svenska" }] },
+            "driving_license_required": true
+        })).unwrap();
+        ad.rating = Some(7);
+        let values = compare_values(&ad, 10);
+        let bare = compare_values(&ad_in("1283"), 10);
+        assert_eq!(values.len(), bare.len());
+        assert!(values.contains(&("Krav", "Truckkort, Svenska, Körkort".to_string())));
+        assert!(values.contains(&("AI-betyg", "7 / 10".to_string())));
+        assert!(bare.contains(&("Arbetsgivare", "–".to_string())));
+        assert!(bare.contains(&("AI-betyg", "–".to_string())));
+    }
+
+    #[test]
+    fn board_step_moves_one_column_and_stops_at_the_ends() {
+        assert_eq!(board_step(0, 1), Some(2));
+        assert_eq!(board_step(3, 1), Some(4));
+        assert_eq!(board_step(2, -1), Some(0));
+        assert_eq!(board_step(0, -1), None);
+        assert_eq!(board_step(4, 1), None);
+        assert_eq!(board_step(1, 1), None);
+    }
+
+    #[test]
+    fn zone_filter_matches_code_or_name() {
+        let zone = vec!["1283".to_string(), "1277".to_string()];
+        assert!(in_zone(&ad_in("1283"), &zone));
+        assert!(in_zone(&ad_in("Helsingborg"), &zone));
+        assert!(!in_zone(&ad_in("Malmö"), &zone));
+        assert!(!in_zone(&ad_in("1280"), &zone));
+        let no_address: crate::models::JobAd = serde_json::from_value(serde_json::json!({ "id": "x" })).unwrap();
+        assert!(!in_zone(&no_address, &zone));
+    }
+
+    #[test]
+    fn hide_applied_only_affects_the_all_view() {
+        let mut applied = ad_in("1283");
+        applied.status = Some(AdStatus::Applied);
+        let ads = vec![applied, ad_in("1283")];
+        let settings = crate::models::AppSettings { hide_applied_in_all: true, ..Default::default() };
+        let mut all = ads.clone();
+        filter_all_view(&mut all, &settings, None);
+        assert_eq!(all.len(), 1);
+        let mut tagged = ads.clone();
+        filter_all_view(&mut tagged, &settings, Some("distans"));
+        assert_eq!(tagged.len(), 2);
+        let mut off = ads;
+        filter_all_view(&mut off, &Default::default(), None);
+        assert_eq!(off.len(), 2);
+    }
+}
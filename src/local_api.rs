@@ -0,0 +1,126 @@
+//! A small read-only HTTP API over the stored ads, for scripts and dashboards on the same
+//! machine. Off unless `AppSettings::local_api_port` is set; it only ever binds 127.0.0.1,
+//! and runs on the app's runtime, so it stops when the app does.
+//!
+//! - `GET /ads?year=2026&month=3&status=applied` – the ads as a JSON array. All parameters
+//!   are optional; `status` is one or more of rejected, bookmarked, thumbsup, applied
+//!   (comma-separated). Without it rejected ads are left out, as in the inbox.
+//! - `GET /ad/{id}` – one ad as stored, 404 if there is none.
+//!
+//! Requests whose `Host` isn't `127.0.0.1:<port>` or `localhost:<port>` get 403, so a web
+//! page that rebinds its own domain to 127.0.0.1 still can't read the ads.
+
+use crate::db::{Db, JobFilter};
+use crate::models::AdStatus;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Largest request head read; the API has no request bodies.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+/// How long a connection may take to send its request head before it is closed.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Listens on 127.0.0.1:`port` (0 picks a free port, for tests).
+pub async fn bind(port: u16) -> std::io::Result<TcpListener> {
+    TcpListener::bind((std::net::Ipv4Addr::LOCALHOST, port)).await
+}
+
+/// Answers requests on `listener` until the task is dropped, one task per connection.
+pub async fn serve(listener: TcpListener, db: Arc<Db>) {
+    let port = listener.local_addr().map(|a| a.port()).unwrap_or_default();
+    loop {
+        let (mut socket, peer) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => { tracing::warn!("Lokala API:t kunde inte ta emot anslutning: {}", e); continue; }
+        };
+        let db = db.clone();
+        tokio::spawn(async move {
+            let mut buf = Vec::new();
+            let mut chunk = [0u8; 1024];
+            let read_head = async {
+                while !buf.windows(4).any(|w| w == b"\r\n\r\n") && buf.len() < MAX_REQUEST_BYTES {
+                    match socket.read(&mut chunk).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                    }
+                }
+            };
+            if tokio::time::timeout(READ_TIMEOUT, read_head).await.is_err() {
+                tracing::debug!("Lokala API:t {} skickade ingen förfrågan i tid", peer);
+                return;
+            }
+            let request = String::from_utf8_lossy(&buf);
+            let first_line = request.lines().next().unwrap_or("");
+            let (status, body) = if host_allowed(&request, port) {
+                respond(&db, first_line).await
+            } else {
+                error("403 Forbidden", "bara 127.0.0.1 och localhost")
+            };
+            tracing::debug!("Lokala API:t {} {} → {}", peer, first_line, status);
+            let response = format!("HTTP/1.1 {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body);
+            let _ = socket.write_all(response.as_bytes()).await;
+            let _ = socket.shutdown().await;
+        });
+    }
+}
+
+/// Whether the request head's `Host` header names this server on the loopback address.
+fn host_allowed(head: &str, port: u16) -> bool {
+    let host = head.lines().skip(1).find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim().eq_ignore_ascii_case("host").then(|| value.trim().to_ascii_lowercase())
+    });
+    host.is_some_and(|h| h == format!("127.0.0.1:{}", port) || h == format!("localhost:{}", port))
+}
+
+fn error(status: &'static str, message: &str) -> (&'static str, String) {
+    (status, serde_json::json!({ "error": message }).to_string())
+}
+
+/// Status line and JSON body for a request line such as `GET /ads?year=2026 HTTP/1.1`.
+async fn respond(db: &Db, request_line: &str) -> (&'static str, String) {
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else { return error("400 Bad Request", "ogiltig förfrågan") };
+    if method != "GET" { return error("405 Method Not Allowed", "bara GET stöds"); }
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let params: HashMap<String, String> = query.split('&').filter_map(|p| p.split_once('='))
+        .map(|(k, v)| (k.to_string(), urlencoding::decode(&v.replace('+', " ")).map(|v| v.into_owned()).unwrap_or_default()))
+        .collect();
+
+    if path == "/ads" {
+        let year = match params.get("year").map(|y| y.parse::<i32>()) { None => None, Some(Ok(y)) => Some(y), Some(Err(_)) => return error("400 Bad Request", "year måste vara ett årtal") };
+        let month = match params.get("month").map(|m| m.parse::<u32>()) { None => None, Some(Ok(m)) if (1..=12).contains(&m) => Some(m), _ => return error("400 Bad Request", "month måste vara 1–12") };
+        let mut statuses = Vec::new();
+        for s in params.get("status").map(|s| s.split(',').map(str::trim).filter(|s| !s.is_empty()).collect::<Vec<_>>()).unwrap_or_default() {
+            match parse_status(s) { Some(status) => statuses.push(status), None => return error("400 Bad Request", &format!("okänd status '{}'", s)) }
+        }
+        let basis = db.load_settings().await.ok().flatten().unwrap_or_default().month_basis;
+        // A year without a month (or the reverse) filters nothing, like in get_filtered_jobs
+        return match db.get_filtered_jobs(JobFilter { statuses: &statuses, year, month, month_basis: basis, ..Default::default() }).await {
+            Ok(ads) => ("200 OK", serde_json::to_string(&ads).unwrap_or_else(|_| "[]".to_string())),
+            Err(e) => error("500 Internal Server Error", &e.to_string()),
+        };
+    }
+    if let Some(id) = path.strip_prefix("/ad/").filter(|id| !id.is_empty()) {
+        let id = urlencoding::decode(id).map(|id| id.into_owned()).unwrap_or_default();
+        return match db.get_raw_job_ad(&id).await {
+            Ok(Some(json)) => ("200 OK", json),
+            Ok(None) => error("404 Not Found", &format!("annonsen {} finns inte", id)),
+            Err(e) => error("500 Internal Server Error", &e.to_string()),
+        };
+    }
+    error("404 Not Found", "okänd sökväg; använd /ads eller /ad/{id}")
+}
+
+fn parse_status(s: &str) -> Option<AdStatus> {
+    match s.to_lowercase().as_str() {
+        "rejected" => Some(AdStatus::Rejected),
+        "bookmarked" => Some(AdStatus::Bookmarked),
+        "thumbsup" => Some(AdStatus::ThumbsUp),
+        "applied" => Some(AdStatus::Applied),
+        _ => None,
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+
+/// Employer logos are cached on disk, one file per employer, so each logo is fetched once.
+fn cache_dir() -> PathBuf {
+    crate::paths::cache_dir("logos")
+}
+
+/// Last use of each cached file, by file name, kept next to the logos. Files missing from it
+/// (cached before it existed) count by their modification time.
+const INDEX_FILE: &str = "index.json";
+
+/// File name for an employer's logo: a hash of the lowercased name, so "A/B" and "A_B" get
+/// different files, plus the extension from the URL (Slint picks the decoder from it).
+fn cache_file_name(employer: &str, url: &str) -> String {
+    let key = fnv1a(employer.to_lowercase().as_bytes());
+    let path = url.split(['?', '#']).next().unwrap_or("");
+    let ext = path.rsplit('.').next().map(|e| e.to_lowercase()).filter(|e| ["png", "jpg", "jpeg", "svg", "gif", "webp"].contains(&e.as_str())).unwrap_or_else(|| "png".to_string());
+    format!("{:016x}.{}", key, ext)
+}
+
+/// 64-bit FNV-1a. `DefaultHasher` may change between Rust releases, which would orphan the
+/// whole cache after an upgrade.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |h, &b| (h ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3))
+}
+
+fn load_index(dir: &Path) -> HashMap<String, DateTime<Utc>> {
+    std::fs::read_to_string(dir.join(INDEX_FILE)).ok().and_then(|s| serde_json::from_str(&s).ok()).unwrap_or_default()
+}
+
+fn save_index(dir: &Path, index: &HashMap<String, DateTime<Utc>>) {
+    if let Ok(json) = serde_json::to_string(index) {
+        if let Err(e) = crate::paths::write_atomic(&dir.join(INDEX_FILE), json) { tracing::debug!("Kunde inte spara logotypindex: {}", e); }
+    }
+}
+
+/// Records that `name` was just used.
+fn touch(dir: &Path, name: &str) {
+    let mut index = load_index(dir);
+    index.insert(name.to_string(), Utc::now());
+    save_index(dir, &index);
+}
+
+/// Deletes the least recently used logos until the cache is at most `max_bytes` (0 = no
+/// limit). Returns how many files were removed.
+fn evict_lru(dir: &Path, max_bytes: u64) -> usize {
+    if max_bytes == 0 { return 0; }
+    let mut index = load_index(dir);
+    let Ok(entries) = std::fs::read_dir(dir) else { return 0 };
+    let mut files: Vec<(DateTime<Utc>, String, u64)> = entries.filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_str()?.to_string();
+            let meta = e.metadata().ok().filter(|m| m.is_file() && name != INDEX_FILE)?;
+            let used = index.get(&name).copied().or_else(|| meta.modified().ok().map(DateTime::<Utc>::from)).unwrap_or_default();
+            Some((used, name, meta.len()))
+        })
+        .collect();
+    let mut total: u64 = files.iter().map(|f| f.2).sum();
+    if total <= max_bytes { return 0; }
+    files.sort();
+    let mut removed = 0;
+    for (_, name, size) in files {
+        if total <= max_bytes { break; }
+        if std::fs::remove_file(dir.join(&name)).is_ok() {
+            total -= size;
+            index.remove(&name);
+            removed += 1;
+        }
+    }
+    save_index(dir, &index);
+    removed
+}
+
+/// Returns the cached logo for `employer`, downloading it from `url` through `proxy` (as the
+/// searches do) the first time. After a download the cache is trimmed to `max_bytes` (0 = no
+/// limit). Any failure just means no logo; the detail view works fine without one.
+pub async fn cached_logo(employer: &str, url: &str, proxy: Option<&str>, max_bytes: u64) -> Option<PathBuf> {
+    if employer.trim().is_empty() || url.trim().is_empty() { return None; }
+    let dir = cache_dir();
+    let name = cache_file_name(employer, url);
+    let path = dir.join(&name);
+    if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+        let _ = tokio::task::spawn_blocking(move || touch(&dir, &name)).await;
+        return Some(path);
+    }
+
+    let client = match crate::api::http_client(proxy) {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::debug!("Kunde inte hämta logotyp för {}: {}", employer, e);
+            return None;
+        }
+    };
+    let response = match client.get(url).send().await {
+        Ok(r) if r.status().is_success() => r,
+        Ok(r) => {
+            tracing::debug!("Logotyp för {} gav HTTP {}", employer, r.status());
+            return None;
+        }
+        Err(e) => {
+            tracing::debug!("Kunde inte hämta logotyp för {}: {}", employer, e);
+            return None;
+        }
+    };
+    let bytes = response.bytes().await.ok()?;
+    tokio::fs::create_dir_all(&dir).await.ok()?;
+    tokio::fs::write(&path, &bytes).await.ok()?;
+    // The index and the eviction walk the whole cache directory, so they run off the runtime
+    let evicted = tokio::task::spawn_blocking(move || {
+        touch(&dir, &name);
+        evict_lru(&dir, max_bytes)
+    }).await.unwrap_or(0);
+    if evicted > 0 { tracing::debug!("Logotypcache: tog bort {} äldre logotyper", evicted); }
+    // A single logo larger than the whole cap is evicted itself
+    tokio::fs::try_exists(&path).await.unwrap_or(false).then_some(path)
+}
+
+/// Empties the logo cache ("Rensa bildcache"). Returns how many logos were removed.
+pub fn clear_cache() -> std::io::Result<usize> {
+    let dir = cache_dir();
+    if !dir.exists() { return Ok(0); }
+    let mut removed = 0;
+    for entry in std::fs::read_dir(&dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() { continue; }
+        std::fs::remove_file(entry.path())?;
+        if entry.file_name() != INDEX_FILE { removed += 1; }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn employers_that_differ_only_in_punctuation_get_separate_files() {
+        let url = "https://example.com/logo.PNG?v=2";
+        assert_ne!(cache_file_name("A/B", url), cache_file_name("A_B", url));
+        assert_eq!(cache_file_name("Acme AB", url), cache_file_name("ACME AB", url));
+        assert!(cache_file_name("Acme AB", url).ends_with(".png"));
+    }
+
+    #[test]
+    fn exceeding_the_cap_evicts_the_least_recently_used_logo() {
+        let dir = std::env::temp_dir().join(format!("jobseeker_logos_{}_{}", std::process::id(), Utc::now().timestamp_millis()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let now = Utc::now();
+        let mut index = HashMap::new();
+        for (i, name) in ["oldest.png", "middle.png", "newest.png"].iter().enumerate() {
+            std::fs::write(dir.join(name), [0u8; 10]).unwrap();
+            index.insert(name.to_string(), now - chrono::Duration::minutes(10 - i as i64));
+        }
+        save_index(&dir, &index);
+
+        assert_eq!(evict_lru(&dir, 30), 0);
+        assert_eq!(evict_lru(&dir, 25), 1);
+        assert!(!dir.join("oldest.png").exists());
+        assert!(dir.join("middle.png").exists() && dir.join("newest.png").exists());
+        assert!(!load_index(&dir).contains_key("oldest.png"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -46,14 +46,20 @@ impl<'de> Deserialize<'de> for AdStatus {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct JobAd {
     pub id: String,
+    #[serde(default = "default_headline")]
     pub headline: String,
     pub description: Option<Description>,
     pub employer: Option<Employer>,
     pub application_details: Option<ApplicationDetails>,
     pub webpage_url: Option<String>,
+    /// Empty when the API left it out; `api::ad_from_hit` fills in the fetch time then.
+    #[serde(default)]
     pub publication_date: String,
     pub last_application_date: Option<String>,
     pub occupation: Option<Occupation>,
+    /// Yrkesområde, e.g. "Data/IT". Ads stored before it was kept don't have it.
+    #[serde(default)]
+    pub occupation_field: Option<Occupation>,
     pub workplace_address: Option<WorkplaceAddress>,
     pub working_hours_type: Option<WorkingHours>,
     #[serde(default)]
@@ -77,6 +83,243 @@ pub struct JobAd {
     pub status: Option<AdStatus>,
     #[serde(default)]
     pub applied_at: Option<DateTime<Utc>>,
+    /// How the application was sent, one of `APPLIED_CHANNELS`. None for ads applied to
+    /// before this was tracked.
+    #[serde(default)]
+    pub applied_channel: Option<String>,
+    /// Employer logo from the jobtech hit, when there is one.
+    #[serde(default)]
+    pub logo_url: Option<String>,
+    /// User tags such as "distans" or "hög lön", stored lowercased (see `parse_tags`).
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// "Important" marker, independent of `status`: a starred ad stays in its bucket.
+    #[serde(default)]
+    pub starred: bool,
+    /// Pinned to the top of the inbox; shown in every month, tag and filter until unpinned.
+    #[serde(default)]
+    pub pinned: bool,
+    /// Number of positions, jobtech's `number_of_vacancies`. None when the hit left it out.
+    #[serde(default, alias = "number_of_vacancies")]
+    pub vacancies: Option<u32>,
+    /// Years of experience the description asks for (see `extract_min_experience_years`).
+    /// None when it doesn't say, and on ads stored before this was extracted.
+    #[serde(default)]
+    pub min_experience_years: Option<u8>,
+    /// jobtech's free-text `salary_description`, e.g. "Fast månadslön 30 000–35 000 kr".
+    #[serde(default)]
+    pub salary_description: Option<String>,
+    /// Monthly salary range read from `salary_description` (see `extract_salary_range`).
+    #[serde(default)]
+    pub salary_min: Option<u32>,
+    #[serde(default)]
+    pub salary_max: Option<u32>,
+    /// "sv" or "en" from `detect_language` over headline and description; None when unclear.
+    #[serde(default)]
+    pub detected_language: Option<String>,
+}
+
+fn default_settings_version_v1() -> u32 { 1 }
+
+fn default_headline() -> String { "(ingen rubrik)".to_string() }
+
+/// Channels an application can be sent through, as shown in the detail view.
+pub const APPLIED_CHANNELS: &[&str] = &["Webb", "E-post", "Annat"];
+
+/// Maps the channel picker's value to what is stored; anything else means "not given".
+pub fn parse_applied_channel(input: &str) -> Option<String> {
+    APPLIED_CHANNELS.iter().find(|c| c.eq_ignore_ascii_case(input.trim())).map(|c| c.to_string())
+}
+
+/// Number words ads use for small year counts, 1–10.
+const SWEDISH_NUMBERS: &[(&str, u8)] = &[("en", 1), ("ett", 1), ("två", 2), ("tre", 3), ("fyra", 4), ("fem", 5), ("sex", 6), ("sju", 7), ("åtta", 8), ("nio", 9), ("tio", 10)];
+
+/// Years of experience a Swedish ad text asks for: "minst 3 års erfarenhet", "tre års
+/// erfarenhet", "minst 5 år inom IT", "2–4 års erfarenhet" (the lower bound counts). A year
+/// count only counts next to "minst"/"minimum"/"åtminstone" or followed by "erfarenhet" in
+/// the same sentence, so "företaget har funnits i 30 år" is ignored. With several
+/// requirements the highest wins. Heuristic; None when nothing matches.
+pub fn extract_min_experience_years(desc: &str) -> Option<u8> {
+    static RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    let re = RE.get_or_init(|| regex::Regex::new(r"(?i)(?:\b(minst|minimum|åtminstone)\s+)?\b(\d{1,2}|en|ett|två|tre|fyra|fem|sex|sju|åtta|nio|tio)\s*(?:\+|[-–]\s*\d{1,2})?\s*års?\b([^.\n]{0,40}erfarenhet)?").expect("Invalid regex"));
+    re.captures_iter(desc)
+        .filter(|c| c.get(1).is_some() || c.get(3).is_some())
+        .filter_map(|c| {
+            let n = c[2].to_lowercase();
+            n.parse::<u8>().ok().or_else(|| SWEDISH_NUMBERS.iter().find(|(w, _)| *w == n).map(|(_, v)| *v))
+        })
+        .max()
+}
+
+/// Short, frequent words that only one of the two languages uses. Words both share ("in",
+/// "is") would only blur the count.
+const SWEDISH_MARKERS: &[&str] = &["och", "att", "det", "som", "för", "med", "är", "vi", "du", "på", "av", "till", "har", "ska", "inom", "samt", "hos", "dig", "vår", "erfarenhet"];
+const ENGLISH_MARKERS: &[&str] = &["and", "the", "to", "of", "with", "you", "we", "are", "for", "our", "will", "be", "have", "your", "experience", "team"];
+
+/// "sv" or "en" for a Swedish or English ad text, by counting words only one of them uses.
+/// Needs a few markers and a clear majority: short or mixed texts give None.
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let (mut sv, mut en) = (0usize, 0usize);
+    for word in text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+        let word = word.to_lowercase();
+        if SWEDISH_MARKERS.contains(&word.as_str()) { sv += 1; }
+        if ENGLISH_MARKERS.contains(&word.as_str()) { en += 1; }
+    }
+    if sv + en < 4 { return None; }
+    if sv >= 2 * en.max(1) { Some("sv") } else if en >= 2 * sv.max(1) { Some("en") } else { None }
+}
+
+/// Lowest and highest figure taken as a monthly salary; outside this it is more likely an
+/// hourly wage, a year or a phone number.
+const MONTHLY_SALARY_RANGE: std::ops::RangeInclusive<u32> = 10_000..=200_000;
+
+/// Monthly salary range in kronor from a Swedish salary text: "35 000 kr" (35000–35000),
+/// "35000-40000", "30 000 – 35 000 kr/mån", "30-35 tkr", "från 28k till 32k". The first
+/// figure that looks like a monthly salary wins. Heuristic; None for "enligt
+/// överenskommelse", hourly wages and anything else without a plausible figure.
+pub fn extract_salary_range(text: &str) -> Option<(u32, u32)> {
+    static RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    let re = RE.get_or_init(|| regex::Regex::new(r"(?i)(\d{1,3}(?:[ \x{a0}.]\d{3})+|\d+)\s*(tkr|k\b)?(?:\s*(?:-|–|till)\s*(\d{1,3}(?:[ \x{a0}.]\d{3})+|\d+)\s*(tkr|k\b)?)?").expect("Invalid regex"));
+    let number = |s: &str| s.chars().filter(char::is_ascii_digit).collect::<String>().parse::<u32>().ok();
+    re.captures_iter(text).find_map(|c| {
+        // "30-35 tkr": a unit after the upper figure applies to both
+        let thousands = c.get(2).or(c.get(4)).is_some();
+        let scale = |n: u32| if thousands { n.saturating_mul(1000) } else { n };
+        let low = scale(number(&c[1])?);
+        let high = c.get(3).and_then(|m| number(m.as_str())).map(scale).unwrap_or(low);
+        let (low, high) = (low.min(high), low.max(high));
+        (MONTHLY_SALARY_RANGE.contains(&low) && MONTHLY_SALARY_RANGE.contains(&high)).then_some((low, high))
+    })
+}
+
+/// Parses a comma-separated tag input into lowercased, trimmed, de-duplicated tags.
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(',').map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty()) {
+        if !tags.contains(&tag) { tags.push(tag); }
+    }
+    tags
+}
+
+impl JobAd {
+    /// The direct application link when the ad has one, otherwise the ad page.
+    pub fn apply_link(&self) -> Option<&str> {
+        self.application_details.as_ref().and_then(|d| d.url.as_deref()).filter(|u| !u.is_empty())
+            .or(self.webpage_url.as_deref())
+    }
+
+    /// True if the headline or description contains any of the (lowercased) blacklisted words.
+    pub fn matches_blacklist_keywords(&self, words: &[String]) -> bool {
+        self.blacklist_keyword_hit(words).is_some()
+    }
+
+    /// The first blacklisted word found in the headline or description.
+    pub fn blacklist_keyword_hit<'a>(&self, words: &'a [String]) -> Option<&'a str> {
+        let headline = self.headline.to_lowercase();
+        let description = self.description.as_ref().and_then(|d| d.text.as_deref()).map(|t| t.to_lowercase()).unwrap_or_default();
+        words.iter().find(|word| headline.contains(word.as_str()) || description.contains(word.as_str())).map(|w| w.as_str())
+    }
+
+    /// True when the cached description is missing or ends in an ellipsis, i.e. the search
+    /// hit was cut short and the full text should be fetched with `get_ad`.
+    pub fn description_looks_truncated(&self) -> bool {
+        let text = self.description.as_ref().and_then(|d| d.text.as_deref()).unwrap_or("").trim_end();
+        text.is_empty() || text.ends_with("...") || text.ends_with('…')
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.trim().to_lowercase();
+        self.tags.iter().any(|t| *t == tag)
+    }
+
+    /// True once `last_application_date` is before `today`; the deadline day itself is still
+    /// open. Only the date part counts, so "2026-02-15" and "2026-02-15T23:59:59" mean the
+    /// same. Ads without a (readable) deadline never expire.
+    pub fn is_expired_on(&self, today: chrono::NaiveDate) -> bool {
+        self.last_application_date.as_deref()
+            .and_then(|d| chrono::NaiveDate::parse_from_str(d.get(..10)?, "%Y-%m-%d").ok())
+            .is_some_and(|deadline| deadline < today)
+    }
+
+    /// False when the ad was published before `since`. Only the date part counts, and ads
+    /// without a readable publication date are kept.
+    pub fn published_on_or_after(&self, since: chrono::NaiveDate) -> bool {
+        self.publication_date.get(..10)
+            .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+            .is_none_or(|published| published >= since)
+    }
+
+    /// False when the ad is known to hire fewer than `min` people. Ads without a vacancy
+    /// count are kept, like ads without a publication date in `published_on_or_after`.
+    pub fn has_min_vacancies(&self, min: u32) -> bool {
+        self.vacancies.is_none_or(|n| n >= min)
+    }
+
+    /// False when the ad asks for more than `max` years of experience; ads that don't say pass.
+    pub fn within_experience(&self, max: u32) -> bool {
+        self.min_experience_years.is_none_or(|y| u32::from(y) <= max)
+    }
+
+    /// Fills in `min_experience_years` from the description, e.g. after it changed.
+    pub fn update_experience_years(&mut self) {
+        self.min_experience_years = self.description.as_ref().and_then(|d| d.text.as_deref()).and_then(extract_min_experience_years);
+    }
+
+    /// Fills in `detected_language` from the headline and description.
+    pub fn update_language(&mut self) {
+        self.detected_language = detect_language(&self.language_text()).map(str::to_string);
+    }
+
+    /// The stored language, or for ads saved before it was detected, one detected now.
+    pub fn language(&self) -> Option<String> {
+        self.detected_language.clone().or_else(|| detect_language(&self.language_text()).map(str::to_string))
+    }
+
+    fn language_text(&self) -> String {
+        format!("{} {}", self.headline, self.description.as_ref().and_then(|d| d.text.as_deref()).unwrap_or(""))
+    }
+
+    /// Fills in `salary_min`/`salary_max` from `salary_description`.
+    pub fn update_salary_range(&mut self) {
+        let range = self.salary_description.as_deref().and_then(extract_salary_range);
+        (self.salary_min, self.salary_max) = (range.map(|r| r.0), range.map(|r| r.1));
+    }
+
+    /// True when the top of the salary range reaches `min`. Ads without a parsed salary pass
+    /// unless `require_known` is set.
+    pub fn meets_min_salary(&self, min: u32, require_known: bool) -> bool {
+        self.salary_max.map_or(!require_known, |max| max >= min)
+    }
+
+    /// False when the ad has an AI rating below `min`; unrated ads pass.
+    pub fn meets_min_rating(&self, min: u8) -> bool {
+        self.rating.is_none_or(|r| r >= min)
+    }
+
+    /// The one definition of "applied", used by filters, counters, stats and reports:
+    /// status Applied (even if the date is unknown), or an `applied_at` on an ad that isn't
+    /// Rejected, which is how older data and external tools record it. The app clears
+    /// `applied_at` when an ad leaves Applied, so normal use never produces the second case.
+    pub fn is_applied(&self) -> bool {
+        self.status == Some(AdStatus::Applied) || (self.applied_at.is_some() && self.status != Some(AdStatus::Rejected))
+    }
+
+    /// Ads the cleanup paths (archiving, month removal, blacklist purge, clearing the inbox)
+    /// never delete: anything the user acted on (saved, thumbs up, applied) or marked with a
+    /// star or a pin. Only New and Rejected ads without either can go.
+    pub fn is_protected(&self) -> bool {
+        !matches!(self.status.unwrap_or(AdStatus::New), AdStatus::New | AdStatus::Rejected) || self.starred || self.pinned
+    }
+
+    /// True if the employer name contains any of the (lowercased) blacklisted employers.
+    /// Partial matches count, so "manpower" hides both "Manpower" and "Manpower Group AB".
+    pub fn is_employer_blacklisted(&self, employers: &[String]) -> bool {
+        let name = match self.employer.as_ref().and_then(|e| e.name.as_deref()) {
+            Some(n) => n.to_lowercase(),
+            None => return false,
+        };
+        employers.iter().any(|e| !e.is_empty() && name.contains(e.as_str()))
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -136,10 +379,79 @@ pub struct WorkExperience {
     pub label: String,
 }
 
+/// The window the application goal (`app_min_count`/`app_goal_count`) is counted over.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GoalPeriod {
+    /// The month shown in the inbox.
+    #[default]
+    Month,
+    /// The current ISO week (Monday–Sunday).
+    Week,
+}
+
+/// Which date decides the month an ad is listed under.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MonthBasis {
+    /// When the user applied/bookmarked it, otherwise when it was fetched.
+    #[default]
+    Activity,
+    /// The ad's publication date, regardless of status.
+    Publication,
+}
+
+/// Colour theme of the window.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeMode {
+    #[default]
+    Dark,
+    Light,
+    /// Follows the operating system's light/dark setting where Slint can detect it,
+    /// otherwise dark.
+    System,
+}
+
+/// Rules that store a newly found ad as Rejected instead of New. Unlike the blacklist, which
+/// never saves the ad, an auto-rejected ad stays in the database and can be looked at again.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct AutoRejectRules {
+    /// Comma-separated phrases such as "5 års erfarenhet", matched in the headline and
+    /// description like blacklisted words.
+    #[serde(default)]
+    pub keywords: String,
+    /// Rejects ads whose last application date had already passed when they were found.
+    #[serde(default)]
+    pub expired_deadline: bool,
+}
+
+/// Why `AutoRejectRules::reason` rejected an ad.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AutoRejectReason {
+    Keyword(String),
+    ExpiredDeadline,
+}
+
+impl AutoRejectRules {
+    /// The first rule `ad` matches on `today`, keywords before the deadline.
+    pub fn reason(&self, ad: &JobAd, today: chrono::NaiveDate) -> Option<AutoRejectReason> {
+        let words = split_lowercase(&self.keywords);
+        if let Some(word) = ad.blacklist_keyword_hit(&words) { return Some(AutoRejectReason::Keyword(word.to_string())); }
+        (self.expired_deadline && ad.is_expired_on(today)).then_some(AutoRejectReason::ExpiredDeadline)
+    }
+}
+
+/// Shape version of stored `AppSettings`. Blobs without a `version` are version 1, the
+/// original field set (no sync_path default, no blacklist_employers etc.).
+pub const SETTINGS_VERSION: u32 = 2;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppSettings {
+    /// See `SETTINGS_VERSION`; always written as the current version.
+    #[serde(default = "default_settings_version_v1")]
+    pub version: u32,
     pub keywords: String,
     pub blacklist_keywords: String,
+    #[serde(default)]
+    pub blacklist_employers: String,
     pub locations_p1: String,
     pub locations_p2: String,
     pub locations_p3: String,
@@ -149,22 +461,604 @@ pub struct AppSettings {
     pub app_min_count: i32,
     pub app_goal_count: i32,
     pub show_motivation: bool,
+    #[serde(default)]
+    pub month_basis: MonthBasis,
+    #[serde(default = "default_ai_system_prompt")]
+    pub ai_system_prompt: String,
+    #[serde(default = "default_ai_scale_max")]
+    pub ai_scale_max: u8,
+    /// Most AI ratings running at once during batch rating.
+    #[serde(default = "default_ai_concurrency")]
+    pub ai_concurrency: u32,
+    /// New/rejected ads older than this many days are removed at startup (0 = never).
+    #[serde(default)]
+    pub auto_archive_days: u32,
+    #[serde(default)]
+    pub goal_period: GoalPeriod,
+    /// Most rows the inbox shows for a month, newest first (0 = no limit).
+    #[serde(default = "default_max_inbox_rows")]
+    pub max_inbox_rows: u32,
+    /// Proxy for all outgoing HTTP, e.g. "http://proxy.foretag.se:8080". None uses the
+    /// HTTP_PROXY/HTTPS_PROXY environment variables, if set.
+    #[serde(default)]
+    pub http_proxy: Option<String>,
+    /// Columns of the ad CSV export, e.g. "id,headline,rating" (see `export_csv::EXPORT_COLUMNS`).
+    #[serde(default = "default_export_columns")]
+    pub export_columns: String,
+    /// Occupation field (taxonomy concept id, see `api::OCCUPATION_FIELDS`) that all searches
+    /// are limited to. None = every field.
+    #[serde(default)]
+    pub occupation_field: Option<String>,
+    /// One line per applied ad when the month is copied to the clipboard; placeholders are
+    /// listed in `reports::APPLIED_LINE_PLACEHOLDERS`.
+    #[serde(default = "default_applied_line_template")]
+    pub applied_line_template: String,
+    /// Leaves applied ads out of the All view (no tag selected); counters still include them.
+    #[serde(default)]
+    pub hide_applied_in_all: bool,
+    /// Expand keywords with `synonyms` when searching and quick-filtering. Off by default so
+    /// exact searches stay exact.
+    #[serde(default)]
+    pub use_synonyms: bool,
+    /// One group per line, "sökord = synonym, synonym"; see `expand_keyword`.
+    #[serde(default = "default_synonyms")]
+    pub synonyms: String,
+    /// Leaves new ads whose last application date has passed out of the All view.
+    #[serde(default)]
+    pub hide_expired: bool,
+    #[serde(default)]
+    pub theme: ThemeMode,
+    /// Keeps only stdout and the in-app log, no log file. Read at startup.
+    #[serde(default)]
+    pub disable_file_log: bool,
+    /// "Minst N platser": the All view leaves out ads hiring fewer people (0 = off).
+    #[serde(default)]
+    pub min_vacancies: u32,
+    #[serde(default)]
+    pub auto_reject_rules: AutoRejectRules,
+    /// Lowest AI rating the inbox lists (0 = off). Unrated ads are always listed, so nothing
+    /// disappears before it has been rated.
+    #[serde(default)]
+    pub min_rating_filter: u8,
+    /// Zone keywords and blacklists for P1–P3. Empty means the zone uses the global
+    /// `keywords`/`blacklist_keywords`, which is also what settings from before this had.
+    #[serde(default)]
+    pub keywords_p1: String,
+    #[serde(default)]
+    pub keywords_p2: String,
+    #[serde(default)]
+    pub keywords_p3: String,
+    #[serde(default)]
+    pub blacklist_p1: String,
+    #[serde(default)]
+    pub blacklist_p2: String,
+    #[serde(default)]
+    pub blacklist_p3: String,
+    /// Hides ads in the "Alla" view that ask for more years of experience than this (0 = off).
+    #[serde(default)]
+    pub max_experience_years: u32,
+    /// Most tabs "Öppna sparade" opens at once (0 = no limit).
+    #[serde(default = "default_max_batch_open")]
+    pub max_batch_open: u32,
+    /// Shows debugging actions such as "Kopiera som JSON" in the ad view.
+    #[serde(default)]
+    pub developer_mode: bool,
+    /// Least time between two JobTech API calls, in ms (0 = none). A search makes one call
+    /// per keyword, synonym and municipality, one after the other, so this spaces all of them.
+    #[serde(default = "default_request_delay_ms")]
+    pub request_delay_ms: u32,
+    /// Hides ads in the "Alla" view whose salary tops out below this, in kr/month (0 = off).
+    #[serde(default)]
+    pub min_salary: u32,
+    /// With `min_salary`, also hides ads whose salary couldn't be read; otherwise they stay.
+    #[serde(default)]
+    pub hide_unknown_salary: bool,
+    /// Orders the inbox by highest salary instead of date; ads without one come last.
+    #[serde(default)]
+    pub sort_by_salary: bool,
+    /// Size cap of the employer logo cache in MB (0 = no limit); the least recently shown
+    /// logos are deleted first.
+    #[serde(default = "default_logo_cache_max_mb")]
+    pub logo_cache_max_mb: u32,
+    /// "Endast svenska"/"Endast engelska" in the "Alla" view. Ads whose language isn't
+    /// clear are always shown.
+    #[serde(default)]
+    pub language_filter: LanguageFilter,
+    /// New ads rated at least this high by "Betygsätt listan" are saved (Bookmarked); 0 = off.
+    #[serde(default)]
+    pub auto_bookmark_rating: u8,
+    /// Port of the read-only JSON API on 127.0.0.1 (see `local_api`); 0 = off. Read at startup.
+    #[serde(default)]
+    pub local_api_port: u16,
+    /// Searches only fetch ads published in the last N days, even on a full refresh; a
+    /// "Publicerad från" date replaces it for that search. 0 = no limit.
+    #[serde(default = "default_age_days")]
+    pub default_age_days: u32,
+    /// Shows "Öppna & markera sökt" in the ad view, which opens the application link and marks
+    /// the ad Applied in one go.
+    #[serde(default)]
+    pub open_marks_applied: bool,
+}
+
+/// Which ad language the "Alla" view keeps.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LanguageFilter {
+    #[default]
+    All,
+    Swedish,
+    English,
 }
 
+impl LanguageFilter {
+    /// True when an ad in `language` ("sv"/"en", None = unclear) stays visible.
+    pub fn allows(self, language: Option<&str>) -> bool {
+        match (self, language) {
+            (LanguageFilter::All, _) | (_, None) => true,
+            (LanguageFilter::Swedish, Some(l)) => l == "sv",
+            (LanguageFilter::English, Some(l)) => l == "en",
+        }
+    }
+}
+
+/// A small Swedish IT/service set to start from; users edit it in the settings.
+pub const DEFAULT_SYNONYMS: &str = "utvecklare = systemutvecklare, mjukvaruutvecklare, programmerare
+supporttekniker = it-tekniker, helpdesktekniker, it-supporttekniker
+helpdesk = servicedesk, it-support
+kundtjänst = kundservice, kundsupport, kundtjänstmedarbetare
+lager = lagerarbetare, lagermedarbetare, truckförare";
+
+/// The keyword followed by the synonyms its group in `synonyms` lists, lowercased and without
+/// duplicates. The group is found by its left-hand word only, so "programmerare" doesn't
+/// pull in "utvecklare". A keyword with no group expands to itself.
+pub fn expand_keyword(keyword: &str, synonyms: &str) -> Vec<String> {
+    let keyword = keyword.trim().to_lowercase();
+    let mut terms = vec![keyword.clone()];
+    for line in synonyms.lines() {
+        let Some((head, rest)) = line.split_once('=') else { continue };
+        if head.trim().to_lowercase() != keyword { continue; }
+        for term in split_lowercase(rest) {
+            if !terms.contains(&term) { terms.push(term); }
+        }
+    }
+    terms
+}
+
+fn default_ai_system_prompt() -> String { crate::ai::DEFAULT_SYSTEM_PROMPT.to_string() }
+fn default_ai_scale_max() -> u8 { crate::ai::DEFAULT_SCALE_MAX }
+fn default_ai_concurrency() -> u32 { crate::ai::DEFAULT_CONCURRENCY }
+fn default_max_inbox_rows() -> u32 { 500 }
+fn default_max_batch_open() -> u32 { 10 }
+fn default_request_delay_ms() -> u32 { 100 }
+fn default_age_days() -> u32 { 30 }
+fn default_logo_cache_max_mb() -> u32 { 20 }
+fn default_export_columns() -> String { crate::export_csv::DEFAULT_EXPORT_COLUMNS.to_string() }
+fn default_synonyms() -> String { DEFAULT_SYNONYMS.to_string() }
+fn default_applied_line_template() -> String { crate::reports::DEFAULT_APPLIED_LINE_TEMPLATE.to_string() }
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
-            keywords: "it, Supporttekniker, helpdesk, kundtjänst, kundsupport".to_string(),
+            version: SETTINGS_VERSION,
+            keywords: crate::presets::SKANE_IT.keywords.to_string(),
             blacklist_keywords: "barnvakt, körkort, barnflicka, nanny, myNanny, undersköterska, parkarbetare".to_string(),
-            locations_p1: "1283, 1277, 1260, 1292, 1284, 1276, 1231, 1282, 1261".to_string(),
-            locations_p2: "1280, 1281".to_string(),
-            locations_p3: "".to_string(),
+            blacklist_employers: "".to_string(),
+            locations_p1: crate::presets::SKANE_IT.locations_p1.to_string(),
+            locations_p2: crate::presets::SKANE_IT.locations_p2.to_string(),
+            locations_p3: crate::presets::SKANE_IT.locations_p3.to_string(),
             my_profile: "Jag är en serviceinriktad person med erfarenhet inom IT-support och kundservice.".to_string(),
             ollama_url: "http://localhost:11434/v1".to_string(),
             sync_path: "".to_string(),
             app_min_count: 6,
             app_goal_count: 12,
             show_motivation: true,
+            month_basis: MonthBasis::Activity,
+            ai_system_prompt: default_ai_system_prompt(),
+            ai_scale_max: default_ai_scale_max(),
+            ai_concurrency: default_ai_concurrency(),
+            auto_archive_days: 0,
+            goal_period: GoalPeriod::Month,
+            max_inbox_rows: default_max_inbox_rows(),
+            http_proxy: None,
+            export_columns: default_export_columns(),
+            occupation_field: None,
+            applied_line_template: default_applied_line_template(),
+            hide_applied_in_all: false,
+            use_synonyms: false,
+            synonyms: default_synonyms(),
+            hide_expired: false,
+            theme: ThemeMode::Dark,
+            disable_file_log: false,
+            min_vacancies: 0,
+            auto_reject_rules: AutoRejectRules::default(),
+            min_rating_filter: 0,
+            keywords_p1: String::new(),
+            keywords_p2: String::new(),
+            keywords_p3: String::new(),
+            blacklist_p1: String::new(),
+            blacklist_p2: String::new(),
+            blacklist_p3: String::new(),
+            max_experience_years: 0,
+            max_batch_open: default_max_batch_open(),
+            developer_mode: false,
+            request_delay_ms: default_request_delay_ms(),
+            min_salary: 0,
+            hide_unknown_salary: false,
+            sort_by_salary: false,
+            logo_cache_max_mb: default_logo_cache_max_mb(),
+            language_filter: LanguageFilter::All,
+            auto_bookmark_rating: 0,
+            local_api_port: 0,
+            default_age_days: default_age_days(),
+            open_marks_applied: false,
+        }
+    }
+}
+
+/// Format version of settings files written by `AppSettings::export_to`. Bump it when a
+/// change would make older apps misread the file.
+pub const SETTINGS_FILE_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct SettingsFile {
+    version: u32,
+    settings: AppSettings,
+}
+
+impl AppSettings {
+    /// Upgrades a stored settings blob of any earlier version to the current shape. Fields
+    /// the blob doesn't have get their `Default` value, so adding a field never resets the
+    /// user's other settings; renames are handled per version step below.
+    pub fn migrate(stored: serde_json::Value) -> Result<AppSettings, serde_json::Error> {
+        let version = stored.get("version").and_then(|v| v.as_u64()).unwrap_or(1) as u32;
+        let mut merged = serde_json::to_value(AppSettings::default())?;
+        if let (Some(target), serde_json::Value::Object(source)) = (merged.as_object_mut(), stored) {
+            for (key, value) in source {
+                if !value.is_null() { target.insert(key, value); }
+            }
+            // Version steps go here, e.g. `if version < 3 { rename "old" to "new" }`.
+            // v1 → v2 only added fields, which the merge above already covers.
+            if version < SETTINGS_VERSION { tracing::info!("Inställningar uppgraderade från version {} till {}", version, SETTINGS_VERSION); }
+            target.insert("version".to_string(), SETTINGS_VERSION.into());
+        }
+        serde_json::from_value(merged)
+    }
+
+    /// Writes the settings as versioned JSON, for copying the configuration to another
+    /// machine. `sync_path` is left out since it is specific to this machine.
+    pub fn export_to(&self, path: &std::path::Path) -> anyhow::Result<()> {
+        let file = SettingsFile { version: SETTINGS_FILE_VERSION, settings: AppSettings { sync_path: String::new(), ..self.clone() } };
+        crate::paths::write_atomic(path, serde_json::to_string_pretty(&file)?)?;
+        Ok(())
+    }
+
+    /// Reads a file written by `export_to`. The whole file is rejected if its version differs
+    /// from ours, so nothing is ever half-applied.
+    pub fn import_from(path: &std::path::Path) -> anyhow::Result<AppSettings> {
+        let text = std::fs::read_to_string(path)?;
+        let value: serde_json::Value = serde_json::from_str(&text).map_err(|e| anyhow::anyhow!("{} är ingen inställningsfil: {}", path.display(), e))?;
+        let version = value.get("version").and_then(|v| v.as_u64());
+        if version != Some(SETTINGS_FILE_VERSION as u64) {
+            anyhow::bail!("Inställningsfilen har version {}, den här versionen av Jobseeker läser bara version {}", version.map(|v| v.to_string()).unwrap_or_else(|| "saknas".to_string()), SETTINGS_FILE_VERSION);
+        }
+        Ok(AppSettings::migrate(value["settings"].clone())?)
+    }
+
+    /// Blacklisted keywords, trimmed and lowercased for case-insensitive matching.
+    pub fn blacklisted_keywords(&self) -> Vec<String> {
+        split_lowercase(&self.blacklist_keywords)
+    }
+
+    /// True when `other` would search differently: keywords, zones, locations, blacklists,
+    /// synonyms, occupation field or the age window changed. Only then are the watermarks
+    /// cleared on save, since older ads may match now; a new goal or theme keeps them.
+    pub fn search_scope_changed(&self, other: &AppSettings) -> bool {
+        let scope = |s: &AppSettings| (
+            [&s.keywords, &s.keywords_p1, &s.keywords_p2, &s.keywords_p3, &s.locations_p1, &s.locations_p2, &s.locations_p3],
+            [&s.blacklist_keywords, &s.blacklist_employers, &s.blacklist_p1, &s.blacklist_p2, &s.blacklist_p3, &s.synonyms],
+            (s.use_synonyms, s.occupation_field.clone(), s.default_age_days),
+        );
+        scope(self) != scope(other)
+    }
+
+    /// The zone's own (keywords, blacklist) for P1–P3, empty where not set.
+    fn zone_overrides(&self, prio: Option<i32>) -> (&str, &str) {
+        match prio {
+            Some(1) => (&self.keywords_p1, &self.blacklist_p1),
+            Some(2) => (&self.keywords_p2, &self.blacklist_p2),
+            Some(3) => (&self.keywords_p3, &self.blacklist_p3),
+            _ => ("", ""),
+        }
+    }
+
+    /// Keywords a search of zone `prio` uses: the zone's own when set, otherwise `keywords`.
+    /// All zones (0) and free search (None) always use the global list.
+    pub fn zone_keywords(&self, prio: Option<i32>) -> &str {
+        let (own, _) = self.zone_overrides(prio);
+        if own.trim().is_empty() { &self.keywords } else { own }
+    }
+
+    /// Like `blacklisted_keywords`, but for zone `prio`, with the same fallback as `zone_keywords`.
+    pub fn zone_blacklisted_keywords(&self, prio: Option<i32>) -> Vec<String> {
+        let (_, own) = self.zone_overrides(prio);
+        if own.trim().is_empty() { self.blacklisted_keywords() } else { split_lowercase(own) }
+    }
+
+    /// The terms one keyword is searched as: itself, plus its synonyms when `use_synonyms` is on.
+    pub fn keyword_terms(&self, keyword: &str) -> Vec<String> {
+        if self.use_synonyms { expand_keyword(keyword, &self.synonyms) } else { vec![keyword.trim().to_lowercase()] }
+    }
+
+    /// Blacklisted employer names, trimmed and lowercased for case-insensitive matching.
+    pub fn blacklisted_employers(&self) -> Vec<String> {
+        split_lowercase(&self.blacklist_employers)
+    }
+}
+
+fn split_lowercase(input: &str) -> Vec<String> {
+    input.split(',').map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ad_with_employer(name: &str) -> JobAd {
+        serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "headline": "Supporttekniker",
+            "publication_date": "2026-01-10T08:00:00",
+            "employer": { "name": name }
+        })).expect("valid test ad")
+    }
+
+    #[test]
+    fn deadline_day_is_still_open() {
+        let day = |d: u32| chrono::NaiveDate::from_ymd_opt(2026, 2, d).unwrap();
+        let mut ad = ad_with_employer("Testbolaget");
+        assert!(!ad.is_expired_on(day(20)));
+        for deadline in ["2026-02-15", "2026-02-15T23:59:59", "2026-02-15T00:00:00"] {
+            ad.last_application_date = Some(deadline.to_string());
+            assert!(!ad.is_expired_on(day(14)), "{}", deadline);
+            assert!(!ad.is_expired_on(day(15)), "{}", deadline);
+            assert!(ad.is_expired_on(day(16)), "{}", deadline);
         }
+        ad.last_application_date = Some("snarast".to_string());
+        assert!(!ad.is_expired_on(day(16)));
+    }
+
+    #[test]
+    fn publication_floor_includes_the_day_itself() {
+        let day = |d: u32| chrono::NaiveDate::from_ymd_opt(2026, 1, d).unwrap();
+        let mut ad = ad_with_employer("Testbolaget");
+        assert!(ad.published_on_or_after(day(9)));
+        assert!(ad.published_on_or_after(day(10)));
+        assert!(!ad.published_on_or_after(day(11)));
+        ad.publication_date = String::new();
+        assert!(ad.published_on_or_after(day(11)));
+    }
+
+    #[test]
+    fn vacancies_come_from_the_hit_and_unknown_counts_pass_the_filter() {
+        let ad: JobAd = serde_json::from_value(serde_json::json!({ "id": "v", "number_of_vacancies": 3 })).unwrap();
+        assert_eq!(ad.vacancies, Some(3));
+        assert!(ad.has_min_vacancies(3));
+        assert!(!ad.has_min_vacancies(4));
+        // Stored ads are written with the field's own name and read back the same
+        let stored: JobAd = serde_json::from_str(&serde_json::to_string(&ad).unwrap()).unwrap();
+        assert_eq!(stored.vacancies, Some(3));
+        let unknown = ad_with_employer("Testbolaget");
+        assert_eq!(unknown.vacancies, None);
+        assert!(unknown.has_min_vacancies(5));
+    }
+
+    #[test]
+    fn auto_reject_keyword_rule_matches_headline_and_description() {
+        let rules = AutoRejectRules { keywords: "5 års erfarenhet, Senior".to_string(), expired_deadline: false };
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let mut ad = ad_with_employer("Testbolaget");
+        assert_eq!(rules.reason(&ad, today), None);
+        ad.headline = "Senior utvecklare".to_string();
+        assert_eq!(rules.reason(&ad, today), Some(AutoRejectReason::Keyword("senior".to_string())));
+        ad.headline = "Utvecklare".to_string();
+        ad.description = Some(Description { text: Some("Krav: minst 5 års erfarenhet av Rust".to_string()) });
+        assert_eq!(rules.reason(&ad, today), Some(AutoRejectReason::Keyword("5 års erfarenhet".to_string())));
+    }
+
+    #[test]
+    fn auto_reject_deadline_rule_only_when_enabled() {
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let mut ad = ad_with_employer("Testbolaget");
+        ad.last_application_date = Some("2026-01-14".to_string());
+        assert_eq!(AutoRejectRules::default().reason(&ad, today), None);
+        let rules = AutoRejectRules { keywords: String::new(), expired_deadline: true };
+        assert_eq!(rules.reason(&ad, today), Some(AutoRejectReason::ExpiredDeadline));
+        // The deadline day itself is still open
+        ad.last_application_date = Some("2026-01-15".to_string());
+        assert_eq!(rules.reason(&ad, today), None);
+    }
+
+    #[test]
+    fn only_search_settings_change_the_search_scope() {
+        let saved = AppSettings { keywords: "support".to_string(), locations_p1: "Malmö".to_string(), ..AppSettings::default() };
+        let goal_and_theme = AppSettings { app_goal_count: 20, theme: ThemeMode::Light, ..saved.clone() };
+        assert!(!saved.search_scope_changed(&goal_and_theme));
+        assert!(saved.search_scope_changed(&AppSettings { keywords: "support, lager".to_string(), ..saved.clone() }));
+        assert!(saved.search_scope_changed(&AppSettings { locations_p1: "Lund".to_string(), ..saved.clone() }));
+        assert!(saved.search_scope_changed(&AppSettings { blacklist_p2: "natt".to_string(), ..saved.clone() }));
+    }
+
+    #[test]
+    fn zone_keywords_fall_back_to_the_global_lists() {
+        let mut s = AppSettings { keywords: "support".to_string(), blacklist_keywords: "Körkort".to_string(), ..AppSettings::default() };
+        s.keywords_p2 = "lager, truck".to_string();
+        s.blacklist_p2 = "Nattarbete".to_string();
+        assert_eq!(s.zone_keywords(Some(1)), "support");
+        assert_eq!(s.zone_keywords(Some(2)), "lager, truck");
+        assert_eq!(s.zone_keywords(Some(0)), "support");
+        assert_eq!(s.zone_keywords(None), "support");
+        assert_eq!(s.zone_blacklisted_keywords(Some(1)), vec!["körkort"]);
+        assert_eq!(s.zone_blacklisted_keywords(Some(2)), vec!["nattarbete"]);
+        // Older settings have no zone fields and keep searching the global lists
+        let old: AppSettings = AppSettings::migrate(serde_json::json!({ "keywords": "support", "version": 2 })).unwrap();
+        assert_eq!(old.zone_keywords(Some(3)), "support");
+    }
+
+    #[test]
+    fn rating_floor_keeps_unrated_ads() {
+        let mut ad = ad_with_employer("Testbolaget");
+        assert!(ad.meets_min_rating(8));
+        ad.rating = Some(7);
+        assert!(ad.meets_min_rating(7));
+        assert!(!ad.meets_min_rating(8));
+        assert!(ad.meets_min_rating(0));
+    }
+
+    #[test]
+    fn keywords_expand_only_when_enabled() {
+        assert_eq!(expand_keyword("Utvecklare", DEFAULT_SYNONYMS), vec!["utvecklare", "systemutvecklare", "mjukvaruutvecklare", "programmerare"]);
+        assert_eq!(expand_keyword("programmerare", DEFAULT_SYNONYMS), vec!["programmerare"]);
+        assert_eq!(expand_keyword("it", "it = it, data\nfelaktig rad"), vec!["it", "data"]);
+        let mut settings = AppSettings::default();
+        assert_eq!(settings.keyword_terms("utvecklare"), vec!["utvecklare"]);
+        settings.use_synonyms = true;
+        assert_eq!(settings.keyword_terms("utvecklare").len(), 4);
+    }
+
+    #[test]
+    fn applied_rule_covers_status_and_date_edge_cases() {
+        let mut ad = ad_with_employer("Testbolaget");
+        assert!(!ad.is_applied());
+        // Status Applied without a date (e.g. imported) still counts
+        ad.status = Some(AdStatus::Applied);
+        assert!(ad.is_applied());
+        // A date with status Bookmarked, as older tools left it, counts too
+        ad.status = Some(AdStatus::Bookmarked);
+        ad.applied_at = Some(Utc::now());
+        assert!(ad.is_applied());
+        // ...but not once the ad is rejected
+        ad.status = Some(AdStatus::Rejected);
+        assert!(!ad.is_applied());
+    }
+
+    #[test]
+    fn employer_blacklist_exact_match() {
+        let settings = AppSettings { blacklist_employers: "Manpower, Adecco".to_string(), ..Default::default() };
+        assert!(ad_with_employer("Adecco").is_employer_blacklisted(&settings.blacklisted_employers()));
+        assert!(!ad_with_employer("Region Skåne").is_employer_blacklisted(&settings.blacklisted_employers()));
+    }
+
+    #[test]
+    fn employer_blacklist_partial_match_is_case_insensitive() {
+        let settings = AppSettings { blacklist_employers: " manpower ,".to_string(), ..Default::default() };
+        assert!(ad_with_employer("Manpower Group Sverige AB").is_employer_blacklisted(&settings.blacklisted_employers()));
+    }
+
+    #[test]
+    fn employer_blacklist_ignores_ads_without_employer() {
+        let mut ad = ad_with_employer("Manpower");
+        ad.employer = None;
+        assert!(!ad.is_employer_blacklisted(&["manpower".to_string()]));
+    }
+
+    #[test]
+    fn apply_link_prefers_application_url() {
+        let mut ad = ad_with_employer("Region Skåne");
+        ad.webpage_url = Some("https://arbetsformedlingen.se/annons/1".to_string());
+        assert_eq!(ad.apply_link(), Some("https://arbetsformedlingen.se/annons/1"));
+        ad.application_details = Some(ApplicationDetails { url: Some("https://jobb.example.se/ansok".to_string()) });
+        assert_eq!(ad.apply_link(), Some("https://jobb.example.se/ansok"));
+    }
+
+    #[test]
+    fn truncated_description_is_detected() {
+        let mut ad = ad_with_employer("Region Skåne");
+        assert!(ad.description_looks_truncated());
+        ad.description = Some(Description { text: Some("Vi söker en supporttekniker som...".to_string()) });
+        assert!(ad.description_looks_truncated());
+        ad.description = Some(Description { text: Some("Vi söker en supporttekniker. Välkommen!".to_string()) });
+        assert!(!ad.description_looks_truncated());
+    }
+
+    /// The stored form in redb is the serde JSON of `JobAd` itself; everything the app sets
+    /// must come back unchanged.
+    #[test]
+    fn stored_json_round_trips_app_fields() {
+        let mut ad = ad_with_employer("Region Skåne");
+        ad.status = Some(AdStatus::Applied);
+        ad.applied_at = Some(Utc::now());
+        ad.rating = Some(7);
+        ad.search_keyword = Some("it".to_string());
+        ad.tags = vec!["distans".to_string()];
+
+        let stored = serde_json::to_string(&ad).expect("serialize");
+        let back: JobAd = serde_json::from_str(&stored).expect("deserialize");
+        assert_eq!(serde_json::to_value(&back).expect("value"), serde_json::to_value(&ad).expect("value"));
+    }
+
+    #[test]
+    fn tags_are_normalized_and_matched_case_insensitively() {
+        let mut ad = ad_with_employer("Region Skåne");
+        ad.tags = parse_tags(" Distans, hög lön,distans,, ");
+        assert_eq!(ad.tags, vec!["distans".to_string(), "hög lön".to_string()]);
+        assert!(ad.has_tag("Hög Lön"));
+        assert!(!ad.has_tag("chansning"));
+    }
+
+    #[test]
+    fn experience_years_are_read_from_swedish_phrasings() {
+        assert_eq!(extract_min_experience_years("Vi söker dig med minst 3 års erfarenhet av support."), Some(3));
+        assert_eq!(extract_min_experience_years("Du har tre års erfarenhet från liknande roll."), Some(3));
+        assert_eq!(extract_min_experience_years("Krav: minst 5 år inom IT-drift"), Some(5));
+        assert_eq!(extract_min_experience_years("Minst ett års erfarenhet"), Some(1));
+        assert_eq!(extract_min_experience_years("2–4 års erfarenhet av Java"), Some(2));
+        assert_eq!(extract_min_experience_years("5+ års erfarenhet av ledarskap, minst 2 år som tekniker"), Some(5));
+    }
+
+    #[test]
+    fn salary_ranges_are_read_from_swedish_phrasings() {
+        assert_eq!(extract_salary_range("35 000 kr"), Some((35_000, 35_000)));
+        assert_eq!(extract_salary_range("35000-40000"), Some((35_000, 40_000)));
+        assert_eq!(extract_salary_range("Fast månadslön 30 000 – 35 000 kr/mån"), Some((30_000, 35_000)));
+        assert_eq!(extract_salary_range("30-35 tkr"), Some((30_000, 35_000)));
+        assert_eq!(extract_salary_range("Från 28k till 32k beroende på erfarenhet"), Some((28_000, 32_000)));
+        assert_eq!(extract_salary_range("Ingångslön 27.500 kr"), Some((27_500, 27_500)));
+    }
+
+    #[test]
+    fn unparseable_salaries_are_none() {
+        assert_eq!(extract_salary_range("Lön enligt överenskommelse"), None);
+        assert_eq!(extract_salary_range("Timlön 150 kr"), None);
+        assert_eq!(extract_salary_range("Individuell lönesättning, tillträde 2026"), None);
+        assert_eq!(extract_salary_range(""), None);
+
+        let mut ad = ad_with_employer("Region Skåne");
+        ad.salary_description = Some("Fast lön".to_string());
+        ad.update_salary_range();
+        assert_eq!((ad.salary_min, ad.salary_max), (None, None));
+        assert!(ad.meets_min_salary(30_000, false));
+        assert!(!ad.meets_min_salary(30_000, true));
+    }
+
+    #[test]
+    fn language_is_detected_on_clear_swedish_and_english_text() {
+        assert_eq!(detect_language("Vi söker dig som har erfarenhet av support och som vill arbeta med kunder på plats hos oss."), Some("sv"));
+        assert_eq!(detect_language("We are looking for you to join our team and work with customers. You will have experience of support."), Some("en"));
+        assert_eq!(detect_language("Supporttekniker Helsingborg"), None);
+        assert_eq!(detect_language(""), None);
+        assert!(LanguageFilter::Swedish.allows(Some("sv")));
+        assert!(!LanguageFilter::Swedish.allows(Some("en")));
+        assert!(LanguageFilter::English.allows(None));
+    }
+
+    #[test]
+    fn unrelated_year_counts_are_not_experience() {
+        assert_eq!(extract_min_experience_years("Företaget har funnits i 30 år. Erfarenhet är meriterande."), None);
+        assert_eq!(extract_min_experience_years("Anställningen gäller ett år med årsredovisning i maj."), None);
+        assert_eq!(extract_min_experience_years("Erfarenhet av kundtjänst är meriterande"), None);
+        let mut ad = ad_with_employer("Region Skåne");
+        assert!(ad.within_experience(2));
+        ad.min_experience_years = Some(3);
+        assert!(!ad.within_experience(2));
+        assert!(ad.within_experience(3));
     }
 }
@@ -0,0 +1,18 @@
+//! Opening links in the browser or mail program. UI callbacks get an `Arc<dyn Opener>`
+//! instead of calling `webbrowser` themselves, so what they would open can be checked in tests.
+
+pub trait Opener: Send + Sync {
+    /// Opens `url` (https, mailto, a file path) with the system's default program.
+    fn open(&self, url: &str) -> anyhow::Result<()>;
+}
+
+/// The real opener, through `webbrowser`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemOpener;
+
+impl Opener for SystemOpener {
+    fn open(&self, url: &str) -> anyhow::Result<()> {
+        webbrowser::open(url)?;
+        Ok(())
+    }
+}
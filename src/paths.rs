@@ -0,0 +1,123 @@
+//! Where Jobseeker keeps its files. The GUI and the tools in `src/bin` must agree on these,
+//! otherwise they quietly end up reading different databases.
+
+use std::path::{Path, PathBuf};
+
+pub const QUALIFIER: &str = "com";
+pub const ORGANIZATION: &str = "GnawSoftware";
+pub const APPLICATION: &str = "Jobseeker";
+pub const DB_FILE_NAME: &str = "jobseeker.redb";
+
+#[cfg(target_os = "android")]
+const ANDROID_FILES_DIR: &str = "/data/data/com.gnawsoftware.jobseeker/files";
+
+pub fn project_dirs() -> Option<directories::ProjectDirs> {
+    directories::ProjectDirs::from(QUALIFIER, ORGANIZATION, APPLICATION)
+}
+
+/// Falls back to `fallback` in the working directory when there is no home/profile
+/// directory. That is loud on purpose: data written there "disappears" as soon as the app
+/// is started from somewhere else.
+fn or_cwd(dir: Option<PathBuf>, fallback: &str) -> PathBuf {
+    dir.unwrap_or_else(|| {
+        let path = PathBuf::from(fallback);
+        let cwd = std::env::current_dir().map(|d| d.display().to_string()).unwrap_or_default();
+        tracing::warn!("Hittade ingen användarkatalog – använder {:?} i arbetskatalogen {}", path, cwd);
+        eprintln!("VARNING: hittade ingen användarkatalog, använder {} i {}. Data hamnar där appen startas.", fallback, cwd);
+        path
+    })
+}
+
+/// The database file, creating its directory if needed.
+pub fn db_path() -> PathBuf {
+    #[cfg(target_os = "android")]
+    {
+        let path = PathBuf::from(ANDROID_FILES_DIR);
+        let _ = std::fs::create_dir_all(&path);
+        path.join(DB_FILE_NAME)
+    }
+    #[cfg(not(target_os = "android"))]
+    {
+        let dir = project_dirs().map(|p| p.data_dir().to_path_buf());
+        if let Some(d) = &dir { let _ = std::fs::create_dir_all(d); }
+        or_cwd(dir.map(|d| d.join(DB_FILE_NAME)), DB_FILE_NAME)
+    }
+}
+
+/// Where the old iced frontend kept its settings. Only read once, by
+/// `Db::import_legacy_settings`; None when there is no config directory (Android).
+pub fn legacy_settings_path() -> Option<PathBuf> {
+    project_dirs().map(|p| p.config_dir().join("settings.json"))
+}
+
+pub fn log_dir() -> PathBuf {
+    or_cwd(project_dirs().map(|p| p.data_dir().join("logs")), "logs")
+}
+
+pub fn cache_dir(name: &str) -> PathBuf {
+    or_cwd(project_dirs().map(|p| p.cache_dir().join(name)), name)
+}
+
+/// Copies the database at `db_path` to `jobseeker.redb.bak.<timestamp>` next to it, as the
+/// tools do before they change anything; `restore_backup` lists these copies.
+pub fn backup_db(db_path: &Path) -> std::io::Result<PathBuf> {
+    let backup = db_path.with_file_name(format!("{}.bak.{}", DB_FILE_NAME, chrono::Local::now().format("%Y%m%d_%H%M%S")));
+    std::fs::copy(db_path, &backup)?;
+    Ok(backup)
+}
+
+/// Where `write_atomic` stages the new content: a hidden file next to `path`, so the
+/// rename stays on the same filesystem.
+fn staging_path(path: &Path) -> PathBuf {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    path.with_file_name(format!(".{}.tmp", name))
+}
+
+/// Replaces `path` with `content` so that a crash leaves either the old or the new file,
+/// never a half-written one: the content goes to a staging file that is renamed over `path`.
+pub fn write_atomic(path: &Path, content: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let staging = staging_path(path);
+    {
+        let mut file = std::fs::File::create(&staging)?;
+        std::io::Write::write_all(&mut file, content.as_ref())?;
+        file.sync_all()?;
+    }
+    std::fs::rename(&staging, path)
+}
+
+/// Fails unless a file can be created in the directory `path` would be written to, so an
+/// export can give up before building a large document. Probes with a throwaway file, since
+/// permission bits alone don't tell (read-only mounts, ACLs, Windows).
+pub fn ensure_writable_dir(path: &Path) -> std::io::Result<()> {
+    let dir = match path.parent() { Some(d) if !d.as_os_str().is_empty() => d, _ => Path::new(".") };
+    if !dir.is_dir() {
+        return Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("mappen {} finns inte", dir.display())));
+    }
+    let probe = dir.join(format!(".jobseeker-skrivtest-{}", std::process::id()));
+    std::fs::File::create(&probe)?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interrupted_write_leaves_previous_file_intact() {
+        let dir = std::env::temp_dir().join(format!("jobseeker_atomic_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("settings.json");
+        write_atomic(&path, r#"{"version":1}"#).unwrap();
+
+        // A crash mid-write leaves only a truncated staging file behind
+        std::fs::write(staging_path(&path), r#"{"vers"#).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), r#"{"version":1}"#);
+
+        // The next write replaces both the leftover and the file
+        write_atomic(&path, r#"{"version":2}"#).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), r#"{"version":2}"#);
+        assert!(!staging_path(&path).exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::models::AppSettings;
+
+/// A named starting point for keywords and the three priority zones. Locations use the
+/// numeric municipality codes the API requires (see the municipality table in `api.rs`).
+#[derive(Debug, Clone, Copy)]
+pub struct ZonePreset {
+    pub name: &'static str,
+    pub keywords: &'static str,
+    pub locations_p1: &'static str,
+    pub locations_p2: &'static str,
+    pub locations_p3: &'static str,
+}
+
+/// Nordvästra Skåne, the original defaults. `AppSettings::default` is built from this one.
+pub const SKANE_IT: ZonePreset = ZonePreset {
+    name: "Skåne IT",
+    keywords: "it, Supporttekniker, helpdesk, kundtjänst, kundsupport",
+    locations_p1: "1283, 1277, 1260, 1292, 1284, 1276, 1231, 1282, 1261",
+    locations_p2: "1280, 1281",
+    locations_p3: "",
+};
+
+pub const PRESETS: &[ZonePreset] = &[
+    SKANE_IT,
+    ZonePreset {
+        name: "Stockholm IT",
+        keywords: "it, Supporttekniker, helpdesk, systemadministratör, utvecklare",
+        locations_p1: "0180, 0184, 0115, 0182",
+        locations_p2: "0126, 0160, 0163, 0123, 0186",
+        locations_p3: "0380, 0181",
+    },
+    ZonePreset {
+        name: "Göteborg vård",
+        keywords: "undersköterska, sjuksköterska, vårdbiträde, personlig assistent",
+        locations_p1: "1480, 1481",
+        locations_p2: "1402, 1401, 1482, 1441",
+        locations_p3: "1489, 1490, 1384",
+    },
+    ZonePreset {
+        // Inga kommuner = hela landet
+        name: "Hela Sverige distans",
+        keywords: "distans, remote, på distans",
+        locations_p1: "",
+        locations_p2: "",
+        locations_p3: "",
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static ZonePreset> {
+    PRESETS.iter().find(|p| p.name == name)
+}
+
+impl ZonePreset {
+    /// Overwrites keywords and P1–P3; every other setting is left as it is.
+    pub fn apply_to(&self, settings: &mut AppSettings) {
+        settings.keywords = self.keywords.to_string();
+        settings.locations_p1 = self.locations_p1.to_string();
+        settings.locations_p2 = self.locations_p2.to_string();
+        settings.locations_p3 = self.locations_p3.to_string();
+    }
+}
@@ -0,0 +1,449 @@
+use crate::db::{Db, JobFilter};
+use crate::export_csv::csv_line;
+use crate::models::{AdStatus, AppSettings, JobAd};
+use anyhow::{Context, Result};
+use chrono::Datelike;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// A report for the caseworker (handläggare), built once and rendered to text, DOCX or PDF
+/// so that every output format contains exactly the same sections.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub title: String,
+    pub sections: Vec<ReportSection>,
+    pub footer: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReportSection {
+    pub heading: String,
+    pub lines: Vec<String>,
+}
+
+/// Which parts of the activity report to include (mirrors the checkboxes on the stats page).
+#[derive(Debug, Clone)]
+pub struct ReportOptions {
+    pub include_jobs: bool,
+    pub include_params: bool,
+    pub include_analysis: bool,
+    /// Ads whose drafts go into the ANSÖKNINGSBREV section, in this order and whatever
+    /// month they belong to. None includes the drafts of the month's applied jobs.
+    pub selected_drafts: Option<Vec<String>>,
+}
+
+/// Output formats for `Report::write_to`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Text,
+    Docx,
+    Pdf,
+}
+
+impl ReportFormat {
+    /// Parses the format string sent from the UI ("pdf", "docx", otherwise plain text).
+    pub fn from_ui(format: &str) -> Self {
+        match format {
+            "pdf" => ReportFormat::Pdf,
+            "docx" => ReportFormat::Docx,
+            _ => ReportFormat::Text,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Text => "txt",
+            ReportFormat::Docx => "docx",
+            ReportFormat::Pdf => "pdf",
+        }
+    }
+}
+
+/// Builds the monthly activity report: search parameters, applied jobs, the application
+/// letters (see `ReportOptions::selected_drafts`) and a short analysis.
+pub async fn activity_report(db: &Db, settings: &AppSettings, year: i32, month: u32, month_display: &str, opts: ReportOptions) -> Report {
+    let mut sections = Vec::new();
+
+    if opts.include_params {
+        sections.push(ReportSection {
+            heading: "SÖKPARAMETRAR".to_string(),
+            lines: vec![
+                format!("• Sökord: {}", settings.keywords),
+                format!("• Prio 1: {}", crate::normalize_locations(&settings.locations_p1)),
+                format!("• Prio 2: {}", crate::normalize_locations(&settings.locations_p2)),
+            ],
+        });
+    }
+
+    if opts.include_jobs {
+        if let Ok(mut ads) = db.get_filtered_jobs(JobFilter { statuses: &[AdStatus::Applied], year: Some(year), month: Some(month), month_basis: settings.month_basis, ..Default::default() }).await {
+            crate::export_csv::canonical_rows_for_export(&mut ads);
+            let mut lines = Vec::new();
+            let mut letters = Vec::new();
+            for ad in ads {
+                let date = ad.applied_at.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "Okänt datum".to_string());
+                let employer = ad.employer.as_ref().and_then(|e| e.name.clone()).unwrap_or_default();
+                let city = ad.workplace_address.as_ref().and_then(|a| a.city.clone()).unwrap_or_default();
+                let via = ad.applied_channel.as_ref().map(|c| format!(" – via {}", c)).unwrap_or_default();
+                let star = if ad.starred { " ★" } else { "" };
+                lines.push(format!("• {}: {}, {} ({}){}{}", date, employer, ad.headline, city, via, star));
+                let details: Vec<String> = [
+                    ("Yrke", ad.occupation.as_ref().and_then(|o| o.label.clone())),
+                    ("Omfattning", ad.working_hours_type.as_ref().and_then(|w| w.label.clone())),
+                    ("Kommun", ad.workplace_address.as_ref().and_then(|a| a.municipality.clone())),
+                ].into_iter().filter_map(|(name, value)| value.filter(|v| !v.is_empty()).map(|v| format!("{}: {}", name, v))).collect();
+                if !details.is_empty() { lines.push(format!("  {}", details.join(" · "))); }
+                if let Some(url) = &ad.webpage_url { lines.push(format!("  Länk: {}", url)); }
+
+                if opts.selected_drafts.is_some() { continue; }
+                if let Ok(Some(draft)) = db.get_application_draft(&ad.id).await {
+                    if !draft.trim().is_empty() {
+                        letters.push(format!("{} – {}", ad.headline, employer));
+                        letters.extend(draft.lines().map(|l| l.to_string()));
+                        letters.push(String::new());
+                    }
+                }
+            }
+            sections.push(ReportSection { heading: format!("SÖKTA JOBB ({} st)", lines.iter().filter(|l| l.starts_with('•')).count()), lines });
+            if !letters.is_empty() {
+                sections.push(ReportSection { heading: "ANSÖKNINGSBREV".to_string(), lines: letters });
+            }
+        }
+    }
+
+    if let Some(ids) = &opts.selected_drafts {
+        let mut letters = Vec::new();
+        for id in ids {
+            let Ok(Some(draft)) = db.get_application_draft(id).await else { continue };
+            if draft.trim().is_empty() { continue; }
+            let ad = db.get_job_ad(id).await.ok().flatten();
+            let headline = ad.as_ref().map(|a| a.headline.clone()).unwrap_or_else(|| id.clone());
+            let employer = ad.as_ref().and_then(|a| a.employer.as_ref()).and_then(|e| e.name.clone()).unwrap_or_default();
+            letters.push(format!("{} – {}", headline, employer));
+            letters.extend(draft.lines().map(|l| l.to_string()));
+            letters.push(String::new());
+        }
+        if !letters.is_empty() {
+            sections.push(ReportSection { heading: "ANSÖKNINGSBREV".to_string(), lines: letters });
+        }
+    }
+
+    if opts.include_analysis {
+        if let Ok(ads) = db.get_filtered_jobs(JobFilter { year: Some(year), month: Some(month), month_basis: settings.month_basis, ..Default::default() }).await {
+            let app = ads.iter().filter(|a| a.is_applied()).count();
+            // Rejected ads are hidden from the default listing, so they need their own query.
+            let rej = db.get_filtered_jobs(JobFilter { statuses: &[AdStatus::Rejected], year: Some(year), month: Some(month), month_basis: settings.month_basis, ..Default::default() }).await.map(|r| r.len()).unwrap_or(0);
+            sections.push(ReportSection {
+                heading: "AKTIVITETSANALYS".to_string(),
+                lines: vec![
+                    format!("• Totalt granskade: {}", ads.len() + rej),
+                    format!("• Konvertering: {} sökta, {} avvisade", app, rej),
+                ],
+            });
+        }
+    }
+
+    Report {
+        title: format!("AKTIVITETSRAPPORT - {}", month_display.to_uppercase()),
+        sections,
+        footer: "Genererad via Jobseeker 2026".to_string(),
+    }
+}
+
+/// Yearly application statistics for review meetings with the caseworker: applications
+/// per month, the employers applied to most and the year's total. Ads without `applied_at`
+/// can't be placed in a year and are left out.
+pub fn stats_report(ads: &[JobAd], year: i32) -> Report {
+    let mut per_month = [0usize; 12];
+    let mut employers: BTreeMap<String, usize> = BTreeMap::new();
+    for ad in ads.iter().filter(|a| a.is_applied()) {
+        let Some(at) = ad.applied_at.map(|d| d.with_timezone(&chrono::Local)) else { continue };
+        if at.year() != year { continue; }
+        per_month[at.month0() as usize] += 1;
+        let employer = ad.employer.as_ref().and_then(|e| e.name.clone()).unwrap_or_else(|| "Okänd".to_string());
+        *employers.entry(employer).or_insert(0) += 1;
+    }
+    let total: usize = per_month.iter().sum();
+
+    let months = per_month.iter().enumerate()
+        .map(|(i, n)| format!("• {}: {}", crate::swedish_month_name(i as u32 + 1), n))
+        .collect();
+    let mut top: Vec<(String, usize)> = employers.into_iter().collect();
+    // Most applications first; the map already sorted equal counts by name
+    top.sort_by(|a, b| b.1.cmp(&a.1));
+    let top_lines = if top.is_empty() { vec!["• Inga".to_string()] }
+        else { top.into_iter().take(10).map(|(name, n)| format!("• {}: {}", name, n)).collect() };
+
+    Report {
+        title: format!("ANSÖKNINGSSTATISTIK {}", year),
+        sections: vec![
+            ReportSection { heading: "ANSÖKNINGAR PER MÅNAD".to_string(), lines: months },
+            ReportSection { heading: "FLEST ANSÖKNINGAR TILL".to_string(), lines: top_lines },
+            ReportSection { heading: "TOTALT".to_string(), lines: vec![format!("• {} ansökningar under {}", total, year)] },
+        ],
+        footer: "Genererad via Jobseeker 2026".to_string(),
+    }
+}
+
+/// Builds `stats_report` from every applied ad in the database and writes it as DOCX.
+pub async fn generate_stats_report(db: &Db, year: i32, output: &Path) -> Result<()> {
+    crate::paths::ensure_writable_dir(output).with_context(|| format!("Kan inte spara till {:?}", output))?;
+    let ads = db.get_filtered_jobs(JobFilter { statuses: &[AdStatus::Applied], ..Default::default() }).await?;
+    stats_report(&ads, year).write_docx(output)
+}
+
+/// Line format used when `AppSettings::applied_line_template` is empty.
+pub const DEFAULT_APPLIED_LINE_TEMPLATE: &str = "{date} — {headline} — {employer} — {link}";
+/// Placeholders `applied_lines` replaces in the template.
+pub const APPLIED_LINE_PLACEHOLDERS: &[&str] = &["{date}", "{headline}", "{employer}", "{municipality}", "{link}", "{channel}"];
+
+/// The applied ads as one template line each, in canonical export order, for pasting into
+/// a weekly report email. Missing values become empty strings.
+pub fn applied_lines(ads: &[JobAd], template: &str) -> String {
+    let mut ads: Vec<JobAd> = ads.iter().filter(|a| a.is_applied()).cloned().collect();
+    crate::export_csv::canonical_rows_for_export(&mut ads);
+    ads.iter().map(|ad| {
+        let municipality = ad.workplace_address.as_ref().and_then(|a| a.municipality.clone().or_else(|| a.city.clone())).unwrap_or_default();
+        template
+            .replace("{date}", &ad.applied_at.map(|d| d.with_timezone(&chrono::Local).format("%Y-%m-%d").to_string()).unwrap_or_default())
+            .replace("{headline}", &ad.headline)
+            .replace("{employer}", ad.employer.as_ref().and_then(|e| e.name.as_deref()).unwrap_or_default())
+            .replace("{municipality}", &municipality)
+            .replace("{link}", &ad.webpage_url.as_deref().map(clean_url).unwrap_or_default())
+            .replace("{channel}", ad.applied_channel.as_deref().unwrap_or_default())
+    }).collect::<Vec<_>>().join("\n")
+}
+
+/// A short summary for sending an ad to someone: headline, employer, location, last
+/// application date and link, one per line. Parts the ad lacks are left out.
+pub fn ad_share_text(ad: &JobAd) -> String {
+    let employer = ad.employer.as_ref().and_then(|e| e.name.clone());
+    let location = ad.workplace_address.as_ref().and_then(|a| a.municipality.clone().or_else(|| a.city.clone()));
+    let deadline = ad.last_application_date.as_deref().map(|d| d.get(..10).unwrap_or(d).to_string());
+    // The ad page shows the whole ad; the application link is only the fallback
+    let link = ad.webpage_url.as_deref().filter(|u| !u.is_empty()).or(ad.apply_link());
+    [
+        Some(ad.headline.clone()),
+        employer.map(|e| format!("Arbetsgivare: {}", e)),
+        location.map(|l| format!("Ort: {}", l)),
+        deadline.map(|d| format!("Sista ansökningsdag: {}", d)),
+        link.map(clean_url),
+    ].into_iter().flatten().collect::<Vec<_>>().join("\n")
+}
+
+/// The ad's structured requirements as a Markdown checklist (`- [ ] Truckkort`) under the
+/// headline, "Krav" before "Meriterande", for ticking off in notes or cover-letter prep.
+/// None when the ad lists no requirements at all.
+pub fn requirements_checklist(ad: &JobAd) -> Option<String> {
+    let labels = |r: &Option<crate::models::Requirements>| -> Vec<String> {
+        r.iter().flat_map(|r| {
+            r.skills.iter().map(|s| s.label.clone())
+                .chain(r.languages.iter().map(|l| l.label.clone()))
+                .chain(r.work_experiences.iter().map(|w| w.label.clone()))
+        }).filter(|l| !l.trim().is_empty()).collect()
+    };
+    let mut must = labels(&ad.must_have);
+    if ad.driving_license_required { must.push("Körkort".to_string()); }
+    let nice = labels(&ad.nice_to_have);
+    if must.is_empty() && nice.is_empty() { return None; }
+    let mut out = format!("# {}\n", ad.headline);
+    for (title, items) in [("Krav", must), ("Meriterande", nice)] {
+        if items.is_empty() { continue; }
+        out.push_str(&format!("\n## {}\n", title));
+        for item in items { out.push_str(&format!("- [ ] {}\n", item.trim())); }
+    }
+    Some(out)
+}
+
+/// Query parameters that only track where a click came from; `clean_url` drops these and
+/// every `utm_*` parameter.
+const TRACKING_PARAMS: &[&str] = &["gclid", "fbclid", "msclkid", "dclid", "mc_cid", "mc_eid", "igshid", "_hsenc", "_hsmi"];
+
+/// `url` without tracking parameters, for copying, sharing and opening; stored URLs are
+/// left as they came. Other parameters and the fragment are kept in their original order
+/// and spelling, and a query left empty is removed along with its `?`.
+pub fn clean_url(url: &str) -> String {
+    let (rest, fragment) = match url.split_once('#') { Some((r, f)) => (r, Some(f)), None => (url, None) };
+    let Some((base, query)) = rest.split_once('?') else { return url.to_string() };
+    let kept: Vec<&str> = query.split('&')
+        .filter(|pair| {
+            let key = pair.split('=').next().unwrap_or("").to_ascii_lowercase();
+            !pair.is_empty() && !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_str())
+        })
+        .collect();
+    let mut cleaned = base.to_string();
+    if !kept.is_empty() { cleaned.push('?'); cleaned.push_str(&kept.join("&")); }
+    if let Some(f) = fragment { cleaned.push('#'); cleaned.push_str(f); }
+    cleaned
+}
+
+/// A `mailto:` link without recipient, with subject and body percent-encoded.
+pub fn mailto_link(subject: &str, body: &str) -> String {
+    format!("mailto:?subject={}&body={}", urlencoding::encode(subject), urlencoding::encode(body))
+}
+
+/// HTML for pasting an application draft into web forms that accept rich text. Blank lines
+/// separate paragraphs, single newlines become `<br>`, and `**fet**`/`*kursiv*` are the
+/// only markup recognised; everything else is escaped and passed through as text.
+pub fn draft_to_html(draft: &str) -> String {
+    let bold = regex::Regex::new(r"\*\*(.+?)\*\*").expect("Invalid regex");
+    let italic = regex::Regex::new(r"\*(.+?)\*").expect("Invalid regex");
+    let inline = |line: &str| {
+        let escaped = line.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;");
+        italic.replace_all(&bold.replace_all(&escaped, "<strong>$1</strong>"), "<em>$1</em>").into_owned()
+    };
+    draft.replace("\r\n", "\n")
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| format!("<p>{}</p>", p.lines().map(|l| inline(l.trim_end())).collect::<Vec<_>>().join("<br>")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Application counts per ISO week, municipality and channel as CSV (`typ;nyckel;antal`), for
+/// showing the unemployment office how many applications were sent each week.
+/// Ads without `applied_at` are skipped since they can't be placed in a week.
+pub fn application_summary_csv(ads: &[JobAd]) -> String {
+    let mut weeks: BTreeMap<String, usize> = BTreeMap::new();
+    let mut municipalities: BTreeMap<String, usize> = BTreeMap::new();
+    let mut channels: BTreeMap<String, usize> = BTreeMap::new();
+    let mut starred = 0;
+    for ad in ads.iter().filter(|a| a.is_applied()) {
+        let Some(applied_at) = ad.applied_at else { continue };
+        let week = applied_at.with_timezone(&chrono::Local).iso_week();
+        *weeks.entry(format!("{}-V{:02}", week.year(), week.week())).or_insert(0) += 1;
+        let municipality = ad.workplace_address.as_ref()
+            .and_then(|a| a.municipality.clone().or_else(|| a.city.clone()))
+            .unwrap_or_else(|| "Okänd".to_string());
+        *municipalities.entry(municipality).or_insert(0) += 1;
+        *channels.entry(ad.applied_channel.clone().unwrap_or_else(|| "Ej angiven".to_string())).or_insert(0) += 1;
+        if ad.starred { starred += 1; }
+    }
+
+    let mut out = String::new();
+    let mut push = |fields: &[&str]| { out.push_str(&csv_line(fields, ';')); out.push('\n'); };
+    push(&["typ", "nyckel", "antal"]);
+    for (week, count) in weeks { push(&["vecka", &week, &count.to_string()]); }
+    for (municipality, count) in municipalities { push(&["kommun", &municipality, &count.to_string()]); }
+    for (channel, count) in channels { push(&["kanal", &channel, &count.to_string()]); }
+    push(&["stjärnmärkta", "sökta", &starred.to_string()]);
+    out
+}
+
+/// Writes `content` unless the file already holds exactly that, so repeated exports don't
+/// produce new files or touch timestamps. Returns whether anything was written.
+pub fn write_if_changed(path: &Path, content: &str) -> Result<bool> {
+    if std::fs::read_to_string(path).map(|old| old == content).unwrap_or(false) {
+        return Ok(false);
+    }
+    crate::paths::write_atomic(path, content).with_context(|| format!("Kunde inte skriva {:?}", path))?;
+    Ok(true)
+}
+
+impl Report {
+    /// Plain-text rendering used for clipboard, e-mail and .txt files.
+    pub fn to_text(&self) -> String {
+        let mut out = format!("{}\n==========================================\n\n", self.title);
+        for section in &self.sections {
+            out.push_str(&format!("{}:\n", section.heading));
+            for line in &section.lines {
+                out.push_str(line);
+                out.push('\n');
+            }
+            out.push('\n');
+        }
+        out.push_str(&format!("\n{}\n", self.footer));
+        out
+    }
+
+    /// Writes the report in the given format to `path`.
+    pub fn write_to(&self, path: &Path, format: ReportFormat) -> Result<()> {
+        match format {
+            ReportFormat::Text => std::fs::write(path, self.to_text()).with_context(|| format!("Kunde inte skriva {:?}", path)),
+            ReportFormat::Docx => self.write_docx(path),
+            ReportFormat::Pdf => self.write_pdf(path),
+        }
+    }
+
+    /// DOCX and PDF check that the target directory is writable before the document is built.
+    pub fn write_docx(&self, path: &Path) -> Result<()> {
+        use docx_rs::{Docx, Paragraph, Run};
+        crate::paths::ensure_writable_dir(path).with_context(|| format!("Kan inte spara till {:?}", path))?;
+
+        let mut docx = Docx::new()
+            .add_paragraph(Paragraph::new().add_run(Run::new().add_text(&self.title).bold().size(32)));
+        for section in &self.sections {
+            docx = docx.add_paragraph(Paragraph::new().add_run(Run::new().add_text(&section.heading).bold().size(24)));
+            for line in &section.lines {
+                docx = docx.add_paragraph(Paragraph::new().add_run(Run::new().add_text(line)));
+            }
+        }
+        docx = docx.add_paragraph(Paragraph::new().add_run(Run::new().add_text(&self.footer).italic().size(18)));
+
+        let file = std::fs::File::create(path).with_context(|| format!("Kunde inte skapa {:?}", path))?;
+        docx.build().pack(file).context("Kunde inte skriva DOCX")?;
+        Ok(())
+    }
+
+    pub fn write_pdf(&self, path: &Path) -> Result<()> {
+        use genpdf::elements::{Break, Paragraph};
+        use genpdf::style::{Style, StyledString};
+        crate::paths::ensure_writable_dir(path).with_context(|| format!("Kan inte spara till {:?}", path))?;
+
+        let mut doc = genpdf::Document::new(load_pdf_font()?);
+        doc.set_title(&self.title);
+        let mut decorator = genpdf::SimplePageDecorator::new();
+        decorator.set_margins(15);
+        doc.set_page_decorator(decorator);
+
+        doc.push(Paragraph::new(StyledString::new(self.title.clone(), Style::new().bold().with_font_size(16))));
+        doc.push(Break::new(1));
+        for section in &self.sections {
+            doc.push(Paragraph::new(StyledString::new(section.heading.clone(), Style::new().bold().with_font_size(12))));
+            for line in &section.lines {
+                doc.push(Paragraph::new(line.clone()));
+            }
+            doc.push(Break::new(1));
+        }
+        doc.push(Paragraph::new(StyledString::new(self.footer.clone(), Style::new().italic().with_font_size(9))));
+
+        doc.render_to_file(path).with_context(|| format!("Kunde inte skriva PDF {:?}", path))?;
+        Ok(())
+    }
+}
+
+/// Whether PDF export will find a font; without one `write_pdf` fails with a message that
+/// names `JOBSEEKER_PDF_FONT`.
+pub fn pdf_font_available() -> bool {
+    load_pdf_font().is_ok()
+}
+
+/// genpdf needs real font files for metrics, even for the built-in PDF fonts. We look in
+/// `JOBSEEKER_PDF_FONT` first and then in the usual system locations.
+fn load_pdf_font() -> Result<genpdf::fonts::FontFamily<genpdf::fonts::FontData>> {
+    const CANDIDATES: &[(&str, &str)] = &[
+        ("/usr/share/fonts/truetype/liberation/LiberationSans-Regular.ttf", "/usr/share/fonts/truetype/liberation/LiberationSans-Bold.ttf"),
+        ("/usr/share/fonts/liberation-sans/LiberationSans-Regular.ttf", "/usr/share/fonts/liberation-sans/LiberationSans-Bold.ttf"),
+        ("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf", "/usr/share/fonts/truetype/dejavu/DejaVuSans-Bold.ttf"),
+        ("/usr/share/fonts/dejavu-sans-fonts/DejaVuSans.ttf", "/usr/share/fonts/dejavu-sans-fonts/DejaVuSans-Bold.ttf"),
+        ("C:\\Windows\\Fonts\\arial.ttf", "C:\\Windows\\Fonts\\arialbd.ttf"),
+        ("/System/Library/Fonts/Supplemental/Arial.ttf", "/System/Library/Fonts/Supplemental/Arial Bold.ttf"),
+    ];
+
+    let mut candidates: Vec<(String, String)> = Vec::new();
+    if let Ok(custom) = std::env::var("JOBSEEKER_PDF_FONT") {
+        candidates.push((custom.clone(), custom));
+    }
+    candidates.extend(CANDIDATES.iter().map(|(r, b)| (r.to_string(), b.to_string())));
+
+    for (regular_path, bold_path) in candidates {
+        if !Path::new(&regular_path).exists() { continue; }
+        let regular = genpdf::fonts::FontData::load(&regular_path, None)?;
+        // Fall back to the regular face when there is no bold variant next to it.
+        let bold = if Path::new(&bold_path).exists() { genpdf::fonts::FontData::load(&bold_path, None)? } else { regular.clone() };
+        return Ok(genpdf::fonts::FontFamily { regular: regular.clone(), bold: bold.clone(), italic: regular, bold_italic: bold });
+    }
+
+    anyhow::bail!("Hittade inget typsnitt för PDF-export (sätt JOBSEEKER_PDF_FONT till en .ttf-fil)")
+}
@@ -0,0 +1,190 @@
+//! The search pipeline without any UI: one API search per keyword, blacklist filtering,
+//! auto-reject rules, saving the new ads and moving the watermark/checkpoint on. The Slint
+//! frontend calls `run_search` and only turns the outcome into status messages, so the
+//! pipeline can also be run from tests and tools.
+
+use crate::api::{ApiError, JobSearchClient};
+use crate::db::{Db, SearchCheckpoint};
+use crate::diagnostics::SearchFilterStats;
+use crate::models::{AdStatus, AppSettings, JobAd};
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// What to search for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchProfile {
+    /// A priority zone (1–3) with its keywords and municipalities; 0 searches every zone at once.
+    Zone(i32),
+    /// Free text, nationwide. Has no watermark or checkpoint.
+    FreeText(String),
+}
+
+impl SearchProfile {
+    /// Key of the zone's watermark and checkpoint ("p1", "p0" for all zones).
+    pub fn key(&self) -> Option<String> {
+        match self { SearchProfile::Zone(p) => Some(format!("p{}", p)), SearchProfile::FreeText(_) => None }
+    }
+
+    pub fn prio(&self) -> Option<i32> {
+        match self { SearchProfile::Zone(p) => Some(*p), SearchProfile::FreeText(_) => None }
+    }
+
+    /// The comma-separated keywords and locations searched. All zones at once join the
+    /// three zones' locations; free text has no locations.
+    pub fn query_and_locations(&self, settings: &AppSettings) -> (String, String) {
+        match self {
+            SearchProfile::FreeText(q) => (q.clone(), String::new()),
+            SearchProfile::Zone(0) => (settings.keywords.clone(), [&settings.locations_p1, &settings.locations_p2, &settings.locations_p3].iter().map(|s| s.trim()).filter(|s| !s.is_empty()).collect::<Vec<_>>().join(", ")),
+            SearchProfile::Zone(p) => {
+                let locs = match p { 2 => &settings.locations_p2, 3 => &settings.locations_p3, _ => &settings.locations_p1 };
+                (settings.zone_keywords(Some(*p)).to_string(), locs.clone())
+            }
+        }
+    }
+}
+
+/// How a run treats earlier runs of the same zone.
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    /// Ignore the watermark and any interrupted run, fetching everything again.
+    pub full_refresh: bool,
+    /// "Publicerad från": only ads published from this day on.
+    pub since: Option<NaiveDate>,
+}
+
+/// What a run did, for the status line and the keyword breakdown.
+#[derive(Debug, Clone, Default)]
+pub struct SearchOutcome {
+    pub new_count: usize,
+    /// Of `new_count`, saved as rejected by the auto-reject rules.
+    pub auto_rejected: usize,
+    /// Keywords whose search failed, with the error.
+    pub failed_keywords: Vec<(String, String)>,
+    /// New ads per keyword in search order; None when the keyword failed.
+    pub per_keyword: Vec<(String, Option<usize>)>,
+    /// Set when the API rate limit stopped the run; the remaining keywords weren't searched.
+    pub rate_limited: Option<String>,
+    pub filter_stats: SearchFilterStats,
+}
+
+/// Start of `date` in local time, as used for `published-after`.
+pub fn local_midnight_utc(date: NaiveDate) -> Option<DateTime<Utc>> {
+    date.and_hms_opt(0, 0, 0)?.and_local_timezone(chrono::Local).earliest().map(|d| d.with_timezone(&Utc))
+}
+
+/// The `published-after` a search sends: the zone's watermark, raised to the start of the
+/// "Publicerad från" day when one is picked, otherwise to `age_days` before `now` (0 = no
+/// limit). A picked day replaces the age window, so older ads can still be fetched on purpose.
+pub fn published_after(watermark: Option<DateTime<Utc>>, since: Option<NaiveDate>, age_days: u32, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let age_floor = (age_days > 0).then(|| now - chrono::Duration::days(age_days as i64));
+    watermark.max(since.and_then(local_midnight_utc).or(age_floor))
+}
+
+/// Searches each term (a keyword and its synonyms) and merges the hits, each ad once. The
+/// API gets one simple query per term; see the constraints on `JobSearchClient::search`.
+pub async fn search_terms(api_client: &JobSearchClient, terms: &[String], municipalities: &[String], watermark: Option<DateTime<Utc>>) -> anyhow::Result<Vec<JobAd>> {
+    let mut seen = std::collections::HashSet::new();
+    let mut ads = Vec::new();
+    for term in terms {
+        for ad in api_client.search(term, municipalities, 100, watermark).await? {
+            if seen.insert(ad.id.clone()) { ads.push(ad); }
+        }
+    }
+    Ok(ads)
+}
+
+/// Runs one search and saves the new ads. A zone search resumes an interrupted run and only
+/// asks for ads published after its watermark, which moves on once every keyword succeeded.
+pub async fn run_search(client: &JobSearchClient, db: &Db, profile: &SearchProfile, settings: &AppSettings, options: &SearchOptions) -> SearchOutcome {
+    let key = profile.key();
+    let watermark = match (&key, options.full_refresh) { (Some(key), false) => db.get_search_watermark(key).await.ok().flatten(), _ => None };
+    if let Some(since) = watermark { tracing::info!("Inkrementell sökning: hämtar bara annonser publicerade efter {}", since); }
+    // The "Publicerad från" floor narrows the request further. When it is above the watermark,
+    // the ads in between are never fetched, so the watermark is left as it was. The default age
+    // window doesn't hold it back: ads older than that are meant to be skipped for good.
+    let floor = options.since.and_then(local_midnight_utc);
+    let floor_above_watermark = floor.is_some_and(|f| watermark.is_none_or(|w| f > w));
+    let watermark = published_after(watermark, options.since, settings.default_age_days, Utc::now());
+    // An interrupted run of this profile is resumed unless a full refresh was asked for
+    let checkpoint = match (&key, options.full_refresh) { (Some(key), false) => db.get_search_checkpoint(key).await.ok().flatten(), _ => None };
+    if let (Some(key), true) = (&key, options.full_refresh) { let _ = db.clear_search_checkpoint(key).await; }
+    let run_started = checkpoint.as_ref().map(|c| c.started_at).unwrap_or_else(Utc::now);
+    let mut completed = checkpoint.map(|c| c.completed_keywords).unwrap_or_default();
+    if !completed.is_empty() { tracing::info!("Återupptar avbruten sökning, hoppar över: {}", completed.join(", ")); }
+
+    let (raw_query, locations) = profile.query_and_locations(settings);
+    // The zones may overlap when searching all of them; parse_locations keeps one call per municipality
+    let municipalities = JobSearchClient::parse_locations(&locations);
+    let query_parts: Vec<String> = raw_query.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()).map(|s| s.replace("\"", "")).collect();
+    let employer_blacklist = settings.blacklisted_employers();
+    let blacklist = settings.zone_blacklisted_keywords(profile.prio());
+    let today = chrono::Local::now().date_naive();
+
+    let mut outcome = SearchOutcome::default();
+    for keyword in &query_parts {
+        if completed.contains(keyword) { continue; }
+        let before = outcome.new_count;
+        match search_terms(client, &settings.keyword_terms(keyword), &municipalities, watermark).await {
+            Ok(ads) => {
+                outcome.filter_stats.api_returned += ads.len();
+                for mut ad in ads {
+                    ad.search_keyword = Some(keyword.clone());
+                    if ad.is_employer_blacklisted(&employer_blacklist) { outcome.filter_stats.blacklisted_employer += 1; continue; }
+                    if let Some(word) = ad.blacklist_keyword_hit(&blacklist) { *outcome.filter_stats.blacklisted_words.entry(word.to_string()).or_insert(0) += 1; continue; }
+                    match db.get_job_ad(&ad.id).await {
+                        Ok(None) => {
+                            let reason = settings.auto_reject_rules.reason(&ad, today);
+                            if let Some(reason) = &reason { tracing::debug!("Avvisar {} automatiskt: {:?}", ad.id, reason); ad.status = Some(AdStatus::Rejected); }
+                            if db.save_job_ad(&ad).await.is_ok() { outcome.new_count += 1; if reason.is_some() { outcome.auto_rejected += 1; } }
+                        }
+                        Ok(Some(_)) => outcome.filter_stats.already_saved += 1,
+                        Err(_) => {}
+                    }
+                }
+                outcome.per_keyword.push((keyword.clone(), Some(outcome.new_count - before)));
+                completed.push(keyword.clone());
+                if let Some(key) = &key {
+                    let checkpoint = SearchCheckpoint { started_at: run_started, completed_keywords: completed.clone() };
+                    if let Err(e) = db.set_search_checkpoint(key, &checkpoint).await { tracing::warn!("Kunde inte spara sökläge: {}", e); }
+                }
+            }
+            Err(e) => {
+                outcome.failed_keywords.push((keyword.clone(), format!("{:#}", e)));
+                outcome.per_keyword.push((keyword.clone(), None));
+                // Further keywords would hit the same limit; the checkpoint lets the next run continue
+                if let Some(limit) = e.downcast_ref::<ApiError>() { outcome.rate_limited = Some(limit.to_string()); break; }
+            }
+        }
+    }
+    if outcome.auto_rejected > 0 { tracing::info!("{} av {} nya annonser avvisades automatiskt", outcome.auto_rejected, outcome.new_count); }
+
+    // Only advance the watermark when every keyword succeeded, otherwise ads could be skipped.
+    // A resumed run counts from when the interrupted one started.
+    if let (Some(key), true) = (&key, outcome.failed_keywords.is_empty()) {
+        if !floor_above_watermark {
+            if let Err(e) = db.set_search_watermark(key, run_started).await { tracing::warn!("Kunde inte spara sökvattenmärke: {}", e); }
+        }
+        let _ = db.clear_search_checkpoint(key).await;
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn published_after_follows_the_default_age_window() {
+        let now = "2026-10-14T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let window = published_after(None, None, 30, now).unwrap();
+        assert_eq!(window, now - chrono::Duration::days(30));
+        // One extra minute so ads published right at the edge are included
+        assert_eq!(crate::api::published_after_minutes(window, now), 30 * 24 * 60 + 1);
+        // A newer watermark wins; 0 days means no floor at all
+        let recent = now - chrono::Duration::days(2);
+        assert_eq!(published_after(Some(recent), None, 30, now), Some(recent));
+        assert_eq!(published_after(None, None, 0, now), None);
+        // A picked "Publicerad från" day replaces the window, also further back
+        let since = NaiveDate::from_ymd_opt(2026, 6, 1).unwrap();
+        assert_eq!(published_after(None, Some(since), 30, now), local_midnight_utc(since));
+    }
+}
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use crate::db::{Db, Result};
+use crate::models::{AdStatus, JobAd};
+
+/// How many actions "Ångra" can step back through.
+pub const UNDO_DEPTH: usize = 10;
+
+/// An ad's status as it was before a change, with the application date so undoing a
+/// status change away from Applied brings back the original date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviousStatus {
+    pub id: String,
+    pub status: Option<AdStatus>,
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+impl PreviousStatus {
+    pub fn of(ad: &JobAd) -> Self {
+        Self { id: ad.id.clone(), status: ad.status, applied_at: ad.applied_at }
+    }
+}
+
+/// The last few status changes, newest last. One entry is one user action, so undoing a
+/// bulk reject brings back every ad it touched.
+#[derive(Debug, Default)]
+pub struct UndoStack {
+    changes: Vec<Vec<PreviousStatus>>,
+}
+
+impl UndoStack {
+    /// Records an action; the oldest one is dropped beyond `UNDO_DEPTH`.
+    pub fn push(&mut self, change: Vec<PreviousStatus>) {
+        if change.is_empty() { return; }
+        self.changes.push(change);
+        if self.changes.len() > UNDO_DEPTH { self.changes.remove(0); }
+    }
+
+    pub fn pop(&mut self) -> Option<Vec<PreviousStatus>> {
+        self.changes.pop()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Puts the ads of one undone action back to their earlier status and returns them as
+/// stored now. Ads deleted in the meantime are skipped.
+pub async fn restore(db: &Db, change: &[PreviousStatus]) -> Result<Vec<JobAd>> {
+    let mut restored = Vec::new();
+    for prev in change {
+        if db.get_job_ad(&prev.id).await?.is_none() { continue; }
+        db.update_ad_status_with_date(&prev.id, prev.status, prev.applied_at).await?;
+        if let Some(ad) = db.get_job_ad(&prev.id).await? { restored.push(ad); }
+    }
+    Ok(restored)
+}
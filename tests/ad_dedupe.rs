@@ -0,0 +1,72 @@
+mod common;
+use common::TestDb;
+
+fn ad(id: &str, headline: &str, published: &str, status: Option<&str>) -> Jobseeker::models::JobAd {
+    common::ad_with(id, serde_json::json!({
+        "headline": headline,
+        "employer": { "name": "Acme AB" },
+        "publication_date": format!("{}T08:00:00", published),
+        "status": status
+    }))
+}
+
+/// Integration test on a store with known duplicates: copies open at the same time are
+/// merged into the most advanced one, a repost half a year later and a different job stay,
+/// a draft follows the kept record, and a dry run changes nothing.
+#[tokio::test]
+async fn dedupe_keeps_the_most_advanced_overlapping_copy() {
+    let ads = vec![
+        ad("march-new", "Supporttekniker", "2026-03-01", None),
+        ad("march-saved", "SUPPORTTEKNIKER ", "2026-03-10", Some("Bookmarked")),
+        ad("march-newest", "Supporttekniker", "2026-03-20", None),
+        ad("september", "Supporttekniker", "2026-09-01", None),
+        ad("other", "Lagerarbetare", "2026-03-01", None),
+    ];
+    let db = TestDb::with_ads("ad_dedupe", &ads).await;
+    db.save_application_draft("march-new", "Mitt utkast").await.unwrap();
+
+    let mut groups = Jobseeker::db::true_duplicate_groups(&ads);
+    for g in &mut groups { g.sort(); }
+    assert_eq!(groups, vec![vec!["march-new".to_string(), "march-newest".to_string(), "march-saved".to_string()]]);
+
+    let plan = db.dedupe_ads(true).await.expect("dry run failed");
+    assert_eq!(plan.groups.len(), 1);
+    assert_eq!(plan.groups[0].0, "march-saved");
+    assert_eq!(plan.removed_count(), 2);
+    assert!(db.get_job_ad("march-new").await.unwrap().is_some());
+
+    let done = db.dedupe_ads(false).await.expect("dedupe failed");
+    assert_eq!(done, plan);
+    for gone in ["march-new", "march-newest"] { assert!(db.get_job_ad(gone).await.unwrap().is_none()); }
+    for kept in ["march-saved", "september", "other"] { assert!(db.get_job_ad(kept).await.unwrap().is_some()); }
+    assert_eq!(db.get_application_draft("march-saved").await.unwrap().as_deref(), Some("Mitt utkast"));
+    assert!(db.dedupe_ads(true).await.unwrap().groups.is_empty());
+}
+
+/// The kept copy takes over what the user put on the deleted ones: a star and a pin stay, the
+/// tags are joined and the best rating wins, even when another copy ranks first by status.
+#[tokio::test]
+async fn dedupe_merges_star_pin_tags_and_rating_into_the_kept_copy() {
+    let mut saved = ad("saved", "Supporttekniker", "2026-03-01", Some("Bookmarked"));
+    saved.tags = vec!["distans".to_string()];
+    saved.rating = Some(5);
+    let mut starred = ad("starred", "Supporttekniker", "2026-03-05", None);
+    starred.starred = true;
+    starred.tags = vec!["hög lön".to_string(), "distans".to_string()];
+    starred.rating = Some(8);
+    let mut pinned = ad("pinned", "Supporttekniker", "2026-03-07", None);
+    pinned.pinned = true;
+    let db = TestDb::with_ads("ad_dedupe_merge", &[saved, starred, pinned]).await;
+
+    let done = db.dedupe_ads(false).await.expect("dedupe failed");
+    assert_eq!(done.groups.len(), 1);
+    let (kept_id, mut removed) = done.groups[0].clone();
+    removed.sort();
+    assert_eq!((kept_id.as_str(), removed), ("saved", vec!["pinned".to_string(), "starred".to_string()]));
+    let kept = db.get_job_ad("saved").await.unwrap().expect("kept copy");
+    assert!(kept.starred && kept.pinned);
+    assert_eq!(kept.tags, vec!["distans".to_string(), "hög lön".to_string()]);
+    assert_eq!(kept.rating, Some(8));
+    assert_eq!(kept.status, Some(Jobseeker::models::AdStatus::Bookmarked));
+    for gone in ["starred", "pinned"] { assert!(db.get_job_ad(gone).await.unwrap().is_none()); }
+}
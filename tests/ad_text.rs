@@ -0,0 +1,58 @@
+/// The share summary leaves out what the ad lacks, and the mailto link encodes subject and
+/// body so line breaks, spaces and å/ä/ö survive.
+#[test]
+fn share_text_and_mailto_link() {
+    let ad: Jobseeker::models::JobAd = serde_json::from_value(serde_json::json!({
+        "id": "share-1",
+        "headline": "Lagerarbetare & truckförare",
+        "publication_date": "2026-01-10T08:00:00",
+        "last_application_date": "2026-02-15T23:59:59",
+        "employer": { "name": "Testbolaget AB" },
+        "workplace_address": { "city": "Malmö" },
+        "webpage_url": "https://arbetsformedlingen.se/platsbanken/annonser/1"
+    })).expect("valid test ad");
+    let text = Jobseeker::reports::ad_share_text(&ad);
+    assert_eq!(text, "Lagerarbetare & truckförare\nArbetsgivare: Testbolaget AB\nOrt: Malmö\nSista ansökningsdag: 2026-02-15\nhttps://arbetsformedlingen.se/platsbanken/annonser/1");
+
+    assert_eq!(
+        Jobseeker::reports::mailto_link(&ad.headline, "Ort: Malmö\nhttps://x.se/?a=1&b=2"),
+        "mailto:?subject=Lagerarbetare%20%26%20truckf%C3%B6rare&body=Ort%3A%20Malm%C3%B6%0Ahttps%3A%2F%2Fx.se%2F%3Fa%3D1%26b%3D2"
+    );
+}
+
+/// Skills, languages and experiences all become checklist items; the driving licence counts
+/// as a requirement and an ad without any gets no checklist.
+#[test]
+fn requirements_checklist_lists_krav_before_meriterande() {
+    let ad: Jobseeker::models::JobAd = serde_json::from_value(serde_json::json!({
+        "id": "krav-1",
+        "headline": "Lagerarbetare",
+        "driving_license_required": true,
+        "must_have": { "skills": [{ "label": "Truckkort" }], "languages": [{ "label": "Svenska" }], "work_experiences": [{ "label": "Lagerarbete" }] },
+        "nice_to_have": { "languages": [{ "label": "Engelska" }], "skills": [{ "label": "WMS" }] }
+    })).expect("valid test ad");
+    assert_eq!(
+        Jobseeker::reports::requirements_checklist(&ad).unwrap(),
+        "# Lagerarbetare\n\n## Krav\n- [ ] Truckkort\n- [ ] Svenska\n- [ ] Lagerarbete\n- [ ] Körkort\n\n## Meriterande\n- [ ] WMS\n- [ ] Engelska\n"
+    );
+
+    let bare: Jobseeker::models::JobAd = serde_json::from_value(serde_json::json!({ "id": "krav-2", "headline": "Städare" })).expect("valid test ad");
+    assert_eq!(Jobseeker::reports::requirements_checklist(&bare), None);
+}
+
+#[test]
+fn clean_url_drops_tracking_and_keeps_real_params() {
+    use Jobseeker::reports::clean_url;
+    assert_eq!(clean_url("https://x.se/jobb/1?utm_source=nl&id=42&UTM_Medium=mail&gclid=abc&lang=sv#ansok"), "https://x.se/jobb/1?id=42&lang=sv#ansok");
+    assert_eq!(clean_url("https://x.se/jobb/1?fbclid=1&utm_campaign=v"), "https://x.se/jobb/1");
+    assert_eq!(clean_url("https://x.se/jobb/1?ref=utm_source"), "https://x.se/jobb/1?ref=utm_source");
+    assert_eq!(clean_url("https://arbetsformedlingen.se/platsbanken/annonser/1"), "https://arbetsformedlingen.se/platsbanken/annonser/1");
+
+    let ad: Jobseeker::models::JobAd = serde_json::from_value(serde_json::json!({
+        "id": "share-2", "headline": "Supporttekniker",
+        "webpage_url": "https://x.se/jobb/2?utm_source=jobtech&id=2"
+    })).expect("valid test ad");
+    assert!(Jobseeker::reports::ad_share_text(&ad).ends_with("https://x.se/jobb/2?id=2"));
+    // The stored URL itself is left alone
+    assert_eq!(ad.webpage_url.as_deref(), Some("https://x.se/jobb/2?utm_source=jobtech&id=2"));
+}
@@ -0,0 +1,123 @@
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+mod common;
+use common::{mock_server, response};
+
+/// A short Retry-After is waited out and the search retried once.
+#[tokio::test]
+async fn waits_out_short_retry_after_then_succeeds() {
+    let hits = r#"{"hits":[{"id":"1","headline":"Supporttekniker","publication_date":"2026-01-10T08:00:00"}]}"#;
+    let base = mock_server(vec![
+        response("429 Too Many Requests", "Retry-After: 1\r\n", "{}"),
+        response("200 OK", "", hits),
+    ]).await;
+    let client = Jobseeker::api::JobSearchClient::new().with_base_url(&base);
+
+    let started = Instant::now();
+    let ads = client.search("support", &[], 10, None).await.expect("search should succeed after the wait");
+    assert_eq!(ads.len(), 1);
+    assert!(started.elapsed() >= Duration::from_secs(1));
+}
+
+/// A Retry-After beyond `MAX_RATE_LIMIT_WAIT` comes back as a typed error at once.
+#[tokio::test]
+async fn long_retry_after_is_a_typed_error() {
+    let base = mock_server(vec![response("429 Too Many Requests", "Retry-After: 120\r\n", "{}")]).await;
+    let client = Jobseeker::api::JobSearchClient::new().with_base_url(&base);
+
+    let err = client.search("support", &[], 10, None).await.expect_err("429 should fail the search");
+    match err.downcast_ref::<Jobseeker::api::ApiError>() {
+        Some(Jobseeker::api::ApiError::RateLimited { retry_after }) => assert_eq!(*retry_after, Duration::from_secs(120)),
+        None => panic!("expected RateLimited, got {:?}", err),
+    }
+    assert_eq!(err.downcast_ref::<Jobseeker::api::ApiError>().unwrap().to_string(), "API överbelastad, försök igen om 120 s");
+}
+
+/// Like `mock_server` for a single response, but hands back the raw request it received.
+async fn recording_server(response: String) -> (String, tokio::sync::oneshot::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("Failed to bind mock server");
+    let addr = listener.local_addr().expect("mock server address");
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    tokio::spawn(async move {
+        let Ok((mut socket, _)) = listener.accept().await else { return };
+        let mut buf = [0u8; 4096];
+        let n = socket.read(&mut buf).await.unwrap_or(0);
+        let _ = tx.send(String::from_utf8_lossy(&buf[..n]).to_lowercase());
+        let _ = socket.write_all(response.as_bytes()).await;
+        let _ = socket.shutdown().await;
+    });
+    (format!("http://{}", addr), rx)
+}
+
+/// Searches and ad lookups identify themselves, with the version by default.
+#[tokio::test]
+async fn requests_carry_the_user_agent() {
+    let (base, request) = recording_server(response("200 OK", "", r#"{"hits":[]}"#)).await;
+    let client = Jobseeker::api::JobSearchClient::new().with_base_url(&base);
+    client.search("support", &[], 10, None).await.expect("search should succeed");
+    let request = request.await.expect("request recorded");
+    assert!(request.contains(&format!("user-agent: {}", Jobseeker::api::DEFAULT_USER_AGENT.to_lowercase())), "{}", request);
+    assert!(Jobseeker::api::DEFAULT_USER_AGENT.starts_with("Jobseeker/"));
+
+    let ad = r#"{"id":"7","headline":"Supporttekniker","publication_date":"2026-01-10T08:00:00"}"#;
+    let (base, request) = recording_server(response("200 OK", "", ad)).await;
+    let client = Jobseeker::api::JobSearchClient::new().with_base_url(&base).with_user_agent("Jobseeker-test (kontakt@example.se)");
+    client.get_ad("7").await.expect("ad should load");
+    assert!(request.await.expect("request recorded").contains("user-agent: jobseeker-test (kontakt@example.se)"));
+}
+
+/// The request delay spaces consecutive calls but doesn't hold up the first one.
+#[tokio::test]
+async fn request_delay_spaces_consecutive_calls() {
+    let ad = r#"{"id":"7","headline":"Supporttekniker","publication_date":"2026-01-10T08:00:00"}"#;
+    let base = mock_server(vec![response("200 OK", "", ad), response("200 OK", "", ad)]).await;
+    let client = Jobseeker::api::JobSearchClient::new().with_base_url(&base).with_request_delay(Duration::from_millis(400));
+
+    let started = Instant::now();
+    client.get_ad("7").await.expect("first ad should load");
+    assert!(started.elapsed() < Duration::from_millis(400));
+    client.get_ad("7").await.expect("second ad should load");
+    assert!(started.elapsed() >= Duration::from_millis(400));
+}
+
+/// Hit counts come from `total.value` and are added up over the municipalities.
+#[tokio::test]
+async fn count_hits_sums_the_municipalities() {
+    let base = mock_server(vec![
+        response("200 OK", "", r#"{"total":{"value":1240},"hits":[]}"#),
+        response("200 OK", "", r#"{"total":{"value":310},"hits":[]}"#),
+    ]).await;
+    let client = Jobseeker::api::JobSearchClient::new().with_base_url(&base);
+    let count = client.count_hits("it", &["1283".to_string(), "1277".to_string()]).await.expect("count should succeed");
+    assert_eq!(count, 1550);
+}
+
+/// The retry after a 429 keeps the request delay from the first request, even when
+/// Retry-After asks for no wait at all.
+#[tokio::test]
+async fn retry_after_429_keeps_the_request_delay() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("Failed to bind mock server");
+    let base = format!("http://{}", listener.local_addr().expect("mock server address"));
+    let hits = r#"{"hits":[]}"#;
+    let responses = vec![response("429 Too Many Requests", "Retry-After: 0\r\n", "{}"), response("200 OK", "", hits)];
+    let server = tokio::spawn(async move {
+        let mut arrivals = Vec::new();
+        for response in responses {
+            let Ok((mut socket, _)) = listener.accept().await else { break };
+            arrivals.push(Instant::now());
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await;
+            let _ = socket.write_all(response.as_bytes()).await;
+            let _ = socket.shutdown().await;
+        }
+        arrivals
+    });
+    let client = Jobseeker::api::JobSearchClient::new().with_base_url(&base).with_request_delay(Duration::from_millis(500));
+
+    client.search("support", &[], 10, None).await.expect("search should succeed after the retry");
+    let arrivals = server.await.expect("mock server");
+    assert_eq!(arrivals.len(), 2);
+    assert!(arrivals[1] - arrivals[0] >= Duration::from_millis(450), "retry came after {:?}", arrivals[1] - arrivals[0]);
+}
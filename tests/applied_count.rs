@@ -0,0 +1,73 @@
+use chrono::{Duration, TimeZone, Utc};
+use Jobseeker::db::JobFilter;
+use Jobseeker::models::{AdStatus, MonthBasis};
+
+mod common;
+use common::TestDb;
+
+/// Integration test: the month's applied count follows the stored statuses through an
+/// apply, a switch to another month and back, and an unapply.
+#[tokio::test]
+async fn applied_count_survives_a_month_switch() {
+    let march = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+    let ads: Vec<_> = ["a", "b"].into_iter().map(|id| {
+        let mut ad = common::ad_with(id, serde_json::json!({ "publication_date": "2026-03-09T08:00:00" }));
+        ad.internal_created_at = march;
+        ad
+    }).collect();
+    let db = TestDb::with_ads("applied_count", &ads).await;
+
+    db.update_ad_status_with_date("a", Some(AdStatus::Applied), Some(march)).await.unwrap();
+    assert_eq!(db.applied_count_in_month(2026, 3, MonthBasis::Activity).await.unwrap(), 1);
+    assert_eq!(db.applied_count_in_month(2026, 4, MonthBasis::Activity).await.unwrap(), 0);
+    assert_eq!(db.applied_count_in_month(2026, 3, MonthBasis::Activity).await.unwrap(), 1);
+
+    db.update_ad_status("a", Some(AdStatus::Rejected)).await.unwrap();
+    assert_eq!(db.applied_count_in_month(2026, 3, MonthBasis::Activity).await.unwrap(), 0);
+    assert_eq!(db.applied_count_in_month(2026, 3, MonthBasis::Publication).await.unwrap(), 0);
+}
+
+/// Integration test: an externally made application keeps the chosen date.
+#[tokio::test]
+async fn applied_with_date_keeps_chosen_date() {
+    let db = TestDb::with_ads("applied_date", &[common::ad("external")]).await;
+
+    let chosen = Utc::now() - Duration::days(7);
+    db.update_ad_status_with_date("external", Some(AdStatus::Applied), Some(chosen)).await.expect("update failed");
+    let ad = db.get_job_ad("external").await.expect("read failed").expect("ad missing");
+    assert_eq!(ad.status, Some(AdStatus::Applied));
+    assert_eq!(ad.applied_at, Some(chosen));
+
+    // A legacy ad with a date but status Bookmarked shows up under the Applied filter
+    let mut legacy = common::ad("legacy");
+    legacy.status = Some(AdStatus::Bookmarked);
+    legacy.applied_at = Some(Utc::now());
+    db.save_job_ad(&legacy).await.expect("save failed");
+    let applied = db.get_filtered_jobs(JobFilter { statuses: &[AdStatus::Applied], ..Default::default() }).await.expect("filter failed");
+    assert_eq!(applied.len(), 2);
+
+    // Moving an ad away from Applied clears the date, so it no longer counts
+    db.update_ad_status("external", Some(AdStatus::Bookmarked)).await.expect("update failed");
+    let ad = db.get_job_ad("external").await.expect("read failed").expect("ad missing");
+    assert!(ad.applied_at.is_none() && !ad.is_applied());
+}
+
+/// Integration test: `applied_count_between` counts applied ads by `applied_at` only.
+#[tokio::test]
+async fn applied_count_between_uses_applied_at() {
+    let now = Utc::now();
+    let mut ads: Vec<_> = [("this-week", 1), ("last-month", 40)].into_iter().map(|(id, days_ago)| {
+        let mut ad = common::ad(id);
+        ad.internal_created_at = now - Duration::days(60);
+        ad.status = Some(AdStatus::Applied);
+        ad.applied_at = Some(now - Duration::days(days_ago));
+        ad
+    }).collect();
+    let mut bookmarked = common::ad("bookmarked");
+    bookmarked.status = Some(AdStatus::Bookmarked);
+    ads.push(bookmarked);
+    let db = TestDb::with_ads("applied_between", &ads).await;
+
+    assert_eq!(db.applied_count_between(now - Duration::days(7), now).await.expect("count failed"), 1);
+    assert_eq!(db.applied_count_between(now - Duration::days(60), now).await.expect("count failed"), 2);
+}
@@ -0,0 +1,35 @@
+use chrono::{FixedOffset, TimeZone, Utc};
+use Jobseeker::models::AdStatus;
+
+mod common;
+use common::{ad, TestDb};
+
+/// Buckets are taken in the given timezone, not in UTC: late Sunday evening in Sweden
+/// (CEST, UTC+2) stays on Sunday, and half past midnight moves to Monday.
+#[test]
+fn applications_are_bucketed_in_local_time() {
+    let cest = FixedOffset::east_opt(2 * 3600).unwrap();
+    let times = [
+        Utc.with_ymd_and_hms(2026, 10, 18, 21, 30, 0).unwrap(), // Sunday 23:30 local
+        Utc.with_ymd_and_hms(2026, 10, 18, 22, 30, 0).unwrap(), // Monday 00:30 local
+        Utc.with_ymd_and_hms(2026, 10, 14, 7, 5, 0).unwrap(),   // Wednesday 09:05 local
+        Utc.with_ymd_and_hms(2026, 10, 14, 7, 55, 0).unwrap(),  // Wednesday 09:55 local
+    ];
+    let buckets = Jobseeker::db::weekday_hour_buckets(times, &cest);
+    assert_eq!(buckets[6][23], 1);
+    assert_eq!(buckets[0][0], 1);
+    assert_eq!(buckets[2][9], 2);
+    assert_eq!(buckets.iter().flatten().sum::<u32>(), 4);
+}
+
+/// Integration test: only applied ads with a date are counted.
+#[tokio::test]
+async fn applied_by_weekday_hour_counts_applied_ads() {
+    let db = TestDb::with_ads("apply_times", &[ad("a"), ad("b"), ad("c")]).await;
+    db.update_ad_status_with_date("a", Some(AdStatus::Applied), Some(Utc.with_ymd_and_hms(2026, 10, 12, 8, 0, 0).unwrap())).await.unwrap();
+    db.update_ad_status_with_date("b", Some(AdStatus::Applied), Some(Utc.with_ymd_and_hms(2026, 10, 13, 8, 0, 0).unwrap())).await.unwrap();
+    db.update_ad_status("c", Some(AdStatus::Bookmarked)).await.unwrap();
+
+    let buckets = db.applied_by_weekday_hour().await.expect("stats failed");
+    assert_eq!(buckets.iter().flatten().sum::<u32>(), 2);
+}
@@ -0,0 +1,62 @@
+use std::fs;
+use chrono::{Duration, Utc};
+use Jobseeker::models::AdStatus;
+
+mod common;
+use common::{TempPath, TestDb};
+
+fn test_ad(id: &str, age_days: i64, status: Option<AdStatus>) -> Jobseeker::models::JobAd {
+    let mut ad = common::ad(id);
+    ad.internal_created_at = Utc::now() - Duration::days(age_days);
+    ad.status = status;
+    ad
+}
+
+/// Integration test: `archive_old` removes only old New/Rejected ads.
+#[tokio::test]
+async fn archive_old_keeps_recent_and_acted_on_ads() {
+    let db = TestDb::with_ads("archive", &[
+        test_ad("old-new", 90, None),
+        test_ad("old-rejected", 90, Some(AdStatus::Rejected)),
+        test_ad("old-bookmarked", 90, Some(AdStatus::Bookmarked)),
+        test_ad("old-applied", 90, Some(AdStatus::Applied)),
+        test_ad("recent-new", 5, None),
+    ]).await;
+
+    assert_eq!(db.archive_old(30).await.expect("archive failed"), 2);
+
+    for id in ["old-bookmarked", "old-applied", "recent-new"] {
+        assert!(db.get_job_ad(id).await.expect("read failed").is_some(), "{} should be kept", id);
+    }
+    for id in ["old-new", "old-rejected"] {
+        assert!(db.get_job_ad(id).await.expect("read failed").is_none(), "{} should be archived", id);
+    }
+}
+
+/// Integration test: a month exported to file reads back as the same ads, and removing it
+/// afterwards keeps the saved ones in the live database.
+#[tokio::test]
+async fn export_month_round_trip() {
+    let out = TempPath::new("archive_month", "json");
+    let march = chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 3, 15, 12, 0, 0).unwrap();
+    let mut new = test_ad("march-new", 0, None);
+    new.internal_created_at = march;
+    let mut saved = test_ad("march-saved", 0, Some(AdStatus::Bookmarked));
+    saved.internal_created_at = march;
+    saved.bookmarked_at = Some(march);
+    let mut april = test_ad("april-new", 0, None);
+    april.internal_created_at = march + Duration::days(30);
+    let db = TestDb::with_ads("archive_month", &[new, saved, april]).await;
+
+    assert_eq!(db.export_month(2026, 3, out.path()).await.expect("export failed"), 2);
+    let exported: Vec<Jobseeker::models::JobAd> = serde_json::from_str(&fs::read_to_string(out.path()).unwrap()).expect("archive should parse");
+    let mut ids: Vec<&str> = exported.iter().map(|ad| ad.id.as_str()).collect();
+    ids.sort();
+    assert_eq!(ids, vec!["march-new", "march-saved"]);
+    assert_eq!(exported.iter().find(|ad| ad.id == "march-saved").and_then(|ad| ad.bookmarked_at), Some(march));
+
+    assert_eq!(db.remove_archived_month(2026, 3).await.expect("remove failed"), 1);
+    assert!(db.get_job_ad("march-new").await.unwrap().is_none());
+    assert!(db.get_job_ad("march-saved").await.unwrap().is_some());
+    assert!(db.get_job_ad("april-new").await.unwrap().is_some());
+}
@@ -0,0 +1,24 @@
+use Jobseeker::models::AdStatus;
+
+mod common;
+use common::TestDb;
+
+/// Integration test: after a batch rating, a New ad at or above the threshold is saved,
+/// while a lower rating and an ad already applied to are left as they are.
+#[tokio::test]
+async fn high_rated_new_ads_are_bookmarked_and_applied_ones_untouched() {
+    let db = TestDb::new("auto_bookmark").await;
+    for (id, rating, status) in [("new-8", 8, None), ("new-5", 5, None), ("applied-9", 9, Some("Applied"))] {
+        db.save_job_ad(&common::ad_with(id, serde_json::json!({ "status": status }))).await.unwrap();
+        db.update_rating(id, rating).await.unwrap();
+    }
+    let ids: Vec<String> = ["new-8", "new-5", "applied-9"].iter().map(|s| s.to_string()).collect();
+
+    assert!(db.auto_bookmark_rated(&ids, 0).await.unwrap().is_empty());
+    assert_eq!(db.auto_bookmark_rated(&ids, 7).await.unwrap(), vec!["new-8".to_string()]);
+    assert_eq!(db.get_job_ad("new-8").await.unwrap().unwrap().status, Some(AdStatus::Bookmarked));
+    assert_eq!(db.get_job_ad("new-5").await.unwrap().unwrap().status, None);
+    assert_eq!(db.get_job_ad("applied-9").await.unwrap().unwrap().status, Some(AdStatus::Applied));
+    // Running again changes nothing: the saved ad is no longer New
+    assert!(db.auto_bookmark_rated(&ids, 7).await.unwrap().is_empty());
+}
@@ -0,0 +1,36 @@
+use Jobseeker::models::AdStatus;
+
+mod common;
+use common::TestDb;
+
+/// Integration test: the blacklist purge removes matching new ads but keeps bookmarked ones.
+#[tokio::test]
+async fn purge_blacklisted_keeps_bookmarked_ads() {
+    let ads: Vec<_> = [("nanny", "Nanny sökes", "Familjen AB"), ("agency", "Supporttekniker", "Manpower Group"), ("kept", "Nanny till förskola", "Kommunen"), ("ok", "Supporttekniker", "Region Skåne")]
+        .into_iter()
+        .map(|(id, headline, employer)| common::ad_with(id, serde_json::json!({ "headline": headline, "employer": { "name": employer } })))
+        .collect();
+    let db = TestDb::with_ads("purge", &ads).await;
+    db.update_ad_status("kept", Some(AdStatus::Bookmarked)).await.expect("bookmark failed");
+
+    let removed = db.purge_blacklisted(&["nanny".to_string()], &["manpower".to_string()]).await.expect("purge failed");
+    assert_eq!(removed, 2);
+    assert!(db.get_job_ad("kept").await.expect("read failed").is_some());
+    assert!(db.get_job_ad("ok").await.expect("read failed").is_some());
+}
+
+/// Integration test: starred and pinned ads survive the blacklist purge like bookmarked ones.
+#[tokio::test]
+async fn purge_blacklisted_keeps_starred_and_pinned_ads() {
+    let ads: Vec<_> = ["starred", "pinned", "plain"].into_iter().map(|id| common::ad_with(id, serde_json::json!({ "headline": "Nanny sökes" }))).collect();
+    let db = TestDb::with_ads("purge_marked", &ads).await;
+    db.set_starred("starred", true).await.expect("star failed");
+    db.set_pinned("pinned", true).await.expect("pin failed");
+    db.update_ad_status("pinned", Some(AdStatus::Rejected)).await.expect("reject failed");
+
+    let removed = db.purge_blacklisted(&["nanny".to_string()], &[]).await.expect("purge failed");
+    assert_eq!(removed, 1);
+    assert!(db.get_job_ad("plain").await.expect("read failed").is_none());
+    assert!(db.get_job_ad("starred").await.expect("read failed").is_some());
+    assert!(db.get_job_ad("pinned").await.expect("read failed").is_some());
+}
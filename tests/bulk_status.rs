@@ -0,0 +1,18 @@
+use Jobseeker::models::AdStatus;
+
+mod common;
+use common::TestDb;
+
+/// Integration test: one bulk call updates every known id and skips unknown ones.
+#[tokio::test]
+async fn bulk_status_updates_all_selected_ads() {
+    let ads: Vec<_> = ["a", "b", "c"].into_iter().map(|id| common::ad_with(id, serde_json::json!({ "headline": "Lagerarbetare" }))).collect();
+    let db = TestDb::with_ads("bulk", &ads).await;
+
+    let ids = vec!["a".to_string(), "b".to_string(), "missing".to_string()];
+    assert_eq!(db.update_ad_status_bulk(&ids, Some(AdStatus::Rejected)).await.expect("bulk failed"), 2);
+
+    assert_eq!(db.get_job_ad("a").await.expect("read failed").and_then(|ad| ad.status), Some(AdStatus::Rejected));
+    assert_eq!(db.get_job_ad("b").await.expect("read failed").and_then(|ad| ad.status), Some(AdStatus::Rejected));
+    assert_eq!(db.get_job_ad("c").await.expect("read failed").and_then(|ad| ad.status), None);
+}
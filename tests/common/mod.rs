@@ -0,0 +1,161 @@
+//! Setup shared by the integration tests: throwaway databases and files in the temp
+//! directory, a plain test ad, and a canned HTTP server standing in for JobTech.
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use Jobseeker::db::Db;
+use Jobseeker::models::JobAd;
+use Jobseeker::opener::Opener;
+
+static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+/// A path in the temp directory no other test uses, with any leftover from an earlier run
+/// removed. It is deleted again (file or directory) when dropped, also when the test fails.
+pub struct TempPath(PathBuf);
+
+impl TempPath {
+    pub fn new(name: &str, extension: &str) -> Self {
+        let unique = format!("jobseeker_{}_{}_{}", name, std::process::id(), NEXT.fetch_add(1, Ordering::Relaxed));
+        let mut path = std::env::temp_dir().join(unique);
+        if !extension.is_empty() { path.set_extension(extension); }
+        let temp = Self(path);
+        temp.remove();
+        temp
+    }
+
+    /// An empty directory; test databases and exported files go inside it.
+    pub fn dir(name: &str) -> Self {
+        let temp = Self::new(name, "");
+        std::fs::create_dir_all(&temp.0).expect("Failed to create temp dir");
+        temp
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    pub fn join(&self, name: &str) -> PathBuf {
+        self.0.join(name)
+    }
+
+    fn remove(&self) {
+        let _ = std::fs::remove_file(&self.0);
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        self.remove();
+    }
+}
+
+/// A fresh database that goes away with the test. Derefs to `Db`; the database is closed
+/// before its file is removed.
+pub struct TestDb {
+    db: Db,
+    file: TempPath,
+}
+
+impl TestDb {
+    pub async fn new(name: &str) -> Self {
+        let file = TempPath::new(name, "redb");
+        Self { db: open(file.path()).await, file }
+    }
+
+    pub async fn with_ads(name: &str, ads: &[JobAd]) -> Self {
+        let db = Self::new(name).await;
+        for ad in ads { db.save_job_ad(ad).await.expect("save failed"); }
+        db
+    }
+
+    pub fn path(&self) -> &Path {
+        self.file.path()
+    }
+
+    /// Hands out the database itself (to move into a server, or to drop it early and open
+    /// the file some other way); the file is removed when the returned `TempPath` drops.
+    pub fn into_parts(self) -> (Db, TempPath) {
+        (self.db, self.file)
+    }
+}
+
+impl std::ops::Deref for TestDb {
+    type Target = Db;
+
+    fn deref(&self) -> &Db {
+        &self.db
+    }
+}
+
+/// Opens (or creates) the database at `path`.
+pub async fn open(path: &Path) -> Db {
+    Db::new(path.to_str().expect("Temp path should be valid UTF-8"))
+        .await
+        .expect("Failed to create/open test DB")
+}
+
+/// A New ad "Supporttekniker" published 2026-01-10.
+pub fn ad(id: &str) -> JobAd {
+    ad_with(id, serde_json::json!({}))
+}
+
+/// `ad(id)` with the fields of the JSON object `fields` set on top, as the API would send them.
+pub fn ad_with(id: &str, fields: serde_json::Value) -> JobAd {
+    let mut json = serde_json::json!({ "id": id, "headline": "Supporttekniker", "publication_date": "2026-01-10T08:00:00" });
+    if let (Some(base), serde_json::Value::Object(fields)) = (json.as_object_mut(), fields) { base.extend(fields); }
+    serde_json::from_value(json).expect("valid test ad")
+}
+
+/// Minimal HTTP server that answers each connection with the next canned response in
+/// order, then stops. Returns the base URL.
+pub async fn mock_server(responses: Vec<String>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("Failed to bind mock server");
+    let addr = listener.local_addr().expect("mock server address");
+    tokio::spawn(async move {
+        for response in responses {
+            let Ok((mut socket, _)) = listener.accept().await else { return };
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await;
+            let _ = socket.write_all(response.as_bytes()).await;
+            let _ = socket.shutdown().await;
+        }
+    });
+    format!("http://{}", addr)
+}
+
+/// A JSON HTTP response; `extra_headers` are full header lines ending in "\r\n", or "".
+pub fn response(status: &str, extra_headers: &str, body: &str) -> String {
+    format!("HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n{}\r\n{}", status, body.len(), extra_headers, body)
+}
+
+/// Remembers every URL instead of opening it. A `failing` one records each open and then
+/// fails, like a system without a browser.
+#[derive(Debug, Default)]
+pub struct RecordingOpener {
+    opened: Mutex<Vec<String>>,
+    failing: bool,
+}
+
+impl RecordingOpener {
+    pub fn failing() -> Self {
+        Self { failing: true, ..Default::default() }
+    }
+
+    /// URLs asked for so far, oldest first.
+    pub fn opened(&self) -> Vec<String> {
+        self.opened.lock().map(|o| o.clone()).unwrap_or_default()
+    }
+}
+
+impl Opener for RecordingOpener {
+    fn open(&self, url: &str) -> anyhow::Result<()> {
+        if let Ok(mut opened) = self.opened.lock() { opened.push(url.to_string()); }
+        if self.failing { anyhow::bail!("ingen webbläsare (test)"); }
+        Ok(())
+    }
+}
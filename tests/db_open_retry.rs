@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+mod common;
+use common::TempPath;
+
+/// Integration test: a lock released while the app is starting is waited out, and one that
+/// is still held after the last try comes back as `BusyLock`, not a panic.
+#[tokio::test]
+async fn startup_waits_out_a_held_lock_and_reports_a_persistent_one() {
+    let file = TempPath::new("open_retry", "redb");
+    let path = file.path().to_str().expect("Temp path should be valid UTF-8").to_string();
+
+    // Stands in for a CLI tool holding the database
+    let tool = common::open(file.path()).await;
+    let err = Jobseeker::db::Db::open_with_retry(&path, 2, Duration::from_millis(10)).await.err().expect("the lock is still held");
+    assert!(matches!(err, Jobseeker::DbError::BusyLock(_)));
+
+    let release = tokio::spawn(async move { tokio::time::sleep(Duration::from_millis(150)).await; drop(tool); });
+    let db = Jobseeker::db::Db::open_with_retry(&path, 10, Duration::from_millis(50)).await.expect("opens once the tool is done");
+    release.await.unwrap();
+    assert!(db.load_settings().await.is_ok());
+}
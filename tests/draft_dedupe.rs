@@ -0,0 +1,36 @@
+mod common;
+use common::TestDb;
+
+async fn test_db(name: &str) -> TestDb {
+    let ads: Vec<_> = ["old", "new", "other"].into_iter().map(|id| {
+        let headline = if id == "other" { "Lagerarbetare" } else { "Supporttekniker" };
+        common::ad_with(id, serde_json::json!({ "headline": headline, "employer": { "name": "Acme AB" } }))
+    }).collect();
+    TestDb::with_ads(name, &ads).await
+}
+
+/// Integration test: of two drafts for a reposted job the newest stays, and a dry run
+/// changes nothing.
+#[tokio::test]
+async fn keeps_the_newest_draft_of_a_duplicate_group() {
+    let db = test_db("drafts_newest").await;
+    db.save_application_draft("new", "Första versionen").await.unwrap();
+    db.save_application_draft("old", "Äldre utkast").await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    db.save_application_draft("new", "Senaste versionen").await.unwrap();
+    db.save_application_draft("other", "Helt annat jobb").await.unwrap();
+
+    let groups = db.duplicate_ad_groups().await.expect("groups failed");
+    assert_eq!(groups, vec![vec!["new".to_string(), "old".to_string()]]);
+
+    let plan = db.dedupe_drafts(&groups, true).await.expect("dry run failed");
+    assert_eq!(plan.removed, vec!["old".to_string()]);
+    assert_eq!(plan.kept, vec!["new".to_string()]);
+    assert!(db.get_application_draft("old").await.unwrap().is_some());
+
+    let done = db.dedupe_drafts(&groups, false).await.expect("dedupe failed");
+    assert_eq!(done, plan);
+    assert_eq!(db.get_application_draft("old").await.unwrap(), None);
+    assert_eq!(db.get_application_draft("new").await.unwrap().as_deref(), Some("Senaste versionen"));
+    assert!(db.get_application_draft("other").await.unwrap().is_some());
+}
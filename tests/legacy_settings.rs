@@ -0,0 +1,43 @@
+mod common;
+use common::TempPath;
+
+/// A settings.json from the iced frontend is moved into the database once; the renamed
+/// file is left alone afterwards, and a new settings.json doesn't override the stored row.
+#[tokio::test]
+async fn legacy_settings_file_is_imported_exactly_once() {
+    let dir = TempPath::dir("legacy_settings");
+    let db = common::open(&dir.join("test.redb")).await;
+    let legacy = dir.join("settings.json");
+    std::fs::write(&legacy, r#"{"keywords":"rust, support","locations_p1":"1283","app_goal_count":20,"window_width":1200}"#).unwrap();
+
+    let imported = db.import_legacy_settings(&legacy).await.unwrap().expect("first start imports");
+    assert!(!legacy.exists());
+    assert!(imported.file_name().unwrap().to_string_lossy().starts_with("settings.json.imported."));
+    let settings = db.load_settings().await.unwrap().expect("settings row");
+    assert_eq!((settings.keywords.as_str(), settings.locations_p1.as_str(), settings.app_goal_count), ("rust, support", "1283", 20));
+    // Fields the old file didn't have get the defaults
+    assert_eq!(settings.app_min_count, Jobseeker::models::AppSettings::default().app_min_count);
+
+    // A second start finds the row and ignores any settings.json that shows up again
+    std::fs::write(&legacy, r#"{"keywords":"något annat"}"#).unwrap();
+    assert!(db.import_legacy_settings(&legacy).await.unwrap().is_none());
+    assert!(legacy.exists());
+    assert_eq!(db.load_settings().await.unwrap().unwrap().keywords, "rust, support");
+}
+
+/// A damaged settings.json is reported as a legacy-import problem naming the file, not as a
+/// damaged database; the file stays where it was and the first start carries on with defaults.
+#[tokio::test]
+async fn malformed_legacy_settings_are_skipped_not_reported_as_corrupt() {
+    let dir = TempPath::dir("legacy_settings_bad");
+    let db = common::open(&dir.join("test.redb")).await;
+    let legacy = dir.join("settings.json");
+    std::fs::write(&legacy, r#"{"keywords": "rust","#).unwrap();
+
+    let err = db.import_legacy_settings(&legacy).await.expect_err("malformed file");
+    assert!(matches!(&err, Jobseeker::DbError::LegacySettings(path, _) if *path == legacy));
+    assert!(err.to_string().contains("settings.json"));
+    assert!(legacy.exists());
+    assert!(db.load_settings().await.unwrap().is_none());
+    assert!(db.seed_first_run().await.unwrap());
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+mod common;
+use common::TestDb;
+
+/// Integration test: the local API lists a month's ads filtered by status and returns single
+/// ads as stored, with 404/400 for missing ads and bad parameters.
+#[tokio::test]
+async fn local_api_serves_ads_from_the_database() {
+    let ads: Vec<_> = [("mar-applied", "2026-03-02", Some("Applied")), ("mar-new", "2026-03-05", None), ("apr-new", "2026-04-01", None)]
+        .into_iter()
+        .map(|(id, published, status)| common::ad_with(id, serde_json::json!({ "publication_date": format!("{}T08:00:00", published), "status": status })))
+        .collect();
+    let (db, _file) = TestDb::with_ads("local_api", &ads).await.into_parts();
+    db.save_settings(&Jobseeker::models::AppSettings { month_basis: Jobseeker::models::MonthBasis::Publication, ..Default::default() }).await.unwrap();
+
+    let listener = Jobseeker::local_api::bind(0).await.expect("bind");
+    let addr = listener.local_addr().unwrap();
+    assert!(addr.ip().is_loopback());
+    let server = tokio::spawn(Jobseeker::local_api::serve(listener, Arc::new(db)));
+    let base = format!("http://{}", addr);
+    let get = |path: &str| reqwest::get(format!("{}{}", base, path));
+
+    let ids = |ads: serde_json::Value| -> Vec<String> { let mut ids: Vec<String> = ads.as_array().unwrap().iter().map(|a| a["id"].as_str().unwrap().to_string()).collect(); ids.sort(); ids };
+    let march = get("/ads?year=2026&month=3").await.unwrap();
+    assert_eq!(march.status(), 200);
+    assert_eq!(ids(march.json().await.unwrap()), vec!["mar-applied", "mar-new"]);
+    let applied = get("/ads?year=2026&month=3&status=applied").await.unwrap().json().await.unwrap();
+    assert_eq!(ids(applied), vec!["mar-applied"]);
+
+    let one: serde_json::Value = get("/ad/apr-new").await.unwrap().json().await.unwrap();
+    assert_eq!(one["id"], "apr-new");
+    assert_eq!(get("/ad/finns-inte").await.unwrap().status(), 404);
+    assert_eq!(get("/ads?month=13").await.unwrap().status(), 400);
+    assert_eq!(get("/ads?status=glömd").await.unwrap().status(), 400);
+    assert_eq!(get("/annat").await.unwrap().status(), 404);
+
+    server.abort();
+}
+
+/// Integration test: a request naming any other host (a rebound web page) gets 403, the
+/// loopback names are served, and a connection that never sends its request is closed.
+#[tokio::test]
+async fn local_api_refuses_foreign_hosts_and_idle_connections() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    let (db, _file) = TestDb::new("local_api_host").await.into_parts();
+    let listener = Jobseeker::local_api::bind(0).await.expect("bind");
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(Jobseeker::local_api::serve(listener, Arc::new(db)));
+
+    let status_for = |host: String| async move {
+        let mut socket = tokio::net::TcpStream::connect(addr).await.unwrap();
+        socket.write_all(format!("GET /ads HTTP/1.1\r\nHost: {}\r\n\r\n", host).as_bytes()).await.unwrap();
+        let mut response = String::new();
+        socket.read_to_string(&mut response).await.unwrap();
+        response.lines().next().unwrap_or("").to_string()
+    };
+    assert_eq!(status_for(format!("evil.example:{}", addr.port())).await, "HTTP/1.1 403 Forbidden");
+    assert_eq!(status_for("127.0.0.1:1".to_string()).await, "HTTP/1.1 403 Forbidden");
+    assert_eq!(status_for(format!("localhost:{}", addr.port())).await, "HTTP/1.1 200 OK");
+    assert_eq!(status_for(format!("127.0.0.1:{}", addr.port())).await, "HTTP/1.1 200 OK");
+
+    let mut idle = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let mut rest = Vec::new();
+    let closed = tokio::time::timeout(std::time::Duration::from_secs(10), idle.read_to_end(&mut rest)).await;
+    assert!(matches!(closed, Ok(Ok(0))), "the idle connection should be closed without a reply");
+
+    server.abort();
+}
@@ -0,0 +1,38 @@
+use Jobseeker::db::JobFilter;
+use Jobseeker::models::MonthBasis;
+
+mod common;
+use common::TestDb;
+
+/// Integration test: `Activity` lists an ad under the month it was applied to or saved,
+/// falling back to when it was fetched; `Publication` always uses the publication date.
+#[tokio::test]
+async fn month_basis_decides_which_date_places_an_ad() {
+    let db = TestDb::new("month_basis").await;
+    for ad in [
+        serde_json::json!({ "id": "applied", "headline": "A", "publication_date": "2026-01-10T08:00:00", "status": "Applied", "applied_at": "2026-03-15T12:00:00Z", "internal_created_at": "2026-01-11T12:00:00Z" }),
+        serde_json::json!({ "id": "saved", "headline": "B", "publication_date": "2026-01-05T08:00:00", "status": "Bookmarked", "bookmarked_at": "2026-02-20T12:00:00Z", "internal_created_at": "2026-01-06T12:00:00Z" }),
+        serde_json::json!({ "id": "fetched", "headline": "C", "publication_date": "2026-01-20T08:00:00", "internal_created_at": "2026-02-03T12:00:00Z" }),
+        serde_json::json!({ "id": "saved-undated", "headline": "D", "publication_date": "2026-02-25T08:00:00", "status": "Bookmarked", "internal_created_at": "2026-03-03T12:00:00Z" }),
+    ] {
+        let ad: Jobseeker::models::JobAd = serde_json::from_value(ad).expect("valid test ad");
+        db.save_job_ad(&ad).await.expect("save failed");
+    }
+
+    let month = |m: u32, basis: MonthBasis| {
+        let db = &db;
+        async move {
+            let mut ids: Vec<String> = db.get_filtered_jobs(JobFilter { year: Some(2026), month: Some(m), month_basis: basis, ..Default::default() }).await.expect("query failed").into_iter().map(|ad| ad.id).collect();
+            ids.sort();
+            ids
+        }
+    };
+    // Activity: applied_at, then bookmarked_at, then internal_created_at
+    assert!(month(1, MonthBasis::Activity).await.is_empty());
+    assert_eq!(month(2, MonthBasis::Activity).await, vec!["fetched", "saved"]);
+    assert_eq!(month(3, MonthBasis::Activity).await, vec!["applied", "saved-undated"]);
+    // Publication: the publication date only, whatever the status
+    assert_eq!(month(1, MonthBasis::Publication).await, vec!["applied", "fetched", "saved"]);
+    assert_eq!(month(2, MonthBasis::Publication).await, vec!["saved-undated"]);
+    assert!(month(3, MonthBasis::Publication).await.is_empty());
+}
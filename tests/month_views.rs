@@ -0,0 +1,16 @@
+use chrono::{Duration, Utc};
+
+mod common;
+use common::TestDb;
+
+/// Integration test: `touch_month_view` returns the previous visit and is kept per month.
+#[tokio::test]
+async fn month_view_returns_previous_visit() {
+    let db = TestDb::new("month_view").await;
+
+    let before = Utc::now();
+    assert!(db.touch_month_view("2026-01").await.expect("touch failed").is_none());
+    let first = db.touch_month_view("2026-01").await.expect("touch failed").expect("first visit not stored");
+    assert!(first >= before - Duration::seconds(1));
+    assert!(db.touch_month_view("2026-02").await.expect("touch failed").is_none());
+}
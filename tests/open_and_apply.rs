@@ -0,0 +1,77 @@
+use chrono::Utc;
+use Jobseeker::actions::{email_ad, open_ad_link, open_and_mark_applied, AdLink, OpenApplied};
+use Jobseeker::models::AdStatus;
+
+mod common;
+use common::{RecordingOpener, TestDb};
+
+fn ad(application_url: Option<&str>, webpage_url: Option<&str>) -> Jobseeker::models::JobAd {
+    common::ad_with("1", serde_json::json!({
+        "headline": "Supporttekniker & IT", "webpage_url": webpage_url, "application_details": { "url": application_url }
+    }))
+}
+
+/// Each open action picks its link: "open" prefers the direct application link, "open_ad"
+/// always the ad page, and tracking parameters are stripped before opening.
+#[test]
+fn open_actions_choose_the_link_and_strip_tracking() {
+    let both = ad(Some("https://example.com/ansok?utm_source=af&id=7"), Some("https://arbetsformedlingen.se/1"));
+    let page_only = ad(None, Some("https://arbetsformedlingen.se/1"));
+    let opener = RecordingOpener::default();
+    assert!(open_ad_link(&opener, &both, AdLink::from_action("open").unwrap()).unwrap());
+    assert!(open_ad_link(&opener, &page_only, AdLink::Apply).unwrap());
+    assert!(open_ad_link(&opener, &both, AdLink::Page).unwrap());
+    assert!(!open_ad_link(&opener, &page_only, AdLink::Direct).unwrap());
+    assert_eq!(opener.opened(), vec!["https://example.com/ansok?id=7", "https://arbetsformedlingen.se/1", "https://arbetsformedlingen.se/1"]);
+    assert!(AdLink::from_action("reject").is_none());
+    assert!(open_ad_link(&RecordingOpener::failing(), &both, AdLink::Apply).is_err());
+}
+
+/// "Dela via e-post" opens one mailto link with the headline as subject and the share text
+/// as body, both percent-encoded.
+#[test]
+fn email_ad_opens_a_mailto_link_with_the_share_text() {
+    let ad = ad(Some("https://example.com/ansok"), None);
+    let opener = RecordingOpener::default();
+    email_ad(&opener, &ad).unwrap();
+    let opened = opener.opened();
+    assert_eq!(opened.len(), 1);
+    assert!(opened[0].starts_with("mailto:?subject=Supporttekniker%20%26%20IT&body="));
+    assert_eq!(opened[0], Jobseeker::reports::mailto_link(&ad.headline, &Jobseeker::reports::ad_share_text(&ad)));
+}
+
+/// Integration test: "Öppna & markera sökt" opens the application link and marks the ad
+/// Applied in one go; when the browser doesn't start the ad stays as it was.
+#[tokio::test]
+async fn open_and_mark_applied_opens_the_link_and_sets_applied() {
+    let ads: Vec<_> = [("direct", Some("https://example.com/ansok")), ("page-only", None)]
+        .into_iter()
+        .map(|(id, application_url)| common::ad_with(id, serde_json::json!({
+            "webpage_url": format!("https://arbetsformedlingen.se/{}", id),
+            "application_details": { "url": application_url }
+        })))
+        .collect();
+    let db = TestDb::with_ads("open_apply", &ads).await;
+
+    let opener = RecordingOpener::default();
+    let before = Utc::now();
+    let outcome = open_and_mark_applied(&db, "direct", &opener).await.unwrap();
+    assert!(matches!(outcome, OpenApplied::Marked { ref previous } if previous.status.is_none()));
+    assert_eq!(opener.opened(), vec!["https://example.com/ansok"]);
+    let ad = db.get_job_ad("direct").await.unwrap().unwrap();
+    assert_eq!(ad.status, Some(AdStatus::Applied));
+    assert!(ad.applied_at.is_some_and(|at| at >= before));
+
+    // Opening again keeps the first application date
+    let outcome = open_and_mark_applied(&db, "direct", &opener).await.unwrap();
+    assert_eq!(outcome, OpenApplied::AlreadyApplied);
+    assert_eq!(db.get_job_ad("direct").await.unwrap().unwrap().applied_at, ad.applied_at);
+
+    // Without a direct link the ad page is opened; a failed open marks nothing
+    let failing = RecordingOpener::failing();
+    let outcome = open_and_mark_applied(&db, "page-only", &failing).await.unwrap();
+    assert_eq!(outcome, OpenApplied::OpenFailed);
+    assert_eq!(failing.opened(), vec!["https://arbetsformedlingen.se/page-only"]);
+    assert_eq!(db.get_job_ad("page-only").await.unwrap().unwrap().status, None);
+    assert_eq!(open_and_mark_applied(&db, "finns-inte", &opener).await.unwrap(), OpenApplied::NotFound);
+}
@@ -0,0 +1,44 @@
+mod common;
+use common::{TempPath, TestDb};
+
+/// Integration test: the view history is newest first, an ad opened twice in a row is kept
+/// once, and the history is capped.
+#[tokio::test]
+async fn view_history_dedupes_consecutive_views_and_is_capped() {
+    let db = TestDb::new("recent").await;
+
+    assert!(db.recent_views(10).await.expect("read failed").is_empty());
+    for id in ["a", "b", "b", "a"] { db.record_view(id).await.expect("record failed"); }
+    let ids: Vec<String> = db.recent_views(10).await.expect("read failed").into_iter().map(|v| v.id).collect();
+    assert_eq!(ids, ["a", "b", "a"]);
+    assert_eq!(db.recent_views(1).await.expect("read failed").len(), 1);
+
+    for i in 0..Jobseeker::db::RECENT_VIEWS_CAP + 5 { db.record_view(&format!("ad{}", i)).await.expect("record failed"); }
+    let views = db.recent_views(usize::MAX).await.expect("read failed");
+    assert_eq!(views.len(), Jobseeker::db::RECENT_VIEWS_CAP);
+    assert_eq!(views[0].id, format!("ad{}", Jobseeker::db::RECENT_VIEWS_CAP + 4));
+}
+
+/// Integration test: a history stored by an older version under the settings table is moved
+/// to its own table when the database is opened.
+#[tokio::test]
+async fn view_history_moves_out_of_the_settings_table() {
+    let file = TempPath::new("recent_move", "redb");
+    let settings: redb::TableDefinition<&str, &str> = redb::TableDefinition::new("settings");
+    {
+        let db = redb::Database::create(file.path()).expect("create raw db");
+        let txn = db.begin_write().unwrap();
+        txn.open_table(settings).unwrap()
+            .insert("recent_views", r#"[{"id":"a","viewed_at":"2026-03-02T08:00:00Z"}]"#).unwrap();
+        txn.commit().unwrap();
+    }
+
+    let db = common::open(file.path()).await;
+    let ids: Vec<String> = db.recent_views(10).await.expect("read failed").into_iter().map(|v| v.id).collect();
+    assert_eq!(ids, ["a"]);
+    drop(db);
+
+    let raw = redb::Database::open(file.path()).expect("reopen raw db");
+    let txn = raw.begin_read().unwrap();
+    assert!(txn.open_table(settings).unwrap().get("recent_views").unwrap().is_none());
+}
@@ -0,0 +1,190 @@
+use std::fs;
+use chrono::{Datelike, Utc};
+use Jobseeker::db::JobFilter;
+
+mod common;
+use common::TempPath;
+
+/// Integration test: the activity report is rendered to both DOCX and PDF from the
+/// same set of applied ads and drafts. The PDF half needs a system font (or
+/// `JOBSEEKER_PDF_FONT`) and is skipped without one.
+#[tokio::test]
+async fn report_docx_and_pdf_from_same_drafts() {
+    let dir = TempPath::dir("report");
+    let db = common::open(&dir.join("test.redb")).await;
+
+    let mut ad = common::ad_with("report-1", serde_json::json!({
+        "employer": { "name": "Testbolaget AB" },
+        "workplace_address": { "city": "Helsingborg" },
+        "working_hours_type": { "label": "Heltid" }
+    }));
+    ad.status = Some(Jobseeker::models::AdStatus::Applied);
+    ad.applied_at = Some(Utc::now());
+    db.save_job_ad(&ad).await.expect("Failed to save ad");
+    db.save_application_draft(&ad.id, "Hej!\nJag söker tjänsten som supporttekniker.")
+        .await
+        .expect("Failed to save draft");
+
+    let now = chrono::Local::now();
+    let settings = Jobseeker::models::AppSettings::default();
+    let options = Jobseeker::reports::ReportOptions { include_jobs: true, include_params: true, include_analysis: true, selected_drafts: None };
+    let report = Jobseeker::reports::activity_report(&db, &settings, now.year(), now.month(), "Testmånad", options).await;
+
+    let text = report.to_text();
+    assert!(text.contains("Supporttekniker"));
+    assert!(text.contains("ANSÖKNINGSBREV"));
+    // Only the known detail is shown; absent occupation/municipality leave no "None"
+    assert!(text.contains("  Omfattning: Heltid\n"));
+    assert!(!text.contains("None"));
+
+    let headings: Vec<&str> = report.sections.iter().map(|s| s.heading.as_str()).collect();
+    assert!(headings.contains(&"ANSÖKNINGSBREV"));
+
+    let docx_path = dir.join("rapport.docx");
+    report.write_docx(&docx_path).expect("DOCX export failed");
+    let docx = docx_rs::read_docx(&fs::read(&docx_path).unwrap()).expect("DOCX doesn't read back").json();
+    let positions: Vec<usize> = headings.iter().map(|h| docx.find(*h).unwrap_or_else(|| panic!("DOCX lacks {}", h))).collect();
+    assert!(positions.is_sorted(), "DOCX headings out of order: {:?}", headings);
+
+    if !Jobseeker::reports::pdf_font_available() {
+        eprintln!("Hoppar över PDF: inget typsnitt hittades (sätt JOBSEEKER_PDF_FONT)");
+        return;
+    }
+    // The PDF embeds its text as glyph indices of the font, so it can't be searched for the
+    // headings; it is rendered from the same sections as the DOCX above.
+    let pdf_path = dir.join("rapport.pdf");
+    report.write_pdf(&pdf_path).expect("PDF export failed");
+    let pdf = fs::read(&pdf_path).unwrap();
+    assert!(pdf.starts_with(b"%PDF-"));
+    assert!(pdf.windows(b"/Title".len()).any(|w| w == b"/Title"));
+}
+
+/// The weekly summary counts applied ads per ISO week, municipality and channel, and an identical
+/// second export leaves the file alone.
+#[test]
+fn summary_csv_counts_weeks_and_skips_unchanged_writes() {
+    let mut ad = common::ad_with("summary-1", serde_json::json!({ "workplace_address": { "city": "Helsingborg", "municipality": "Helsingborg" } }));
+    ad.status = Some(Jobseeker::models::AdStatus::Applied);
+    ad.applied_at = Some(chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 1, 14, 12, 0, 0).unwrap());
+    let mut unapplied = ad.clone();
+    unapplied.status = None;
+    unapplied.applied_at = None;
+    let mut by_mail = ad.clone();
+    by_mail.applied_channel = Jobseeker::models::parse_applied_channel("e-post");
+
+    let csv = Jobseeker::reports::application_summary_csv(&[ad, unapplied, by_mail]);
+    assert!(csv.contains("vecka;2026-V03;2"));
+    assert!(csv.contains("kommun;Helsingborg;2"));
+    assert!(csv.contains("kanal;E-post;1"));
+    assert!(csv.contains("kanal;Ej angiven;1"));
+
+    let path = TempPath::new("summary", "csv");
+    assert!(Jobseeker::reports::write_if_changed(path.path(), &csv).expect("first write failed"));
+    assert!(!Jobseeker::reports::write_if_changed(path.path(), &csv).expect("second write failed"));
+}
+
+/// The clipboard list has one template line per applied ad, newest first, and blanks for
+/// values the ad doesn't have.
+#[test]
+fn applied_lines_follow_template() {
+    let ad = |id: &str, day: u32, employer: Option<&str>| {
+        let mut ad: Jobseeker::models::JobAd = serde_json::from_value(serde_json::json!({
+            "id": id,
+            "headline": format!("Jobb {}", id),
+            "employer": employer.map(|e| serde_json::json!({ "name": e })),
+            "webpage_url": format!("https://example.se/{}", id)
+        })).expect("valid test ad");
+        ad.status = Some(Jobseeker::models::AdStatus::Applied);
+        ad.applied_at = Some(chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 3, day, 12, 0, 0).unwrap());
+        ad
+    };
+    let mut skipped = ad("c", 20, None);
+    skipped.status = Some(Jobseeker::models::AdStatus::Bookmarked);
+    let ads = vec![ad("a", 2, Some("Bolaget AB")), ad("b", 9, None), skipped];
+
+    let text = Jobseeker::reports::applied_lines(&ads, Jobseeker::reports::DEFAULT_APPLIED_LINE_TEMPLATE);
+    assert_eq!(text, "2026-03-09 — Jobb b —  — https://example.se/b\n2026-03-02 — Jobb a — Bolaget AB — https://example.se/a");
+    assert_eq!(Jobseeker::reports::applied_lines(&ads[..1], "{employer}: {headline}"), "Bolaget AB: Jobb a");
+}
+
+/// The yearly statistics DOCX is written for a year with applications, and only that
+/// year's applications are counted.
+#[tokio::test]
+async fn stats_report_docx_for_year_with_applications() {
+    let dir = TempPath::dir("stats");
+    let db = common::open(&dir.join("test.redb")).await;
+
+    for (id, year, month) in [("a", 2025, 3), ("b", 2025, 3), ("c", 2025, 11), ("d", 2024, 5)] {
+        let mut ad = common::ad_with(id, serde_json::json!({ "publication_date": "2024-01-10T08:00:00", "employer": { "name": "Testbolaget AB" } }));
+        ad.status = Some(Jobseeker::models::AdStatus::Applied);
+        ad.applied_at = Some(chrono::TimeZone::with_ymd_and_hms(&Utc, year, month, 10, 12, 0, 0).unwrap());
+        db.save_job_ad(&ad).await.expect("Failed to save ad");
+    }
+
+    let ads = db.get_filtered_jobs(JobFilter { statuses: &[Jobseeker::models::AdStatus::Applied], ..Default::default() }).await.expect("query failed");
+    let text = Jobseeker::reports::stats_report(&ads, 2025).to_text();
+    assert!(text.contains("• Mars: 2"));
+    assert!(text.contains("• Testbolaget AB: 3"));
+    assert!(text.contains("• 3 ansökningar under 2025"));
+
+    let path = dir.join("statistik.docx");
+    Jobseeker::reports::generate_stats_report(&db, 2025, &path).await.expect("DOCX export failed");
+    assert!(fs::metadata(&path).map(|m| m.len() > 0).unwrap_or(false));
+}
+
+/// Drafts become paragraphs with `<br>` line breaks; markup in the text is escaped except
+/// for bold and italic.
+#[test]
+fn draft_html_keeps_paragraphs_and_escapes_text() {
+    let draft = "Hej!\n\nJag söker tjänsten som **supporttekniker** hos *Testbolaget*.\nLön: <enligt avtal> & förmåner\n\n\nMvh\nAnna";
+    assert_eq!(
+        Jobseeker::reports::draft_to_html(draft),
+        "<p>Hej!</p>\n<p>Jag söker tjänsten som <strong>supporttekniker</strong> hos <em>Testbolaget</em>.<br>Lön: &lt;enligt avtal&gt; &amp; förmåner</p>\n<p>Mvh<br>Anna</p>"
+    );
+}
+
+/// Exporting into a directory that doesn't exist fails with an error in every format,
+/// before anything is built, instead of panicking or silently writing nothing.
+#[tokio::test]
+async fn export_to_unwritable_path_is_an_error() {
+    let dir = TempPath::dir("unwritable");
+    let db = common::open(&dir.join("test.redb")).await;
+
+    let missing = dir.join("finns-inte");
+    let report = Jobseeker::reports::stats_report(&[], 2026);
+    for format in ["txt", "docx", "pdf"] {
+        let format = Jobseeker::reports::ReportFormat::from_ui(format);
+        let path = missing.join(format!("rapport.{}", format.extension()));
+        assert!(report.write_to(&path, format).is_err(), "{:?} should fail", path);
+    }
+    let err = Jobseeker::reports::generate_stats_report(&db, 2026, &missing.join("statistik.docx")).await.expect_err("stats export should fail");
+    assert!(format!("{:#}", err).contains("finns inte"));
+    assert!(!missing.exists());
+}
+
+/// Integration test: `list_drafts` feeds the drafts picker, and only the picked drafts end
+/// up in the report, including one for an ad that wasn't applied to.
+#[tokio::test]
+async fn report_contains_only_the_selected_drafts() {
+    let dir = TempPath::dir("drafts");
+    let db = common::open(&dir.join("test.redb")).await;
+
+    for (id, headline) in [("d1", "Supporttekniker"), ("d2", "Lagerarbetare"), ("d3", "Chaufför")] {
+        let mut ad = common::ad_with(id, serde_json::json!({ "headline": headline }));
+        if id != "d3" { ad.status = Some(Jobseeker::models::AdStatus::Applied); ad.applied_at = Some(Utc::now()); }
+        db.save_job_ad(&ad).await.expect("Failed to save ad");
+        db.save_application_draft(id, &format!("Brev till {}", headline)).await.expect("Failed to save draft");
+    }
+    db.save_application_draft("empty", "  ").await.expect("Failed to save draft");
+
+    let listed = db.list_drafts().await.expect("list failed");
+    assert_eq!(listed.iter().map(|(id, _)| id.as_str()).collect::<Vec<_>>(), ["d3", "d2", "d1"]);
+
+    let now = chrono::Local::now();
+    let options = Jobseeker::reports::ReportOptions { include_jobs: true, include_params: false, include_analysis: false, selected_drafts: Some(vec!["d3".to_string(), "d1".to_string()]) };
+    let text = Jobseeker::reports::activity_report(&db, &Jobseeker::models::AppSettings::default(), now.year(), now.month(), "Testmånad", options).await.to_text();
+    assert!(text.contains("Brev till Chaufför"));
+    assert!(text.contains("Brev till Supporttekniker"));
+    assert!(!text.contains("Brev till Lagerarbetare"));
+    assert_eq!(text.matches("ANSÖKNINGSBREV").count(), 1);
+}
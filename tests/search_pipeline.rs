@@ -0,0 +1,78 @@
+use chrono::Utc;
+use Jobseeker::search::{run_search, SearchOptions, SearchProfile};
+
+mod common;
+use common::{mock_server, response, TestDb};
+
+fn settings() -> Jobseeker::models::AppSettings {
+    Jobseeker::models::AppSettings {
+        keywords: "support, lager".to_string(),
+        blacklist_keywords: "barnvakt".to_string(),
+        blacklist_employers: "Spamfirma AB".to_string(),
+        locations_p1: String::new(),
+        use_synonyms: false,
+        ..Default::default()
+    }
+}
+
+/// Blacklisted ads are counted and dropped, ads already stored aren't saved again, and a
+/// run where every keyword succeeded moves the zone's watermark on.
+#[tokio::test]
+async fn run_search_saves_new_ads_and_filters_blacklisted() {
+    let support = r#"{"hits":[
+        {"id":"s1","headline":"Supporttekniker","publication_date":"2026-03-02T08:00:00"},
+        {"id":"s2","headline":"Barnvakt sökes","publication_date":"2026-03-02T08:00:00"},
+        {"id":"s3","headline":"IT-support","employer":{"name":"Spamfirma AB"},"publication_date":"2026-03-02T08:00:00"}
+    ]}"#;
+    let lager = r#"{"hits":[
+        {"id":"s1","headline":"Supporttekniker","publication_date":"2026-03-02T08:00:00"},
+        {"id":"l1","headline":"Lagerarbetare","publication_date":"2026-03-03T08:00:00"}
+    ]}"#;
+    let base = mock_server(vec![response("200 OK", "", support), response("200 OK", "", lager)]).await;
+    let client = Jobseeker::api::JobSearchClient::new().with_base_url(&base);
+    let db = TestDb::new("search_ok").await;
+
+    let outcome = run_search(&client, &db, &SearchProfile::Zone(1), &settings(), &SearchOptions::default()).await;
+    assert_eq!(outcome.new_count, 2);
+    assert!(outcome.failed_keywords.is_empty());
+    assert_eq!(outcome.per_keyword, vec![("support".to_string(), Some(1)), ("lager".to_string(), Some(1))]);
+    assert_eq!(outcome.filter_stats.api_returned, 5);
+    assert_eq!(outcome.filter_stats.blacklisted_employer, 1);
+    assert_eq!(outcome.filter_stats.blacklisted_words.get("barnvakt"), Some(&1));
+    assert_eq!(outcome.filter_stats.already_saved, 1);
+    assert!(db.get_job_ad("l1").await.unwrap().is_some());
+    assert!(db.get_job_ad("s2").await.unwrap().is_none());
+    assert!(db.get_search_watermark("p1").await.unwrap().is_some());
+}
+
+/// A failing keyword is reported and keeps the watermark where it was; the checkpoint
+/// remembers the keyword that did succeed, so the next run skips it.
+#[tokio::test]
+async fn failed_keyword_keeps_the_watermark_and_checkpoints_the_rest() {
+    let support = r#"{"hits":[{"id":"s1","headline":"Supporttekniker","publication_date":"2026-03-02T08:00:00"}]}"#;
+    let base = mock_server(vec![response("200 OK", "", support), response("500 Internal Server Error", "", "{}")]).await;
+    let client = Jobseeker::api::JobSearchClient::new().with_base_url(&base);
+    let db = TestDb::new("search_fail").await;
+
+    let outcome = run_search(&client, &db, &SearchProfile::Zone(1), &settings(), &SearchOptions::default()).await;
+    assert_eq!(outcome.new_count, 1);
+    assert_eq!(outcome.failed_keywords.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>(), vec!["lager"]);
+    assert_eq!(outcome.per_keyword.last(), Some(&("lager".to_string(), None)));
+    assert!(db.get_search_watermark("p1").await.unwrap().is_none());
+    assert_eq!(db.get_search_checkpoint("p1").await.unwrap().unwrap().completed_keywords, vec!["support".to_string()]);
+}
+
+/// Integration test: search checkpoints are stored per profile and cleared together with
+/// the watermarks.
+#[tokio::test]
+async fn search_checkpoint_round_trips_and_clears() {
+    let db = TestDb::new("checkpoint").await;
+
+    let checkpoint = Jobseeker::db::SearchCheckpoint { started_at: Utc::now(), completed_keywords: vec!["it".to_string(), "helpdesk".to_string()] };
+    db.set_search_checkpoint("p1", &checkpoint).await.expect("set failed");
+    assert_eq!(db.get_search_checkpoint("p1").await.expect("get failed"), Some(checkpoint));
+    assert!(db.get_search_checkpoint("p2").await.expect("get failed").is_none());
+
+    db.clear_search_watermarks().await.expect("clear failed");
+    assert!(db.get_search_checkpoint("p1").await.expect("get failed").is_none());
+}
@@ -1,5 +1,7 @@
 use std::fs;
-use chrono::Utc;
+
+mod common;
+use common::{TempPath, TestDb};
 
 /// Integration test: Save settings (including locations_p1/2/3) and load them back.
 ///
@@ -7,25 +9,7 @@ use chrono::Utc;
 /// three priority location fields through a roundtrip.
 #[tokio::test]
 async fn settings_roundtrip() {
-    // Build a unique temp path to avoid collisions between test runs
-    let mut tmp = std::env::temp_dir();
-    tmp.push(format!(
-        "jobseeker_test_{}_{}.redb",
-        std::process::id(),
-        Utc::now().timestamp()
-    ));
-    let path_str = tmp
-        .to_str()
-        .expect("Temp path should be valid UTF-8")
-        .to_string();
-
-    // Ensure no leftover DB file present
-    let _ = fs::remove_file(&tmp);
-
-    // Initialize DB
-    let db = Jobseeker::db::Db::new(&path_str)
-        .await
-        .expect("Failed to create/open test DB");
+    let db = TestDb::new("settings").await;
 
     // Prepare settings with all three priority locations populated
     let settings = Jobseeker::models::AppSettings {
@@ -36,6 +20,8 @@ async fn settings_roundtrip() {
         locations_p3: "".to_string(),
         my_profile: "Testprofil".to_string(),
         ollama_url: "http://localhost:11434/v1".to_string(),
+        theme: Jobseeker::models::ThemeMode::Light,
+        ..Default::default()
     };
 
     // Save and load back
@@ -53,8 +39,75 @@ async fn settings_roundtrip() {
     assert_eq!(loaded.locations_p1, settings.locations_p1);
     assert_eq!(loaded.locations_p2, settings.locations_p2);
     assert_eq!(loaded.locations_p3, settings.locations_p3);
+    assert_eq!(loaded.theme, Jobseeker::models::ThemeMode::Light);
+}
+
+/// Settings exported to a file import back unchanged (minus the machine-specific sync
+/// folder), and a file with another version is rejected as a whole.
+#[test]
+fn settings_file_export_import() {
+    let file = TempPath::new("settings_export", "json");
+    let path = file.path();
+    let settings = Jobseeker::models::AppSettings {
+        keywords: "it, support".to_string(),
+        blacklist_employers: "Bemanning AB".to_string(),
+        sync_path: "/home/anna/Sync".to_string(),
+        ..Default::default()
+    };
+
+    settings.export_to(path).expect("export failed");
+    let imported = Jobseeker::models::AppSettings::import_from(path).expect("import failed");
+    assert_eq!(imported.keywords, settings.keywords);
+    assert_eq!(imported.blacklist_employers, settings.blacklist_employers);
+    assert!(imported.sync_path.is_empty());
+
+    fs::write(path, r#"{"version": 99, "settings": {}}"#).expect("write failed");
+    let err = Jobseeker::models::AppSettings::import_from(path).expect_err("version 99 should be rejected");
+    assert!(err.to_string().contains("version 99"));
+}
+
+/// A version-1 blob (no `version`, none of the later fields, no sync_path) keeps what it
+/// has and gets defaults for the rest instead of failing to load.
+#[test]
+fn v1_settings_blob_is_migrated() {
+    let v1 = serde_json::json!({
+        "keywords": "lager, truck",
+        "blacklist_keywords": "körkort",
+        "locations_p1": "1283",
+        "locations_p2": "",
+        "locations_p3": "",
+        "my_profile": "Gammal profil",
+        "ollama_url": "http://localhost:11434/v1",
+        "app_min_count": 4,
+        "app_goal_count": 8,
+        "show_motivation": false
+    });
+    let migrated = Jobseeker::models::AppSettings::migrate(v1).expect("v1 blob should migrate");
+    let defaults = Jobseeker::models::AppSettings::default();
+    assert_eq!(migrated.version, Jobseeker::models::SETTINGS_VERSION);
+    assert_eq!(migrated.keywords, "lager, truck");
+    assert_eq!(migrated.app_goal_count, 8);
+    assert!(!migrated.show_motivation);
+    assert_eq!(migrated.sync_path, defaults.sync_path);
+    assert_eq!(migrated.max_inbox_rows, defaults.max_inbox_rows);
+    assert_eq!(migrated.export_columns, defaults.export_columns);
+    assert_eq!(migrated.theme, Jobseeker::models::ThemeMode::Dark);
+}
+
+/// Integration test: a fresh database is a first run and gets the defaults seeded once; a
+/// database that already holds ads is not treated as new.
+#[tokio::test]
+async fn first_run_seeds_default_settings_once() {
+    let db = TestDb::new("first_run").await;
+    assert!(db.is_first_run().await.expect("first-run check failed"));
+    assert!(db.load_settings().await.expect("load failed").is_none());
+    assert!(db.seed_first_run().await.expect("seed failed"));
+    let seeded = db.load_settings().await.expect("load failed").expect("settings should be stored");
+    assert_eq!(seeded.keywords, Jobseeker::models::AppSettings::default().keywords);
+    assert!(!db.is_first_run().await.expect("first-run check failed"));
+    assert!(!db.seed_first_run().await.expect("seed failed"));
 
-    // Cleanup: drop DB and remove file
-    drop(db);
-    let _ = fs::remove_file(&tmp);
+    // An install whose settings row went missing but that has ads is not a first run
+    let db = TestDb::with_ads("first_run_ads", &[common::ad("1")]).await;
+    assert!(!db.is_first_run().await.expect("first-run check failed"));
 }
@@ -0,0 +1,55 @@
+use chrono::{Duration, Utc};
+use Jobseeker::models::AdStatus;
+
+mod common;
+use common::TestDb;
+
+/// Integration test: while the app holds the database, `open_db_exclusive` reports the lock
+/// and `open_db_snapshot` reads a copy instead, which is removed again afterwards.
+#[tokio::test]
+async fn snapshot_reads_a_database_held_by_the_app() {
+    // Stands in for the running app, which keeps the file locked
+    let (app, file) = TestDb::with_ads("snapshot", &[common::ad("held")]).await.into_parts();
+
+    assert!(matches!(Jobseeker::open_db_exclusive(file.path()), Err(Jobseeker::DbError::BusyLock(_))));
+
+    let snapshot = Jobseeker::db::open_db_snapshot(file.path()).expect("snapshot failed");
+    assert!(snapshot.is_copy());
+    assert_eq!(Jobseeker::db::corpus_stats(&snapshot).expect("stats failed").total, 1);
+    drop(snapshot);
+
+    // The app's own database is untouched and still usable
+    assert!(app.get_job_ad("held").await.expect("read failed").is_some());
+    drop(app);
+    let direct = Jobseeker::db::open_db_snapshot(file.path()).expect("open failed");
+    assert!(!direct.is_copy());
+}
+
+/// Integration test: `corpus_stats` counts by status and spans the fetch times, reading a
+/// database opened read-only the way `db_check --stats` does.
+#[tokio::test]
+async fn corpus_stats_counts_statuses_on_a_readonly_database() {
+    let ad = |id: &str, age_days: i64, status: Option<AdStatus>| {
+        let mut ad = common::ad(id);
+        ad.internal_created_at = Utc::now() - Duration::days(age_days);
+        ad.status = status;
+        ad
+    };
+    let oldest = ad("old", 30, None);
+    let newest = ad("new", 0, Some(AdStatus::Applied));
+    let ads = [oldest.clone(), ad("rejected", 5, Some(AdStatus::Rejected)), ad("plain", 2, None), newest.clone()];
+    let (db, file) = TestDb::with_ads("corpus", &ads).await.into_parts();
+    drop(db);
+
+    let snapshot = Jobseeker::db::open_db_snapshot(file.path()).expect("snapshot open failed");
+    let stats = Jobseeker::db::corpus_stats(&snapshot).expect("stats failed");
+    assert_eq!(stats.total, 4);
+    assert_eq!(stats.unreadable, 0);
+    let count = |status: AdStatus| stats.by_status.iter().find(|(s, _)| *s == status).map(|(_, n)| *n);
+    assert_eq!(count(AdStatus::New), Some(2));
+    assert_eq!(count(AdStatus::Rejected), Some(1));
+    assert_eq!(count(AdStatus::Applied), Some(1));
+    assert_eq!(count(AdStatus::Bookmarked), Some(0));
+    assert_eq!(stats.oldest, Some(oldest.internal_created_at));
+    assert_eq!(stats.newest, Some(newest.internal_created_at));
+}
@@ -0,0 +1,30 @@
+mod common;
+use common::TestDb;
+
+/// Integration test: snippets can be saved, replaced, listed by name and deleted, and the
+/// AI profile is added as "Min profil" once, even after the user deletes it.
+#[tokio::test]
+async fn snippet_crud_and_profile_seed() {
+    let db = TestDb::new("snippets").await;
+
+    assert!(db.list_snippets().await.unwrap().is_empty());
+    db.save_snippet("Varför jag söker", "Jag vill **utvecklas** inom support.").await.unwrap();
+    db.save_snippet(" avslutning ", "Med vänliga hälsningar").await.unwrap();
+    db.save_snippet("Varför jag söker", "Jag vill växa inom IT.").await.unwrap();
+    assert!(db.save_snippet("  ", "utan namn").await.is_err());
+    assert_eq!(db.list_snippets().await.unwrap(), vec![
+        ("avslutning".to_string(), "Med vänliga hälsningar".to_string()),
+        ("Varför jag söker".to_string(), "Jag vill växa inom IT.".to_string()),
+    ]);
+
+    assert!(db.delete_snippet("avslutning").await.unwrap());
+    assert!(!db.delete_snippet("avslutning").await.unwrap());
+    assert_eq!(db.list_snippets().await.unwrap().len(), 1);
+
+    assert!(!db.seed_profile_snippet("  ").await.unwrap());
+    assert!(db.seed_profile_snippet("Serviceinriktad med IT-erfarenhet.").await.unwrap());
+    assert!(db.list_snippets().await.unwrap().contains(&(Jobseeker::db::PROFILE_SNIPPET_NAME.to_string(), "Serviceinriktad med IT-erfarenhet.".to_string())));
+    db.delete_snippet(Jobseeker::db::PROFILE_SNIPPET_NAME).await.unwrap();
+    assert!(!db.seed_profile_snippet("Serviceinriktad med IT-erfarenhet.").await.unwrap());
+    assert_eq!(db.list_snippets().await.unwrap().len(), 1);
+}
@@ -0,0 +1,50 @@
+use Jobseeker::models::AdStatus;
+
+mod common;
+use common::{ad, TestDb};
+
+/// Integration test: starring never changes the status, survives status changes, and the
+/// starred list cuts across statuses (rejected included).
+#[tokio::test]
+async fn starring_is_orthogonal_to_status() {
+    let db = TestDb::with_ads("starred", &[ad("applied"), ad("rejected"), ad("plain")]).await;
+    db.update_ad_status("applied", Some(AdStatus::Applied)).await.expect("status failed");
+    db.update_ad_status("rejected", Some(AdStatus::Rejected)).await.expect("status failed");
+
+    db.set_starred("applied", true).await.expect("star failed");
+    db.set_starred("rejected", true).await.expect("star failed");
+    let applied = db.get_job_ad("applied").await.expect("read failed").expect("missing ad");
+    assert!(applied.starred);
+    assert_eq!(applied.status, Some(AdStatus::Applied));
+
+    db.update_ad_status("applied", None).await.expect("status failed");
+    assert!(db.get_job_ad("applied").await.expect("read failed").expect("missing ad").starred);
+
+    let mut starred: Vec<String> = db.get_starred_jobs().await.expect("starred failed").into_iter().map(|a| a.id).collect();
+    starred.sort();
+    assert_eq!(starred, vec!["applied".to_string(), "rejected".to_string()]);
+
+    // Starred ads are kept when the inbox is cleared
+    db.clear_non_bookmarked().await.expect("clear failed");
+    assert!(db.get_job_ad("rejected").await.expect("read failed").is_some());
+    assert!(db.get_job_ad("plain").await.expect("read failed").is_none());
+}
+
+/// Pinning is stored like starring: it survives status changes, the pinned list cuts across
+/// statuses and clearing the inbox keeps pinned ads.
+#[tokio::test]
+async fn pinned_ads_are_listed_and_kept() {
+    let db = TestDb::with_ads("pinned", &[ad("pinned"), ad("plain")]).await;
+    db.set_pinned("pinned", true).await.expect("pin failed");
+    db.update_ad_status("pinned", Some(AdStatus::Rejected)).await.expect("status failed");
+
+    let pinned: Vec<String> = db.get_pinned_jobs().await.expect("pinned failed").into_iter().map(|a| a.id).collect();
+    assert_eq!(pinned, vec!["pinned".to_string()]);
+
+    db.clear_non_bookmarked().await.expect("clear failed");
+    assert!(db.get_job_ad("pinned").await.expect("read failed").is_some());
+    assert!(db.get_job_ad("plain").await.expect("read failed").is_none());
+
+    db.set_pinned("pinned", false).await.expect("unpin failed");
+    assert!(db.get_pinned_jobs().await.expect("pinned failed").is_empty());
+}
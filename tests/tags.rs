@@ -0,0 +1,28 @@
+use Jobseeker::db::JobFilter;
+use Jobseeker::models::AdStatus;
+
+mod common;
+use common::{ad, TestDb};
+
+/// Integration test: tags on a bookmarked ad survive `clear_non_bookmarked`, can be
+/// filtered on, and are counted by `tag_counts`.
+#[tokio::test]
+async fn tags_survive_clear_and_filter() {
+    let db = TestDb::with_ads("tags", &[ad("kept"), ad("dropped")]).await;
+    db.update_ad_status("kept", Some(AdStatus::Bookmarked)).await.expect("Failed to bookmark");
+    db.set_tags("kept", Jobseeker::models::parse_tags("Distans, hög lön")).await.expect("Failed to tag");
+    db.set_tags("dropped", vec!["distans".to_string()]).await.expect("Failed to tag");
+
+    assert_eq!(db.tag_counts().await.expect("tag_counts failed").first(), Some(&("distans".to_string(), 2)));
+
+    db.clear_non_bookmarked().await.expect("clear failed");
+
+    let kept = db.get_job_ad("kept").await.expect("read failed").expect("bookmarked ad was removed");
+    assert_eq!(kept.tags, vec!["distans".to_string(), "hög lön".to_string()]);
+    assert!(db.get_job_ad("dropped").await.expect("read failed").is_none());
+
+    let tagged = db.get_filtered_jobs(JobFilter { tag: Some("hög lön"), ..Default::default() }).await.expect("filter failed");
+    assert_eq!(tagged.len(), 1);
+    let untagged = db.get_filtered_jobs(JobFilter { tag: Some("chansning"), ..Default::default() }).await.expect("filter failed");
+    assert!(untagged.is_empty());
+}
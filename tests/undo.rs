@@ -0,0 +1,64 @@
+use chrono::{TimeZone, Utc};
+use Jobseeker::models::AdStatus;
+use Jobseeker::undo::{restore, PreviousStatus, UndoStack};
+
+mod common;
+use common::{ad, TestDb};
+
+async fn test_db(name: &str) -> TestDb {
+    TestDb::with_ads(name, &[ad("a"), ad("b")]).await
+}
+
+/// Integration test: undoing a (bulk) reject brings both ads back as new.
+#[tokio::test]
+async fn undo_of_a_reject_restores_the_ads() {
+    let db = test_db("undo_reject").await;
+    let mut stack = UndoStack::default();
+    let mut before = Vec::new();
+    for id in ["a", "b"] { before.push(PreviousStatus::of(&db.get_job_ad(id).await.unwrap().unwrap())); }
+    db.update_ad_status_bulk(&["a".to_string(), "b".to_string()], Some(AdStatus::Rejected)).await.expect("bulk failed");
+    stack.push(before);
+
+    let change = stack.pop().expect("one action to undo");
+    assert!(stack.is_empty());
+    let restored = restore(&db, &change).await.expect("undo failed");
+    assert_eq!(restored.len(), 2);
+    assert!(restored.iter().all(|ad| ad.status.is_none()));
+    assert_eq!(db.get_job_ad("a").await.unwrap().and_then(|ad| ad.status), None);
+}
+
+/// Integration test: undoing an apply puts the ad back as saved without an application
+/// date, and undoing a change away from Applied brings the original date back.
+#[tokio::test]
+async fn undo_of_an_apply_restores_the_previous_status_and_date() {
+    let db = test_db("undo_apply").await;
+    db.update_ad_status("a", Some(AdStatus::Bookmarked)).await.unwrap();
+    let before = PreviousStatus::of(&db.get_job_ad("a").await.unwrap().unwrap());
+    db.update_ad_status("a", Some(AdStatus::Applied)).await.unwrap();
+    restore(&db, &[before]).await.expect("undo failed");
+    let ad = db.get_job_ad("a").await.unwrap().unwrap();
+    assert_eq!(ad.status, Some(AdStatus::Bookmarked));
+    assert!(ad.applied_at.is_none());
+
+    let applied_on = Utc.with_ymd_and_hms(2026, 1, 12, 9, 0, 0).unwrap();
+    db.update_ad_status_with_date("b", Some(AdStatus::Applied), Some(applied_on)).await.unwrap();
+    let before = PreviousStatus::of(&db.get_job_ad("b").await.unwrap().unwrap());
+    db.update_ad_status("b", Some(AdStatus::Rejected)).await.unwrap();
+    restore(&db, &[before]).await.expect("undo failed");
+    let ad = db.get_job_ad("b").await.unwrap().unwrap();
+    assert_eq!(ad.status, Some(AdStatus::Applied));
+    assert_eq!(ad.applied_at, Some(applied_on));
+}
+
+#[test]
+fn undo_stack_keeps_only_the_last_actions() {
+    let mut stack = UndoStack::default();
+    for i in 0..Jobseeker::undo::UNDO_DEPTH + 3 {
+        stack.push(vec![PreviousStatus { id: i.to_string(), status: None, applied_at: None }]);
+    }
+    stack.push(Vec::new());
+    let mut popped = Vec::new();
+    while let Some(change) = stack.pop() { popped.push(change[0].id.clone()); }
+    assert_eq!(popped.len(), Jobseeker::undo::UNDO_DEPTH);
+    assert_eq!(popped.first().map(String::as_str), Some("12"));
+}